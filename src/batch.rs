@@ -0,0 +1,137 @@
+//! Many independent runs summarized together, started with `--batch`.
+
+use std::fmt;
+
+use rayon::prelude::*;
+
+use crate::game::{create_seeded_grid, run_headless_metrics, SimulationConfig};
+use crate::metrics::{SimulationMetrics, TerminationReason};
+
+/// Runs `count` simulations headless, the `i`-th one seeded with the seed of `config` plus `i`.
+///
+/// With `parallel`, the runs are spread over the threads of rayon's global pool; the metrics are
+/// returned in seed order either way.
+pub fn run_batch(config: SimulationConfig, count: usize, parallel: bool) -> Vec<SimulationMetrics> {
+    let run = |index: usize| {
+        let config = SimulationConfig { seed: config.seed.wrapping_add(index as u64), ..config.clone() };
+        run_headless_metrics(&config, create_seeded_grid(&config))
+    };
+    if parallel {
+        (0..count).into_par_iter().map(run).collect()
+    } else {
+        (0..count).map(run).collect()
+    }
+}
+
+/// Mean, minimum and maximum of one statistic over the runs of a batch.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spread {
+    pub mean: f64,
+    pub min: u64,
+    pub max: u64,
+}
+
+impl Spread {
+    fn of(values: impl Iterator<Item = u64> + Clone) -> Option<Self> {
+        let count = values.clone().count();
+        let min = values.clone().min()?;
+        let max = values.clone().max()?;
+        let mean = values.map(|value| value as f64).sum::<f64>() / count as f64;
+        Some(Spread { mean, min, max })
+    }
+}
+
+/// Aggregate statistics of a batch of runs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BatchSummary {
+    pub runs: usize,
+    pub total_iterations: Spread,
+    pub peak_population: Spread,
+    pub extinctions: usize,
+}
+
+impl BatchSummary {
+    /// Summarizes the runs, `None` when there are none.
+    pub fn new(metrics: &[SimulationMetrics]) -> Option<Self> {
+        Some(BatchSummary {
+            runs: metrics.len(),
+            total_iterations: Spread::of(metrics.iter().map(|run| run.total_iterations))?,
+            peak_population: Spread::of(metrics.iter().map(|run| run.peak_population as u64))?,
+            extinctions: metrics.iter().filter(|run| run.termination_reason == TerminationReason::Extinction).count(),
+        })
+    }
+
+    /// Fraction of the runs that went extinct.
+    pub fn extinction_rate(&self) -> f64 {
+        self.extinctions as f64 / self.runs as f64
+    }
+}
+
+impl fmt::Display for BatchSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Batch of {} runs:", self.runs)?;
+        writeln!(f, "  {:<18}{:>10}{:>10}{:>10}", "", "mean", "min", "max")?;
+        for (name, spread) in [("Total iterations", self.total_iterations), ("Peak population", self.peak_population)] {
+            writeln!(f, "  {:<18}{:>10.1}{:>10}{:>10}", name, spread.mean, spread.min, spread.max)?;
+        }
+        write!(f, "  Extinction rate:  {:.1}% ({} of {})", 100.0 * self.extinction_rate(), self.extinctions, self.runs)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn metrics(total_iterations: u64, peak_population: usize, termination_reason: TerminationReason) -> SimulationMetrics {
+        SimulationMetrics {
+            total_iterations,
+            peak_population,
+            peak_iteration: 0,
+            final_population: 0,
+            mean_entropy: 0.0,
+            elapsed_wall_time: Duration::ZERO,
+            termination_reason,
+            births: 0,
+            deaths: 0,
+            region: None,
+            trace: None,
+            final_objects: None,
+        }
+    }
+
+    #[test]
+    fn test_run_batch_returns_a_run_per_seed() {
+        let config = SimulationConfig { grid_size: 12, seed: 7, max_iterations: Some(200), ..SimulationConfig::default() };
+
+        let runs = run_batch(config.clone(), 5, false);
+
+        assert_eq!(runs.len(), 5);
+        for run in &runs {
+            assert!(run.total_iterations <= 200);
+            assert_eq!(run.outcome().iteration(), run.total_iterations);
+        }
+        let parallel = run_batch(config, 5, true);
+        let reasons = |runs: &[SimulationMetrics]| runs.iter().map(|run| (run.termination_reason, run.total_iterations)).collect::<Vec<_>>();
+        assert_eq!(reasons(&parallel), reasons(&runs));
+    }
+
+    #[test]
+    fn test_batch_summary() {
+        let runs = [
+            metrics(10, 40, TerminationReason::Extinction),
+            metrics(30, 20, TerminationReason::SteadyState(2)),
+            metrics(20, 60, TerminationReason::Extinction),
+            metrics(100, 80, TerminationReason::MaxIterations),
+        ];
+
+        let summary = BatchSummary::new(&runs).unwrap();
+
+        assert_eq!(summary.total_iterations, Spread { mean: 40.0, min: 10, max: 100 });
+        assert_eq!(summary.peak_population, Spread { mean: 50.0, min: 20, max: 80 });
+        assert_eq!(summary.extinction_rate(), 0.5);
+        assert!(summary.to_string().ends_with("Extinction rate:  50.0% (2 of 4)"));
+        assert_eq!(BatchSummary::new(&[]), None);
+    }
+}
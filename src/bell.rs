@@ -0,0 +1,140 @@
+//! Terminal bell rung on notable events of a run, opted into with `--bell-on`.
+
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+/// Event of a run that rings the terminal bell.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BellEvent {
+    /// The last live cells died.
+    Extinction,
+    /// The grid repeated an earlier state, whether the run stops there or not.
+    SteadyState,
+    /// The population crossed this many live cells, up or down.
+    Population(usize),
+}
+
+impl fmt::Display for BellEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BellEvent::Extinction => write!(f, "extinction"),
+            BellEvent::SteadyState => write!(f, "steady-state"),
+            BellEvent::Population(threshold) => write!(f, "population:{}", threshold),
+        }
+    }
+}
+
+/// Error returned when parsing an unknown bell event.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseBellEventError {
+    pub input: String,
+}
+
+impl fmt::Display for ParseBellEventError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Unknown bell event `{}`, expected extinction, steady-state or population:N", self.input)
+    }
+}
+
+impl Error for ParseBellEventError {}
+
+impl FromStr for BellEvent {
+    type Err = ParseBellEventError;
+
+    /// Parses an event written as by [`fmt::Display`].
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "extinction" => Ok(BellEvent::Extinction),
+            "steady-state" => Ok(BellEvent::SteadyState),
+            _ => text
+                .strip_prefix("population:")
+                .and_then(|threshold| threshold.parse().ok())
+                .map(BellEvent::Population)
+                .ok_or_else(|| ParseBellEventError { input: text.to_string() }),
+        }
+    }
+}
+
+/// Follows the population of a run generation by generation and tells when the bell should ring.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BellWatcher {
+    events: Vec<BellEvent>,
+    previous: Option<usize>,
+}
+
+impl BellWatcher {
+    pub fn new(events: &[BellEvent]) -> Self {
+        BellWatcher { events: events.to_vec(), previous: None }
+    }
+
+    /// Records the population of the next generation, returning whether it went extinct or
+    /// crossed a threshold since the previous one.
+    pub fn observe(&mut self, population: usize) -> bool {
+        let Some(previous) = self.previous.replace(population) else {
+            return false;
+        };
+        self.events.iter().any(|&event| match event {
+            BellEvent::Extinction => previous > 0 && population == 0,
+            BellEvent::SteadyState => false,
+            BellEvent::Population(threshold) => crossed_threshold(previous, population, threshold),
+        })
+    }
+
+    /// Whether finding a steady state rings the bell.
+    pub fn on_steady_state(&self) -> bool {
+        self.events.contains(&BellEvent::SteadyState)
+    }
+}
+
+/// Whether the population went from below `threshold` to at least `threshold`, or back.
+pub fn crossed_threshold(previous: usize, current: usize, threshold: usize) -> bool {
+    (previous >= threshold) != (current >= threshold)
+}
+
+/// Rings the terminal bell.
+pub fn ring() {
+    print!("\x07");
+    io::stdout().flush().expect("Failed to flush stdout");
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bell_event() {
+        for event in [BellEvent::Extinction, BellEvent::SteadyState, BellEvent::Population(250)] {
+            assert_eq!(event.to_string().parse(), Ok(event));
+        }
+        assert!("population".parse::<BellEvent>().is_err());
+        assert!("population:many".parse::<BellEvent>().is_err());
+    }
+
+    #[test]
+    fn test_rings_when_population_crosses_threshold() {
+        let mut watcher = BellWatcher::new(&[BellEvent::Population(10)]);
+
+        let rings: Vec<bool> = [5, 9, 10, 12, 10, 9, 9, 30].into_iter().map(|population| watcher.observe(population)).collect();
+
+        assert_eq!(rings, [false, false, true, false, false, true, false, true]);
+    }
+
+    #[test]
+    fn test_rings_on_extinction_only_when_asked() {
+        let mut extinction = BellWatcher::new(&[BellEvent::Extinction]);
+        let mut threshold = BellWatcher::new(&[BellEvent::Population(100)]);
+
+        for watcher in [&mut extinction, &mut threshold] {
+            watcher.observe(3);
+        }
+
+        assert!(extinction.observe(0));
+        assert!(!extinction.observe(0));
+        assert!(!threshold.observe(0));
+        assert!(!extinction.on_steady_state());
+        assert!(BellWatcher::new(&[BellEvent::SteadyState]).on_steady_state());
+    }
+}
@@ -0,0 +1,421 @@
+use std::error::Error;
+use std::fmt;
+use std::time::Duration;
+
+use crate::game::{create_seeded_grid, grid_dimensions, run_headless, run_with_observer, Grid, Mirror, SeedMode, SimulationConfig, Topology, Universe};
+use crate::grid_ops::{check_region, RegionError};
+use crate::metrics::SimulationOutcome;
+use crate::packed::MAX_PACKED_WIDTH;
+use crate::rules::{Rules, SharedRuleFn};
+
+/// Number of rows and columns of the grid unless set otherwise.
+pub const DEFAULT_GRID_SIZE: usize = 30;
+
+/// Error returned for an invalid simulation configuration.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ConfigError {
+    /// The grid has no rows or no columns.
+    EmptyGrid,
+    /// The initial density is outside of `0.0..=1.0`.
+    Density(f64),
+    /// The stabilization window is shorter than 2 generations.
+    StabilizeWindow(usize),
+    /// Packed stepping was requested for a grid that doesn't support it.
+    Packed,
+    /// The generations are to be computed by zero threads.
+    NoThreads,
+    /// The tracked region doesn't fit in the grid.
+    Region(RegionError),
+    /// The traced cell `(x, y)` is outside of a grid of the given `(rows, cols)`.
+    Trace { cell: (usize, usize), dimensions: (usize, usize) },
+    /// The environment variable of this name is set to a value that doesn't parse.
+    Environment(&'static str),
+    /// A grid of these `(rows, cols)` is too small to seed a random number of live cells in.
+    TooSmallToSeed { dimensions: (usize, usize) },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::EmptyGrid => write!(f, "Grid must have at least one row and one column"),
+            ConfigError::Density(density) => write!(f, "Density must be between 0.0 and 1.0, but got {}", density),
+            ConfigError::StabilizeWindow(window) => {
+                write!(f, "Stabilization window must be at least 2, but got {}", window)
+            }
+            ConfigError::NoThreads => write!(f, "At least one thread is needed to compute generations"),
+            ConfigError::Region(err) => err.fmt(f),
+            ConfigError::Trace { cell: (x, y), dimensions: (rows, cols) } => {
+                write!(f, "Traced cell ({}, {}) is outside of the {}x{} grid", x, y, rows, cols)
+            }
+            ConfigError::Environment(name) => write!(f, "Invalid value of environment variable {}", name),
+            ConfigError::TooSmallToSeed { dimensions: (rows, cols) } => write!(
+                f,
+                "A {}x{} grid is too small to seed at random; use a larger grid, --density or a pattern",
+                rows, cols
+            ),
+            ConfigError::Packed => write!(
+                f,
+                "Packed stepping requires Conway's rule, the finite topology and at most {} columns",
+                MAX_PACKED_WIDTH
+            ),
+        }
+    }
+}
+
+impl Error for ConfigError {}
+
+impl SimulationConfig {
+    /// Checks that the settings are consistent with each other.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let (rows, cols) = self.dimensions();
+        if rows == 0 || cols == 0 {
+            return Err(ConfigError::EmptyGrid);
+        }
+        if let Some(density) = self.density.filter(|density| !(0.0..=1.0).contains(density)) {
+            return Err(ConfigError::Density(density));
+        }
+        if let Some(window) = self.stabilize.filter(|&window| window < 2) {
+            return Err(ConfigError::StabilizeWindow(window));
+        }
+        if let Some(region) = self.region {
+            check_region(region, (rows, cols)).map_err(ConfigError::Region)?;
+        }
+        if let Some((x, y)) = self.trace.filter(|&(x, y)| x >= cols || y >= rows) {
+            return Err(ConfigError::Trace { cell: (x, y), dimensions: (rows, cols) });
+        }
+        if self.threads == Some(0) {
+            return Err(ConfigError::NoThreads);
+        }
+        if self.packed && (self.topology != Topology::Finite || cols > MAX_PACKED_WIDTH || self.rule != Rules::conway()) {
+            return Err(ConfigError::Packed);
+        }
+        Ok(())
+    }
+
+    /// Checks that a random grid can be seeded with these settings, for runs that don't start from a pattern.
+    ///
+    /// Without a density, the random seeding places at least one live cell per row or column of
+    /// the narrower side, but never more than a fifth of the cells; grids where these bounds cross
+    /// would only get a handful of cells.
+    pub fn validate_seeding(&self) -> Result<(), ConfigError> {
+        let (rows, cols) = self.dimensions();
+        if self.seed_mode == SeedMode::Random && self.density.is_none() && rows * cols / 5 < rows.min(cols) {
+            return Err(ConfigError::TooSmallToSeed { dimensions: (rows, cols) });
+        }
+        Ok(())
+    }
+}
+
+/// Chainable way of configuring a simulation from code.
+///
+/// Unset options keep their defaults: a 30x30 finite grid under Conway's rule, seeded with seed 0.
+///
+/// ```
+/// use rusty_game_of_life::builder::SimulationBuilder;
+///
+/// let universe = SimulationBuilder::new().grid_size(20).seed(7).wrap(true, true).build().unwrap();
+/// assert_eq!(universe.grid().len(), 20);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationBuilder {
+    config: SimulationConfig,
+    initial: Option<Grid>,
+}
+
+impl Default for SimulationBuilder {
+    fn default() -> Self {
+        SimulationBuilder::new()
+    }
+}
+
+impl SimulationBuilder {
+    pub fn new() -> Self {
+        SimulationBuilder {
+            config: SimulationConfig { grid_size: DEFAULT_GRID_SIZE, ..Default::default() },
+            initial: None,
+        }
+    }
+
+    /// Number of rows, and of columns too unless [`grid_width`](Self::grid_width) is set.
+    pub fn grid_size(mut self, grid_size: usize) -> Self {
+        self.config.grid_size = grid_size;
+        self
+    }
+
+    /// Number of columns of a grid that isn't square.
+    pub fn grid_width(mut self, grid_width: impl Into<Option<usize>>) -> Self {
+        self.config.grid_width = grid_width.into();
+        self
+    }
+
+    /// Starts from the given grid instead of a seeded one; the grid dimensions follow it.
+    pub fn initial(mut self, grid: Grid) -> Self {
+        let (rows, cols) = grid_dimensions(&grid);
+        self.config.grid_size = rows;
+        self.config.grid_width = Some(cols);
+        self.initial = Some(grid);
+        self
+    }
+
+    pub fn rule(mut self, rule: Rules) -> Self {
+        self.config.rule = rule;
+        self
+    }
+
+    /// Decides every cell's next state with the given function instead of the rule.
+    pub fn rule_fn(mut self, rule: impl Into<Option<SharedRuleFn>>) -> Self {
+        self.config.rule_fn = rule.into();
+        self
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.config.seed = seed;
+        self
+    }
+
+    pub fn topology(mut self, topology: Topology) -> Self {
+        self.config.topology = topology;
+        self
+    }
+
+    /// Sets which axes wrap around, see [`Topology::from_axes`].
+    pub fn wrap(self, horizontal: bool, vertical: bool) -> Self {
+        self.topology(Topology::from_axes(horizontal, vertical))
+    }
+
+    /// Generation at which the run stops.
+    pub fn max_iterations(mut self, max_iterations: impl Into<Option<u64>>) -> Self {
+        self.config.max_iterations = max_iterations.into();
+        self
+    }
+
+    /// Wall-clock time after which the run stops.
+    pub fn max_runtime(mut self, max_runtime: impl Into<Option<Duration>>) -> Self {
+        self.config.max_runtime = max_runtime.into();
+        self
+    }
+
+    /// Saves a checkpoint every this many generations of the interactive simulation.
+    pub fn checkpoint_interval(mut self, interval: impl Into<Option<u64>>) -> Self {
+        self.config.checkpoint_interval = interval.into();
+        self
+    }
+
+    /// Saves a separately numbered checkpoint every this many generations.
+    pub fn checkpoint_every(mut self, every: impl Into<Option<u64>>) -> Self {
+        self.config.checkpoint_every = every.into();
+        self
+    }
+
+    /// Number of the newest numbered checkpoints left on disk.
+    pub fn keep_checkpoints(mut self, keep: impl Into<Option<usize>>) -> Self {
+        self.config.keep_checkpoints = keep.into();
+        self
+    }
+
+    /// Number of threads computing each generation.
+    pub fn threads(mut self, threads: impl Into<Option<usize>>) -> Self {
+        self.config.threads = threads.into();
+        self
+    }
+
+    /// Stops at steady states, oscillations and extinction.
+    pub fn exit_steady(mut self, exit_steady: bool) -> Self {
+        self.config.exit_steady = exit_steady;
+        self
+    }
+
+    /// Keeps running past the steady state or oscillation found by `exit_steady`, reporting it once.
+    pub fn quiet_steady(mut self, quiet_steady: bool) -> Self {
+        self.config.quiet_steady = quiet_steady;
+        self
+    }
+
+    pub fn warmup(mut self, generations: u64) -> Self {
+        self.config.warmup = generations;
+        self
+    }
+
+    /// Probability of each cell being alive in the seeded grid.
+    pub fn density(mut self, density: impl Into<Option<f64>>) -> Self {
+        self.config.density = density.into();
+        self
+    }
+
+    pub fn invert(mut self, invert: bool) -> Self {
+        self.config.invert = invert;
+        self
+    }
+
+    pub fn mirror(mut self, mirror: impl Into<Option<Mirror>>) -> Self {
+        self.config.mirror = mirror.into();
+        self
+    }
+
+    pub fn seed_mode(mut self, seed_mode: SeedMode) -> Self {
+        self.config.seed_mode = seed_mode;
+        self
+    }
+
+    pub fn packed(mut self, packed: bool) -> Self {
+        self.config.packed = packed;
+        self
+    }
+
+    pub fn active_cells(mut self, active_cells: bool) -> Self {
+        self.config.active_cells = active_cells;
+        self
+    }
+
+    pub fn incremental_counts(mut self, incremental_counts: bool) -> Self {
+        self.config.incremental_counts = incremental_counts;
+        self
+    }
+
+    pub fn dead_border(mut self, dead_border: bool) -> Self {
+        self.config.dead_border = dead_border;
+        self
+    }
+
+    pub fn full_history(mut self, full_history: bool) -> Self {
+        self.config.full_history = full_history;
+        self
+    }
+
+    /// Stops once the population barely changes over this many generations.
+    pub fn stabilize(mut self, window: impl Into<Option<usize>>) -> Self {
+        self.config.stabilize = window.into();
+        self
+    }
+
+    /// Stops as soon as a generation has no births and no deaths.
+    pub fn terminate_on_quiescence(mut self, terminate_on_quiescence: bool) -> Self {
+        self.config.terminate_on_quiescence = terminate_on_quiescence;
+        self
+    }
+
+    /// Rectangle `(r1, c1, r2, c2)` whose population is tracked next to the whole grid's.
+    pub fn region(mut self, region: impl Into<Option<(usize, usize, usize, usize)>>) -> Self {
+        self.config.region = region.into();
+        self
+    }
+
+    /// Cell `(x, y)`, at column x of row y, whose state and neighbor count are recorded every generation.
+    pub fn trace(mut self, cell: impl Into<Option<(usize, usize)>>) -> Self {
+        self.config.trace = cell.into();
+        self
+    }
+
+    /// Validates and returns the configuration.
+    pub fn config(&self) -> Result<SimulationConfig, ConfigError> {
+        self.config.validate()?;
+        Ok(self.config.clone())
+    }
+
+    /// Validates the configuration and returns the initial grid.
+    fn initial_grid(&self) -> Result<(SimulationConfig, Grid), ConfigError> {
+        let config = self.config()?;
+        let initial = self.initial.clone().unwrap_or_else(|| create_seeded_grid(&config));
+        Ok((config, initial))
+    }
+
+    /// Creates the universe at its initial state.
+    pub fn build(&self) -> Result<Universe, ConfigError> {
+        let (config, initial) = self.initial_grid()?;
+        Ok(Universe::new(initial, config.topology)
+            .with_rules(config.rule)
+            .with_rule_fn(config.rule_fn)
+            .with_packed(config.packed)
+            .with_active_cells(config.active_cells)
+            .with_incremental_counts(config.incremental_counts)
+            .with_dead_border(config.dead_border)
+            .with_threads(config.threads.unwrap_or(1)))
+    }
+
+    /// Runs the simulation headless until it terminates, see [`run_headless`].
+    pub fn run(&self) -> Result<SimulationOutcome, ConfigError> {
+        let (config, initial) = self.initial_grid()?;
+        Ok(run_headless(&config, initial, config.iteration_limit()).0)
+    }
+
+    /// Runs the simulation headless, calling `observer` with the grid and its generation after every step.
+    pub fn run_with_observer(&self, observer: impl FnMut(&Grid, u64)) -> Result<SimulationOutcome, ConfigError> {
+        let (config, initial) = self.initial_grid()?;
+        Ok(run_with_observer(&config, initial, config.iteration_limit(), observer).0)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::parse_grid;
+
+    #[test]
+    fn test_builder_defaults() {
+        let config = SimulationBuilder::new().config().unwrap();
+
+        assert_eq!(config.dimensions(), (DEFAULT_GRID_SIZE, DEFAULT_GRID_SIZE));
+        assert_eq!(config.topology, Topology::Finite);
+        assert_eq!(config.rule, Rules::conway());
+        assert_eq!(config.seed, 0);
+        assert_eq!(config.max_iterations, None);
+        assert!(!config.exit_steady && !config.packed);
+    }
+
+    #[test]
+    fn test_builder_builds_seeded_universe() {
+        let builder = SimulationBuilder::new().grid_size(12).grid_width(20).seed(5).wrap(true, false);
+
+        let universe = builder.build().unwrap();
+
+        let config = builder.config().unwrap();
+        assert_eq!(config.topology, Topology::CylindricalHorizontal);
+        assert_eq!(universe.grid(), &create_seeded_grid(&config));
+        assert_eq!(universe.generation(), 0);
+    }
+
+    #[test]
+    fn test_builder_runs_initial_grid() {
+        let blinker = parse_grid(".....\n..#..\n..#..\n..#..\n.....").unwrap();
+
+        let outcome = SimulationBuilder::new().initial(blinker).max_iterations(50).run().unwrap();
+
+        assert_eq!(outcome, SimulationOutcome::SteadyState { iteration: 2, period: 2 });
+    }
+
+    #[test]
+    fn test_builder_runs_with_observer() {
+        let blinker = parse_grid(".....\n..#..\n..#..\n..#..\n.....").unwrap();
+        let mut populations = Vec::new();
+
+        SimulationBuilder::new()
+            .initial(blinker)
+            .run_with_observer(|grid, _| populations.push(grid.iter().flatten().filter(|&&cell| cell).count()))
+            .unwrap();
+
+        assert_eq!(populations, vec![3, 3]);
+    }
+
+    #[test]
+    fn test_builder_validation() {
+        assert_eq!(SimulationBuilder::new().grid_size(0).build().unwrap_err(), ConfigError::EmptyGrid);
+        assert_eq!(SimulationBuilder::new().density(1.5).build().unwrap_err(), ConfigError::Density(1.5));
+        assert_eq!(SimulationBuilder::new().stabilize(1).run().unwrap_err(), ConfigError::StabilizeWindow(1));
+        assert_eq!(
+            SimulationBuilder::new().packed(true).wrap(true, true).config().unwrap_err(),
+            ConfigError::Packed
+        );
+        assert!(SimulationBuilder::new().packed(true).grid_size(64).config().is_ok());
+        assert_eq!(SimulationBuilder::new().threads(0).config().unwrap_err(), ConfigError::NoThreads);
+        assert_eq!(
+            SimulationBuilder::new().grid_size(10).region((2, 2, 10, 4)).config().unwrap_err(),
+            ConfigError::Region(RegionError { region: (2, 2, 10, 4), dimensions: (10, 10) })
+        );
+        assert_eq!(
+            SimulationBuilder::new().grid_size(10).grid_width(20).trace((5, 10)).config().unwrap_err(),
+            ConfigError::Trace { cell: (5, 10), dimensions: (10, 20) }
+        );
+        assert!(SimulationBuilder::new().grid_size(10).grid_width(20).trace((19, 9)).config().is_ok());
+    }
+}
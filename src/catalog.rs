@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::cells::parse_cells;
+use crate::game::Grid;
+use crate::rle::parse_rle;
+
+/// Well known pattern built into the program, in the plaintext `.cells` format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DemoPattern {
+    pub name: &'static str,
+    pub cells: &'static str,
+}
+
+impl DemoPattern {
+    /// Cells of the pattern, trimmed to its bounding box.
+    pub fn grid(&self) -> Grid {
+        parse_cells(self.cells).expect("built-in patterns are valid")
+    }
+}
+
+/// Patterns shown one after another by `--demo`, from the smallest spaceship to a glider gun.
+pub const DEMO_CATALOG: [DemoPattern; 5] = [
+    DemoPattern { name: "Glider", cells: ".O\n..O\nOOO\n" },
+    DemoPattern { name: "Lightweight spaceship", cells: ".O..O\nO....\nO...O\nOOOO.\n" },
+    DemoPattern {
+        name: "Pulsar",
+        cells: "\
+..OOO...OOO..
+.............
+O....O.O....O
+O....O.O....O
+O....O.O....O
+..OOO...OOO..
+.............
+..OOO...OOO..
+O....O.O....O
+O....O.O....O
+O....O.O....O
+.............
+..OOO...OOO..
+",
+    },
+    DemoPattern { name: "Pentadecathlon", cells: "..O....O..\nOO.OOOO.OO\n..O....O..\n" },
+    DemoPattern {
+        name: "Gosper glider gun",
+        cells: "\
+........................O...........
+......................O.O...........
+............OO......OO............OO
+...........O...O....OO............OO
+OO........O.....O...OO..............
+OO........O...O.OO....O.O...........
+..........O.....O.......O...........
+...........O...O....................
+............OO......................
+",
+    },
+];
+
+/// Small patterns that take hundreds to thousands of generations to stabilize, for `--seed-pattern`.
+pub const METHUSELAHS: [DemoPattern; 3] = [
+    // Stabilizes after 1103 generations
+    DemoPattern { name: "R-pentomino", cells: ".OO\nOO.\n.O.\n" },
+    // Stabilizes after 5206 generations
+    DemoPattern { name: "Acorn", cells: ".O.....\n...O...\nOO..OOO\n" },
+    // Dies out after 130 generations
+    DemoPattern { name: "Diehard", cells: "......O.\nOO......\n.O...OOO\n" },
+];
+
+/// Finds a built-in pattern by its name, ignoring case.
+pub fn builtin_pattern(name: &str) -> Option<DemoPattern> {
+    DEMO_CATALOG.into_iter().chain(METHUSELAHS).find(|pattern| pattern.name.eq_ignore_ascii_case(name))
+}
+
+/// Finds a methuselah by its name, ignoring case.
+pub fn methuselah(name: &str) -> Option<DemoPattern> {
+    METHUSELAHS.into_iter().find(|pattern| pattern.name.eq_ignore_ascii_case(name))
+}
+
+/// What went wrong while loading a pattern library.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LibraryErrorKind {
+    /// The file couldn't be read.
+    Io(String),
+    /// Two entries share the name.
+    DuplicateName(String),
+    /// The JSON or the RLE of an entry is malformed.
+    ParseError(String),
+}
+
+/// Error returned when a pattern library can't be loaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LibraryError {
+    pub kind: LibraryErrorKind,
+}
+
+impl fmt::Display for LibraryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            LibraryErrorKind::Io(message) => write!(f, "Failed to read the pattern library: {}", message),
+            LibraryErrorKind::DuplicateName(name) => write!(f, "Pattern `{}` appears more than once in the library", name),
+            LibraryErrorKind::ParseError(message) => write!(f, "Invalid pattern library: {}", message),
+        }
+    }
+}
+
+impl Error for LibraryError {}
+
+/// One named pattern of a library file.
+#[derive(Deserialize)]
+struct LibraryEntry {
+    name: String,
+    rle: String,
+}
+
+/// Loads a pattern library file, see [`read_pattern_library`].
+pub fn load_pattern_library(path: &Path) -> Result<HashMap<String, Grid>, LibraryError> {
+    let file = File::open(path).map_err(|err| LibraryError { kind: LibraryErrorKind::Io(err.to_string()) })?;
+    read_pattern_library(BufReader::new(file))
+}
+
+/// Reads a JSON array of `{"name": ..., "rle": ...}` objects into the patterns' cells by name.
+pub fn read_pattern_library(reader: impl Read) -> Result<HashMap<String, Grid>, LibraryError> {
+    let parse_error = |message: String| LibraryError { kind: LibraryErrorKind::ParseError(message) };
+    let entries: Vec<LibraryEntry> = serde_json::from_reader(reader).map_err(|err| parse_error(err.to_string()))?;
+
+    let mut library = HashMap::with_capacity(entries.len());
+    for entry in entries {
+        if library.contains_key(&entry.name) {
+            return Err(LibraryError { kind: LibraryErrorKind::DuplicateName(entry.name) });
+        }
+        let pattern = parse_rle(&entry.rle).map_err(|err| parse_error(format!("pattern `{}`: {}", entry.name, err)))?;
+        library.insert(entry.name, pattern.cells);
+    }
+    Ok(library)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+    use crate::game::{count_population, grid_dimensions, initialize_grid, run_headless, SimulationConfig, Topology};
+    use crate::grid_ops::{bounding_box, live_cells, place_pattern_centered, BoundingBox};
+    use crate::metrics::SimulationOutcome;
+
+    #[test]
+    fn test_demo_catalog_entries() {
+        let names: Vec<&str> = DEMO_CATALOG.iter().map(|pattern| pattern.name).collect();
+
+        assert_eq!(names, ["Glider", "Lightweight spaceship", "Pulsar", "Pentadecathlon", "Gosper glider gun"]);
+    }
+
+    #[test]
+    fn test_demo_patterns_fill_small_bounding_boxes() {
+        for (pattern, population) in DEMO_CATALOG.iter().zip([5, 9, 48, 12, 36]) {
+            let grid = parse_cells(pattern.cells).unwrap();
+            let (rows, cols) = grid_dimensions(&grid);
+
+            assert_eq!(count_population(&grid), population, "{}", pattern.name);
+            assert_eq!(bounding_box(&grid), Some(BoundingBox { top: 0, left: 0, height: rows, width: cols }), "{}", pattern.name);
+            assert!(rows <= 16 && cols <= 40, "{} is {}x{}", pattern.name, rows, cols);
+        }
+    }
+
+    #[test]
+    fn test_builtin_pattern_lookup() {
+        assert_eq!(builtin_pattern("pulsar"), Some(DEMO_CATALOG[2]));
+        assert_eq!(builtin_pattern("Gosper glider gun").map(|pattern| pattern.name), Some("Gosper glider gun"));
+        assert_eq!(builtin_pattern("loafer"), None);
+    }
+
+    #[test]
+    fn test_methuselah_lookup() {
+        let names: Vec<&str> = METHUSELAHS.iter().map(|pattern| pattern.name).collect();
+
+        assert_eq!(names, ["R-pentomino", "Acorn", "Diehard"]);
+        assert_eq!(methuselah("r-pentomino"), Some(METHUSELAHS[0]));
+        assert_eq!(builtin_pattern("acorn"), Some(METHUSELAHS[1]));
+        assert_eq!(methuselah("pulsar"), None);
+    }
+
+    #[test]
+    fn test_acorn_is_placed_centered() {
+        let mut grid = initialize_grid(11, 11);
+        place_pattern_centered(&mut grid, &methuselah("acorn").unwrap().grid());
+
+        assert_eq!(live_cells(&grid), [(4, 3), (5, 5), (6, 2), (6, 3), (6, 6), (6, 7), (6, 8)]);
+    }
+
+    #[test]
+    fn test_r_pentomino_outlives_small_starts() {
+        let config = SimulationConfig { grid_size: 120, topology: Topology::Toroidal, ..SimulationConfig::default() };
+        let mut grid = initialize_grid(120, 120);
+        place_pattern_centered(&mut grid, &methuselah("R-pentomino").unwrap().grid());
+
+        let (outcome, last) = run_headless(&config, grid, 1200);
+
+        assert!(matches!(outcome, SimulationOutcome::MaxIterations { .. }), "{:?}", outcome);
+        assert!(count_population(&last) > 5);
+    }
+
+    #[test]
+    fn test_read_pattern_library() {
+        let json = r#"[
+            {"name": "blinker", "rle": "x = 3, y = 1\n3o!"},
+            {"name": "beehive", "rle": "x = 4, y = 3\nb2o$o2bo$b2o!"}
+        ]"#;
+
+        let library = read_pattern_library(Cursor::new(json.as_bytes())).unwrap();
+
+        assert_eq!(library.len(), 2);
+        assert_eq!(grid_dimensions(&library["beehive"]), (3, 4));
+        assert_eq!(library["blinker"], vec![vec![true; 3]]);
+    }
+
+    #[test]
+    fn test_read_pattern_library_errors() {
+        let duplicate = r#"[{"name": "dot", "rle": "x = 1, y = 1\no!"}, {"name": "dot", "rle": "x = 1, y = 1\no!"}]"#;
+        let error = read_pattern_library(Cursor::new(duplicate.as_bytes())).unwrap_err();
+        assert_eq!(error.kind, LibraryErrorKind::DuplicateName("dot".to_string()));
+
+        for invalid in [r#"{"name": "dot"}"#, r#"[{"name": "dot", "rle": "o!"}]"#] {
+            let error = read_pattern_library(Cursor::new(invalid.as_bytes())).unwrap_err();
+            assert!(matches!(error.kind, LibraryErrorKind::ParseError(_)), "{:?}", error);
+        }
+    }
+}
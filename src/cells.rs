@@ -0,0 +1,112 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::game::{parse_row, Grid, ParseGridError};
+use crate::grid_ops::live_cells;
+use crate::rle::{Pattern, PatternMetadata};
+
+/// Parses a pattern in the plaintext `.cells` format.
+///
+/// `O` is a live cell and `.` a dead one; lines starting with `!` are comments. Rows shorter than
+/// the longest one are padded with dead cells.
+pub fn parse_cells(text: &str) -> Result<Grid, ParseGridError> {
+    let mut grid: Grid = Vec::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim_end();
+        if line.starts_with('!') {
+            continue;
+        }
+        grid.push(parse_row(line.chars(), 'O', index + 1)?);
+    }
+
+    while grid.last().is_some_and(|row| row.is_empty()) {
+        grid.pop();
+    }
+    let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+    for row in &mut grid {
+        row.resize(width, false);
+    }
+    Ok(grid)
+}
+
+/// Parses a `.cells` pattern into the `(row, col)` coordinates of its live cells.
+pub fn parse_cells_coords(text: &str) -> Result<Vec<(usize, usize)>, ParseGridError> {
+    parse_cells(text).map(|grid| live_cells(&grid))
+}
+
+/// Parses a `.cells` pattern together with its comments; a `!Name:` comment names the pattern.
+pub fn parse_cells_pattern(text: &str) -> Result<Pattern, ParseGridError> {
+    let mut metadata = PatternMetadata::default();
+    for comment in text.lines().filter_map(|line| line.strip_prefix('!')).map(str::trim) {
+        match comment.strip_prefix("Name:") {
+            Some(name) => metadata.name = Some(name.trim().to_string()),
+            None if !comment.is_empty() => metadata.comments.push(comment.to_string()),
+            None => {}
+        }
+    }
+    Ok(Pattern { cells: parse_cells(text)?, metadata })
+}
+
+/// Reads a `.cells` pattern file, see [`parse_cells_pattern`].
+pub fn load_cells(path: &Path) -> io::Result<Pattern> {
+    let input = fs::read_to_string(path)?;
+    parse_cells_pattern(&input).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Formats the grid in the plaintext `.cells` format, one line per row.
+pub fn format_cells(grid: &Grid) -> String {
+    grid.iter()
+        .map(|row| row.iter().map(|&cell| if cell { 'O' } else { '.' }).chain(Some('\n')).collect::<String>())
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cells() {
+        let grid = parse_cells("!Name: Glider\n!\n.O\n..O\nOOO\n").unwrap();
+
+        assert_eq!(
+            grid,
+            vec![
+                vec![false, true, false],
+                vec![false, false, true],
+                vec![true, true, true],
+            ]
+        );
+        assert_eq!(
+            parse_cells("O#").unwrap_err(),
+            ParseGridError::UnexpectedChar { line: 1, character: '#' }
+        );
+    }
+
+    #[test]
+    fn test_parse_cells_pattern() {
+        let text = "!Name: Glider\n!The smallest spaceship\n.O\n..O\nOOO\n";
+
+        let pattern = parse_cells_pattern(text).unwrap();
+
+        assert_eq!(pattern.metadata.name.as_deref(), Some("Glider"));
+        assert_eq!(pattern.metadata.comments, ["The smallest spaceship"]);
+        assert_eq!(parse_cells_coords(text).unwrap(), [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn test_parse_cells_pads_short_rows() {
+        let grid = parse_cells("O\n..O\n\n.O").unwrap();
+
+        assert_eq!(grid, vec![vec![true, false, false], vec![false, false, true], vec![false; 3], vec![false, true, false]]);
+    }
+
+    #[test]
+    fn test_cells_round_trip() {
+        let grid = vec![vec![true, false, false], vec![false, false, false], vec![false, true, true]];
+
+        assert_eq!(format_cells(&grid), "O..\n...\n.OO\n");
+        assert_eq!(parse_cells(&format_cells(&grid)).unwrap(), grid);
+    }
+}
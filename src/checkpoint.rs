@@ -0,0 +1,233 @@
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::game::{grid_dimensions, GameError, Grid, SimulationConfig};
+use crate::record::GridState;
+
+/// File written every `checkpoint_interval` generations by the interactive simulation.
+pub const CHECKPOINT_FILE: &str = "checkpoint.bin";
+/// Bytes every checkpoint file starts with.
+const MAGIC: &[u8; 4] = b"RGOL";
+/// Version of the checkpoint format, bumped whenever the serialized layout changes.
+const VERSION: u8 = 5;
+
+/// State of a simulation paused at some generation, enough to continue it later.
+///
+/// The steady state history and run statistics aren't saved, so they start over after a restore.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointState {
+    pub iteration: u64,
+    pub config: SimulationConfig,
+    pub grid: Grid,
+}
+
+/// Error returned for a checkpoint file that can't be written or isn't one this version can read.
+#[derive(Debug)]
+pub enum CheckpointError {
+    /// The file doesn't start with the `RGOL` magic.
+    NotACheckpoint,
+    /// The file was written in another version of the format.
+    UnsupportedVersion(u8),
+    /// The state couldn't be encoded, or the file doesn't decode to one.
+    Encoding(bincode::Error),
+    /// The saved grid doesn't have the dimensions its configuration describes.
+    SizeMismatch,
+}
+
+impl fmt::Display for CheckpointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CheckpointError::NotACheckpoint => write!(f, "Not a checkpoint file"),
+            CheckpointError::UnsupportedVersion(version) => {
+                write!(f, "Unsupported checkpoint version {}, expected {}", version, VERSION)
+            }
+            CheckpointError::Encoding(err) => write!(f, "Invalid checkpoint: {}", err),
+            CheckpointError::SizeMismatch => write!(f, "Checkpoint grid size doesn't match its configuration"),
+        }
+    }
+}
+
+impl Error for CheckpointError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CheckpointError::Encoding(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Layout of the serialized checkpoint; the grid is stored as its live cells.
+#[derive(Serialize, Deserialize)]
+struct Checkpoint {
+    iteration: u64,
+    config: SimulationConfig,
+    grid: GridState,
+}
+
+/// Saves the grid at the given generation together with its configuration.
+///
+/// The file starts with the `RGOL` magic and a version byte followed by the bincode encoded state.
+pub fn save_checkpoint(grid: &Grid, iteration: u64, config: &SimulationConfig, path: &Path) -> Result<(), GameError> {
+    let checkpoint = Checkpoint { iteration, config: config.clone(), grid: GridState::from_grid(grid) };
+    let mut writer = BufWriter::new(File::create(path)?);
+    writer.write_all(MAGIC)?;
+    writer.write_all(&[VERSION])?;
+    bincode::serialize_into(&mut writer, &checkpoint).map_err(CheckpointError::Encoding)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Loads a checkpoint written by [`save_checkpoint`], rejecting files of another format or version.
+pub fn load_checkpoint(path: &Path) -> Result<CheckpointState, GameError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut header = [0; 5];
+    reader.read_exact(&mut header)?;
+    if &header[..4] != MAGIC {
+        return Err(CheckpointError::NotACheckpoint.into());
+    }
+    if header[4] != VERSION {
+        return Err(CheckpointError::UnsupportedVersion(header[4]).into());
+    }
+
+    let checkpoint: Checkpoint = bincode::deserialize_from(reader).map_err(CheckpointError::Encoding)?;
+    let grid = checkpoint.grid.to_grid()?;
+    if checkpoint.config.dimensions() != grid_dimensions(&grid) {
+        return Err(CheckpointError::SizeMismatch.into());
+    }
+
+    Ok(CheckpointState { iteration: checkpoint.iteration, config: checkpoint.config, grid })
+}
+
+/// Name of the file written by [`CheckpointRotation`] at the given generation.
+pub fn numbered_checkpoint_file(iteration: u64) -> String {
+    format!("checkpoint_{}.bin", iteration)
+}
+
+/// Checkpoints saved to a separate `checkpoint_<iteration>.bin` file each, so a run can be
+/// continued from any of them; only the newest `keep` are left on disk when set.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CheckpointRotation {
+    dir: PathBuf,
+    keep: Option<usize>,
+    saved: VecDeque<PathBuf>,
+}
+
+impl CheckpointRotation {
+    pub fn new(dir: impl Into<PathBuf>, keep: Option<usize>) -> Self {
+        CheckpointRotation { dir: dir.into(), keep, saved: VecDeque::new() }
+    }
+
+    /// Saves a checkpoint of the given generation, then deletes the oldest ones saved by this
+    /// rotation beyond the number to keep.
+    pub fn save(&mut self, grid: &Grid, iteration: u64, config: &SimulationConfig) -> Result<(), GameError> {
+        let path = self.dir.join(numbered_checkpoint_file(iteration));
+        save_checkpoint(grid, iteration, config, &path)?;
+        self.saved.push_back(path);
+        while self.keep.is_some_and(|keep| self.saved.len() > keep) {
+            if let Some(oldest) = self.saved.pop_front() {
+                fs::remove_file(oldest)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Topology, Universe};
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("rusty-game-of-life-{}-{}.bin", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_checkpoint_restore_matches_continuous_run() {
+        let config = SimulationConfig { grid_size: 24, topology: Topology::Toroidal, seed: 11, ..Default::default() };
+        let path = temp_path("checkpoint");
+
+        let mut continuous = Universe::from_config(&config);
+        for _ in 0..50 {
+            continuous.step_once();
+        }
+        save_checkpoint(continuous.grid(), continuous.generation(), &config, &path).unwrap();
+        let state = load_checkpoint(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(state.iteration, 50);
+        assert_eq!(state.config, config);
+        let mut restored = Universe::new(state.grid, state.config.topology).with_generation(state.iteration);
+        for _ in 0..10 {
+            continuous.step_once();
+            restored.step_once();
+        }
+        assert_eq!(restored.generation(), 60);
+        assert_eq!(restored.grid(), continuous.grid());
+    }
+
+    #[test]
+    fn test_rotation_keeps_newest_checkpoints() {
+        let dir = std::env::temp_dir().join(format!("rusty-game-of-life-rotation-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config = SimulationConfig { grid_size: 8, ..Default::default() };
+        let grid = crate::game::create_seeded_grid(&config);
+
+        let mut rotation = CheckpointRotation::new(&dir, Some(2));
+        for iteration in [5, 10, 15] {
+            rotation.save(&grid, iteration, &config).unwrap();
+        }
+        let mut names: Vec<String> =
+            fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().file_name().into_string().unwrap()).collect();
+        names.sort();
+        let restored = load_checkpoint(&dir.join("checkpoint_15.bin")).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(names, vec!["checkpoint_10.bin", "checkpoint_15.bin"]);
+        assert_eq!(restored.iteration, 15);
+        assert_eq!(restored.grid, grid);
+    }
+
+    #[test]
+    fn test_load_checkpoint_validates_header() {
+        let path = temp_path("bad-checkpoint");
+
+        std::fs::write(&path, b"NOPE\x01").unwrap();
+        let wrong_magic = load_checkpoint(&path).unwrap_err();
+        std::fs::write(&path, b"RGOL\x09").unwrap();
+        let wrong_version = load_checkpoint(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(wrong_magic, GameError::Checkpoint(CheckpointError::NotACheckpoint)));
+        assert!(matches!(wrong_version, GameError::Checkpoint(CheckpointError::UnsupportedVersion(9))));
+        assert!(wrong_version.to_string().contains("version 9"));
+    }
+
+    #[test]
+    fn test_checkpoint_of_previous_layout_is_rejected_by_version() {
+        let config = SimulationConfig { grid_size: 8, seed: 3, ..Default::default() };
+        let grid = crate::game::create_seeded_grid(&config);
+        let (path, quiescent_path) = (temp_path("previous-layout"), temp_path("previous-layout-quiescent"));
+        save_checkpoint(&grid, 4, &config, &path).unwrap();
+        save_checkpoint(&grid, 4, &SimulationConfig { terminate_on_quiescence: true, ..config }, &quiescent_path).unwrap();
+
+        // Dropping the byte of `terminate_on_quiescence` gives the layout written by the previous version
+        let mut bytes = std::fs::read(&path).unwrap();
+        let quiescent = std::fs::read(&quiescent_path).unwrap();
+        let flag = bytes.iter().zip(&quiescent).position(|(a, b)| a != b).unwrap();
+        bytes.remove(flag);
+        bytes[4] = VERSION - 1;
+        std::fs::write(&path, &bytes).unwrap();
+        let err = load_checkpoint(&path).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        std::fs::remove_file(&quiescent_path).unwrap();
+
+        assert!(matches!(err, GameError::Checkpoint(CheckpointError::UnsupportedVersion(version)) if version == VERSION - 1));
+        assert_eq!(err.to_string(), format!("Unsupported checkpoint version {}, expected {}", VERSION - 1, VERSION));
+    }
+}
@@ -0,0 +1,116 @@
+pub mod palettes;
+
+use crate::game::{grid_dimensions, Grid};
+
+/// Hue of newly born cells, in degrees (green).
+const YOUNG_HUE: f32 = 120.0;
+
+/// Converts a color from HSL (hue in degrees, saturation and lightness in `0.0..=1.0`) to RGB.
+pub fn hsl_to_rgb(h: f32, s: f32, l: f32) -> (u8, u8, u8) {
+    let chroma = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let sector = h.rem_euclid(360.0) / 60.0;
+    let x = chroma * (1.0 - (sector % 2.0 - 1.0).abs());
+    let (r, g, b) = match sector as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let m = l - chroma / 2.0;
+    let channel = |value: f32| ((value + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+/// Color of a live cell of the given age, from green for newborn cells to red for the oldest ones.
+pub fn age_color(age: u32, max_age: u32) -> (u8, u8, u8) {
+    let hue = if max_age <= 1 {
+        YOUNG_HUE
+    } else {
+        let progress = (age.clamp(1, max_age) - 1) as f32 / (max_age - 1) as f32;
+        YOUNG_HUE * (1.0 - progress)
+    };
+    hsl_to_rgb(hue, 1.0, 0.5)
+}
+
+/// Number of consecutive generations each cell has been alive for.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CellAges {
+    ages: Vec<Vec<u32>>,
+    max_age: u32,
+}
+
+impl CellAges {
+    /// Creates ages for a grid with the given dimensions, all cells dead.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        CellAges { ages: vec![vec![0; cols]; rows], max_age: 0 }
+    }
+
+    /// Ages the live cells of the grid by one generation and resets dead ones.
+    pub fn update(&mut self, grid: &Grid) {
+        debug_assert_eq!(grid_dimensions(grid), (self.ages.len(), self.ages.first().map_or(0, Vec::len)));
+        for (ages, cells) in self.ages.iter_mut().zip(grid) {
+            for (age, &alive) in ages.iter_mut().zip(cells) {
+                *age = if alive { *age + 1 } else { 0 };
+                self.max_age = self.max_age.max(*age);
+            }
+        }
+    }
+
+    /// Age of the cell at the given position, `0` when it's dead.
+    pub fn age(&self, row: usize, col: usize) -> u32 {
+        self.ages[row][col]
+    }
+
+    /// Highest age observed so far.
+    pub fn max_age(&self) -> u32 {
+        self.max_age
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: (u8, u8, u8), expected: (u8, u8, u8)) {
+        let close = |a: u8, b: u8| a.abs_diff(b) <= 1;
+        assert!(
+            close(actual.0, expected.0) && close(actual.1, expected.1) && close(actual.2, expected.2),
+            "{:?} != {:?}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_hsl_to_rgb() {
+        assert_close(hsl_to_rgb(0.0, 1.0, 0.5), (255, 0, 0));
+        assert_close(hsl_to_rgb(120.0, 1.0, 0.5), (0, 255, 0));
+        assert_close(hsl_to_rgb(240.0, 1.0, 0.5), (0, 0, 255));
+        assert_close(hsl_to_rgb(60.0, 1.0, 0.5), (255, 255, 0));
+        assert_close(hsl_to_rgb(360.0, 1.0, 0.5), (255, 0, 0));
+        assert_close(hsl_to_rgb(0.0, 0.0, 0.5), (128, 128, 128));
+    }
+
+    #[test]
+    fn test_age_color_gradient() {
+        assert_close(age_color(1, 1), (0, 255, 0));
+        assert_close(age_color(1, 10), (0, 255, 0));
+        assert_close(age_color(10, 10), (255, 0, 0));
+        assert_close(age_color(50, 10), (255, 0, 0));
+    }
+
+    #[test]
+    fn test_cell_ages() {
+        let mut ages = CellAges::new(1, 2);
+
+        ages.update(&vec![vec![true, true]]);
+        ages.update(&vec![vec![true, false]]);
+        ages.update(&vec![vec![true, true]]);
+
+        assert_eq!((ages.age(0, 0), ages.age(0, 1)), (3, 1));
+        assert_eq!(ages.max_age(), 3);
+    }
+}
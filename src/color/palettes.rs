@@ -0,0 +1,89 @@
+use std::error::Error;
+use std::fmt;
+
+/// Named gradient of xterm 256-color codes for live cells, from newborn to the oldest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Palette {
+    pub name: &'static str,
+    pub colors: &'static [u8],
+}
+
+impl Palette {
+    /// Color code of a live cell of the given age, splitting the ages up to `max_age` into equal buckets.
+    pub fn color(&self, age: u32, max_age: u32) -> u8 {
+        let bucket = if max_age <= 1 {
+            0
+        } else {
+            (age.clamp(1, max_age) - 1) as usize * self.colors.len() / max_age as usize
+        };
+        self.colors[bucket.min(self.colors.len() - 1)]
+    }
+}
+
+/// Yellow flames cooling down to embers.
+pub const FIRE: Palette = Palette { name: "fire", colors: &[226, 220, 214, 208, 202, 196, 160, 124, 88] };
+/// Surf cyan sinking into deep blue.
+pub const OCEAN: Palette = Palette { name: "ocean", colors: &[51, 45, 39, 33, 27, 21, 20, 19, 18, 17] };
+/// White fading to dark gray.
+pub const GRAYSCALE: Palette = Palette { name: "grayscale", colors: &[255, 253, 251, 249, 247, 245, 243, 241] };
+
+/// Every palette selectable by name.
+pub const PALETTES: [Palette; 3] = [FIRE, OCEAN, GRAYSCALE];
+
+/// Error returned when no palette has the requested name.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownPaletteError {
+    pub name: String,
+}
+
+impl fmt::Display for UnknownPaletteError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<&str> = PALETTES.iter().map(|palette| palette.name).collect();
+        write!(f, "Unknown palette `{}`, expected one of: {}", self.name, names.join(", "))
+    }
+}
+
+impl Error for UnknownPaletteError {}
+
+/// Looks up a palette by its name.
+pub fn palette(name: &str) -> Result<Palette, UnknownPaletteError> {
+    PALETTES
+        .into_iter()
+        .find(|palette| palette.name == name)
+        .ok_or_else(|| UnknownPaletteError { name: name.to_string() })
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_named_palettes() {
+        for (name, expected) in [("fire", 9), ("ocean", 10), ("grayscale", 8)] {
+            let palette = palette(name).unwrap();
+
+            assert_eq!(palette.name, name);
+            assert_eq!(palette.colors.iter().collect::<HashSet<_>>().len(), expected);
+        }
+    }
+
+    #[test]
+    fn test_unknown_palette_is_rejected() {
+        let error = palette("sunset").unwrap_err();
+
+        assert_eq!(error, UnknownPaletteError { name: "sunset".to_string() });
+        assert_eq!(error.to_string(), "Unknown palette `sunset`, expected one of: fire, ocean, grayscale");
+    }
+
+    #[test]
+    fn test_palette_color_buckets() {
+        assert_eq!(FIRE.color(1, 1), 226);
+        assert_eq!(FIRE.color(1, 90), 226);
+        assert_eq!(FIRE.color(90, 90), 88);
+        assert_eq!(FIRE.color(500, 90), 88);
+        assert_eq!(GRAYSCALE.color(3, 4), 247);
+    }
+}
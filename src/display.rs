@@ -0,0 +1,609 @@
+use std::collections::VecDeque;
+use std::io::{self, BufRead, Write};
+use std::time::Duration;
+
+use clap::ValueEnum;
+
+use crate::bell::BellEvent;
+use crate::color::palettes::Palette;
+use crate::color::{age_color, CellAges};
+use crate::game::{count_population, display_cells, grid_entropy, Grid, Universe};
+use crate::grid_ops::{bounding_box, count_live_in_region, format_diff, CellDiff};
+use crate::heatmap::{compute_heatmap_char, Heatmap};
+use crate::rules::Rules;
+use crate::terminal::ColorMode;
+
+/// Options controlling how the grid and its status line are rendered.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DisplayOptions {
+    /// Include the Shannon entropy of the grid in the status line.
+    pub show_entropy: bool,
+    /// Skip all rendering and pacing, running the simulation at full speed.
+    pub headless: bool,
+    /// Follow terminal resizes, growing or shrinking the grid to fill the window.
+    pub fit: bool,
+    /// Colors used to draw live cells.
+    pub color: ColorMode,
+    /// Color live cells by age with a 24-bit gradient from green (newborn) to red (oldest).
+    pub color_by_age: bool,
+    /// Show how often each cell has been alive instead of the current generation.
+    pub heatmap: bool,
+    /// Redraw the heatmap every this many generations; it's only drawn at the end when unset.
+    pub heatmap_interval: Option<u64>,
+    /// Show the number of live neighbors of each cell instead of the cell itself.
+    pub show_counts: bool,
+    /// Show the grid from this many generations ago next to the current one.
+    pub compare_lag: Option<usize>,
+    /// Draw a box-drawing frame around the grid.
+    pub border: bool,
+    /// Include the size and position of the live cells' bounding box in the status line.
+    pub show_bbox: bool,
+    /// Color cells by age from this palette instead of the 24-bit gradient.
+    pub palette: Option<Palette>,
+    /// Characters drawn for live and dead cells.
+    pub glyphs: Glyphs,
+    /// Hold the first frame on screen until the user presses Enter.
+    pub start_paused: bool,
+    /// Time each generation is shown, 50 milliseconds unless set.
+    pub delay: Option<Duration>,
+    /// Time the first frame is shown before the animation starts, the same as the others unless set.
+    pub hold_first: Option<Duration>,
+    /// Show the autocorrelation of the population at this lag below the status line.
+    pub autocorrelation_lag: Option<usize>,
+    /// Show the cells born and died since the previous generation below the status line.
+    pub show_births: bool,
+    /// Events ringing the terminal bell.
+    pub bell_on: Vec<BellEvent>,
+    /// List the cells born and died below the grid for this many generations.
+    pub debug_diff: Option<u64>,
+    /// Include the population of the rectangle `(r1, c1, r2, c2)` in the status line.
+    pub region: Option<(usize, usize, usize, usize)>,
+    /// Evolve the same initial grid under this rule too, showing it next to the main one.
+    pub compare_rule: Option<Rules>,
+    /// Draw live cells with the dead glyph and dead cells with the live one, leaving the grid itself alone.
+    pub invert_display: bool,
+    /// Draw the grid and a statistics panel with ratatui on the alternate screen.
+    #[cfg(feature = "tui")]
+    pub tui: bool,
+    /// Number of cells the arrow keys scroll the TUI by.
+    #[cfg(feature = "tui")]
+    pub scroll_step: usize,
+}
+
+impl DisplayOptions {
+    /// Whether the run waits for the user after showing its first frame; headless runs never do.
+    pub fn starts_paused(&self) -> bool {
+        self.start_paused && !self.headless
+    }
+
+    /// Time a frame is shown, the first one held for `hold_first`; `None` leaves the default delay.
+    pub fn frame_time(&self, first_frame: bool) -> Option<Duration> {
+        self.hold_first.filter(|_| first_frame).or(self.delay)
+    }
+
+    /// Whether frames are drawn with the ratatui interface instead of escape sequences.
+    pub fn uses_tui(&self) -> bool {
+        #[cfg(feature = "tui")]
+        return self.tui && !self.headless;
+        #[cfg(not(feature = "tui"))]
+        false
+    }
+}
+
+/// Characters drawn for live and dead cells, `#` and `.` by default.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Glyphs {
+    pub alive: char,
+    pub dead: char,
+}
+
+impl Default for Glyphs {
+    fn default() -> Self {
+        Glyphs { alive: '#', dead: '.' }
+    }
+}
+
+impl Glyphs {
+    /// Glyphs of the theme, or the default ones, with explicitly chosen characters taking precedence.
+    pub fn resolve(theme: Option<Theme>, alive: Option<char>, dead: Option<char>) -> Self {
+        let defaults = theme.map_or_else(Glyphs::default, Theme::glyphs);
+        Glyphs { alive: alive.unwrap_or(defaults.alive), dead: dead.unwrap_or(defaults.dead) }
+    }
+
+    /// Same glyphs with live and dead cells trading places.
+    pub fn swapped(self) -> Self {
+        Glyphs { alive: self.dead, dead: self.alive }
+    }
+}
+
+/// Glyph set suited to the terminal background.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Theme {
+    /// Solid blocks for live cells on blank dead cells.
+    Dark,
+    /// The dark theme inverted: blank live cells among solid blocks.
+    Light,
+}
+
+impl Theme {
+    pub fn glyphs(self) -> Glyphs {
+        match self {
+            Theme::Dark => Glyphs { alive: '█', dead: ' ' },
+            Theme::Light => Glyphs { alive: ' ', dead: '█' },
+        }
+    }
+}
+
+/// Most recent grids, kept to show how the grid looked a fixed number of generations ago.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LagBuffer {
+    grids: VecDeque<Grid>,
+    lag: usize,
+}
+
+impl LagBuffer {
+    pub fn new(lag: usize) -> Self {
+        LagBuffer { grids: VecDeque::with_capacity(lag + 1), lag }
+    }
+
+    /// Adds the current grid, dropping the ones older than the lag.
+    pub fn push(&mut self, grid: Grid) {
+        if self.grids.len() > self.lag {
+            self.grids.pop_front();
+        }
+        self.grids.push_back(grid);
+    }
+
+    /// Oldest grid kept, from `lag` generations ago once enough generations have passed.
+    pub fn lagged(&self) -> Option<&Grid> {
+        self.grids.front()
+    }
+
+    /// Number of generations between the oldest and the newest grid.
+    pub fn distance(&self) -> usize {
+        self.grids.len().saturating_sub(1)
+    }
+}
+
+/// Displays the oldest grid of the buffer next to the current one, which must have been pushed last.
+pub fn display_comparison(lag: &LagBuffer, grid: &Grid, iteration: u64, options: &DisplayOptions) {
+    print!("\x1B[H");
+
+    let lagged = lag.lagged().unwrap_or(grid);
+    let left_label = format!("Gen {}", iteration - lag.distance() as u64);
+    let right_label = format!("Gen {}", iteration);
+    let output = format!(
+        "{}{}\n",
+        display_side_by_side(lagged, grid, &left_label, &right_label),
+        status_line(grid, iteration, options)
+    );
+    print!("{}", output);
+    std::io::stdout().flush().expect("Failed to flush stdout");
+}
+
+/// Displays two universes evolving under different rules next to each other, labeled with their rules.
+pub fn display_rule_comparison(left: &Universe, right: &Universe, options: &DisplayOptions) {
+    print!("\x1B[H");
+    print!("{}", rule_comparison_frame(left, right, options));
+    std::io::stdout().flush().expect("Failed to flush stdout");
+}
+
+/// Renders both universes side by side, followed by the status line and each one's population.
+fn rule_comparison_frame(left: &Universe, right: &Universe, options: &DisplayOptions) -> String {
+    let (left_rules, right_rules) = (left.rules().to_string(), right.rules().to_string());
+    format!(
+        "{}{}  {}: {}  {}: {}\n",
+        display_side_by_side(left.grid(), right.grid(), &left_rules, &right_rules),
+        status_line(left.grid(), left.generation(), options),
+        left_rules,
+        count_population(left.grid()),
+        right_rules,
+        count_population(right.grid())
+    )
+}
+
+/// Renders two grids next to each other under their labels, separated by `  |  `.
+pub fn display_side_by_side(left: &Grid, right: &Grid, left_label: &str, right_label: &str) -> String {
+    let left_text = display_cells(left).to_string();
+    let right_text = display_cells(right).to_string();
+    let left_lines: Vec<&str> = left_text.lines().collect();
+    let right_lines: Vec<&str> = right_text.lines().collect();
+    let width = left_lines.iter().map(|line| line.len()).chain(Some(left_label.len())).max().unwrap_or(0);
+
+    let mut output = format!("{:<width$}  |  {}\n", left_label, right_label);
+    for row in 0..left_lines.len().max(right_lines.len()) {
+        let left_line = left_lines.get(row).copied().unwrap_or("");
+        let right_line = right_lines.get(row).copied().unwrap_or("");
+        output.push_str(&format!("{:<width$}  |  {}\n", left_line, right_line));
+    }
+    output
+}
+
+/// Clears the terminal screen.
+pub fn clear_screen() {
+    print!("\x1B[2J\x1B[H");
+    std::io::stdout().flush().expect("Failed to flush stdout");
+}
+
+/// Shows a hint below the grid and blocks until a line is read from `input`.
+pub fn wait_for_start(input: &mut impl BufRead) -> io::Result<()> {
+    println!("Press Enter to start.");
+    input.read_line(&mut String::new())?;
+    Ok(())
+}
+
+/// Displays the grid in the terminal, coloring cells by age when `ages` are given.
+pub fn display_grid(grid: &Grid, iteration: u64, options: &DisplayOptions, ages: Option<&CellAges>) {
+    print!("\x1B[H");
+    print!("{}", grid_frame(grid, iteration, options, ages));
+    std::io::stdout().flush().expect("Failed to flush stdout");
+}
+
+/// Prints the autocorrelation of the population so far on the line below the status line.
+pub fn display_autocorrelation(lag: usize, autocorrelation: f64) {
+    println!("{}\x1B[K", autocorrelation_line(lag, autocorrelation));
+    std::io::stdout().flush().expect("Failed to flush stdout");
+}
+
+fn autocorrelation_line(lag: usize, autocorrelation: f64) -> String {
+    format!("Autocorrelation (lag {}): {:.3}", lag, autocorrelation)
+}
+
+/// Prints the births and deaths since the previous generation on a line below the status line.
+pub fn display_births(births: u64, deaths: u64) {
+    println!("{}\x1B[K", births_line(births, deaths));
+    std::io::stdout().flush().expect("Failed to flush stdout");
+}
+
+fn births_line(births: u64, deaths: u64) -> String {
+    format!("Born: {} Died: {} Net: {:+}", births, deaths, births as i64 - deaths as i64)
+}
+
+/// Renders the grid, framed if requested, followed by its status line.
+fn grid_frame(grid: &Grid, iteration: u64, options: &DisplayOptions, ages: Option<&CellAges>) -> String {
+    let cells = match (ages, live_cell_color(options.color)) {
+        (Some(ages), _) => age_colored_cells(grid, ages, options.palette),
+        (None, Some(color)) => colored_cells(grid, color),
+        (None, None) => display_cells(grid).to_string(),
+    };
+    let glyphs = if options.invert_display { options.glyphs.swapped() } else { options.glyphs };
+    let cells = with_glyphs(cells, glyphs);
+    let cells = if options.border { bordered(&cells, grid.first().map_or(0, Vec::len)) } else { cells };
+    format!("{}{}\n", cells, status_line(grid, iteration, options))
+}
+
+/// Swaps the default `#` and `.` of rendered cells for the chosen glyphs; color codes contain neither.
+fn with_glyphs(cells: String, glyphs: Glyphs) -> String {
+    if glyphs == Glyphs::default() {
+        return cells;
+    }
+    cells
+        .chars()
+        .map(|character| match character {
+            '#' => glyphs.alive,
+            '.' => glyphs.dead,
+            character => character,
+        })
+        .collect()
+}
+
+/// Wraps rendered rows of `cols` space separated cells in a box-drawing frame.
+///
+/// The width comes from the column count rather than the text, which may contain color codes.
+fn bordered(cells: &str, cols: usize) -> String {
+    let horizontal = "─".repeat(2 * cols + 1);
+    let mut output = format!("┌{}┐\n", horizontal);
+    for line in cells.lines() {
+        output.push_str(&format!("│ {} │\n", line));
+    }
+    output.push_str(&format!("└{}┘\n", horizontal));
+    output
+}
+
+/// Displays the live neighbor count of every cell as a digit, highlighting the live cells.
+pub fn display_neighbor_counts(grid: &Grid, counts: &[Vec<u8>], iteration: u64, options: &DisplayOptions) {
+    print!("\x1B[H");
+
+    let highlight = live_cell_color(options.color).unwrap_or("\x1B[1m");
+    let output = format!("{}{}\n", count_cells(grid, counts, highlight), status_line(grid, iteration, options));
+    print!("{}", output);
+    std::io::stdout().flush().expect("Failed to flush stdout");
+}
+
+/// Replaces whatever is below the current frame with the listed cell changes.
+pub fn display_diff(diffs: &[CellDiff]) {
+    print!("\x1B[J{}", format_diff(diffs));
+    std::io::stdout().flush().expect("Failed to flush stdout");
+}
+
+/// Displays how often each cell has been alive, as digits from `0` (never) to `9` (90% or more).
+pub fn display_heatmap(heatmap: &Heatmap, options: &DisplayOptions) {
+    print!("\x1B[H");
+
+    let output = format!("{}{}\n", heatmap_text(heatmap), heatmap_status_line(heatmap, options));
+    print!("{}", output);
+    std::io::stdout().flush().expect("Failed to flush stdout");
+}
+
+/// Renders the heatmap as rows of space separated digits.
+fn heatmap_text(heatmap: &Heatmap) -> String {
+    let total = heatmap.generations();
+    heatmap
+        .rows()
+        .map(|row| {
+            let digits: Vec<String> = row.iter().map(|&count| compute_heatmap_char(count, total).to_string()).collect();
+            digits.join(" ") + "\n"
+        })
+        .collect()
+}
+
+/// Builds the status line shown below the heatmap.
+fn heatmap_status_line(heatmap: &Heatmap, options: &DisplayOptions) -> String {
+    let line = format!("Heatmap over {} generations", heatmap.generations());
+    match options.heatmap_interval {
+        Some(_) => line,
+        None => line + " (final)",
+    }
+}
+
+/// Escape sequence setting the color of live cells, `None` when colors are off.
+fn live_cell_color(mode: ColorMode) -> Option<&'static str> {
+    match mode {
+        ColorMode::NoColor => None,
+        ColorMode::Ansi16 => Some("\x1B[92m"),
+        ColorMode::Ansi256 => Some("\x1B[38;5;83m"),
+        ColorMode::TrueColor => Some("\x1B[38;2;80;250;123m"),
+    }
+}
+
+/// Renders the grid like [`display_cells`], drawing live cells in the given color.
+fn colored_cells(grid: &Grid, color: &str) -> String {
+    display_cells(grid).to_string().replace('#', &format!("{}#\x1B[0m", color))
+}
+
+/// Renders the grid drawing each live cell in a color reflecting its age, from the palette when given.
+fn age_colored_cells(grid: &Grid, ages: &CellAges, palette: Option<Palette>) -> String {
+    let mut output = String::new();
+    for (row, cells) in grid.iter().enumerate() {
+        for (col, &alive) in cells.iter().enumerate() {
+            if col > 0 {
+                output.push(' ');
+            }
+            if alive {
+                let (age, max_age) = (ages.age(row, col), ages.max_age());
+                match palette {
+                    Some(palette) => output.push_str(&format!("\x1B[38;5;{}m#", palette.color(age, max_age))),
+                    None => {
+                        let (r, g, b) = age_color(age, max_age);
+                        output.push_str(&format!("\x1B[38;2;{};{};{}m#", r, g, b));
+                    }
+                }
+            } else {
+                output.push_str("\x1B[0m.");
+            }
+        }
+        output.push_str("\x1B[0m\n");
+    }
+    output
+}
+
+/// Renders the neighbor counts as rows of space separated digits, live cells in the highlight style.
+fn count_cells(grid: &Grid, counts: &[Vec<u8>], highlight: &str) -> String {
+    let mut output = String::new();
+    for (cells, counts) in grid.iter().zip(counts) {
+        let digits: Vec<String> = cells
+            .iter()
+            .zip(counts)
+            .map(|(&alive, count)| if alive { format!("{}{}\x1B[0m", highlight, count) } else { count.to_string() })
+            .collect();
+        output.push_str(&digits.join(" "));
+        output.push('\n');
+    }
+    output
+}
+
+/// Builds the status line shown below the grid.
+fn status_line(grid: &Grid, iteration: u64, options: &DisplayOptions) -> String {
+    let mut line = format!("Iteration: {}", iteration);
+    if options.show_entropy {
+        line.push_str(&format!("  H: {:.3}", grid_entropy(grid)));
+    }
+    if options.show_bbox {
+        match bounding_box(grid) {
+            Some(bbox) => line.push_str(&format!("  BBox: {}x{} at ({},{})", bbox.width, bbox.height, bbox.left, bbox.top)),
+            None => line.push_str("  BBox: empty"),
+        }
+    }
+    if let Some((r1, c1, r2, c2)) = options.region {
+        line.push_str(&format!("  Region: {}", count_live_in_region(grid, r1, c1, r2, c2)));
+    }
+    line
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::color::palettes::OCEAN;
+
+    #[test]
+    fn test_colored_cells() {
+        let grid = vec![vec![true, false]];
+
+        assert_eq!(colored_cells(&grid, "\x1B[92m"), "\x1B[92m#\x1B[0m .\n");
+        assert_eq!(live_cell_color(ColorMode::NoColor), None);
+    }
+
+    #[test]
+    fn test_heatmap_text() {
+        let mut heatmap = Heatmap::new(1, 3);
+        heatmap.record(&vec![vec![true, true, false]]);
+        heatmap.record(&vec![vec![true, false, false]]);
+
+        assert_eq!(heatmap_text(&heatmap), "9 5 0\n");
+    }
+
+    #[test]
+    fn test_age_colored_cells() {
+        let grid = vec![vec![true, false]];
+        let mut ages = CellAges::new(1, 2);
+        ages.update(&grid);
+
+        assert_eq!(age_colored_cells(&grid, &ages, None), "\x1B[38;2;0;255;0m# \x1B[0m.\x1B[0m\n");
+        assert_eq!(age_colored_cells(&grid, &ages, Some(OCEAN)), "\x1B[38;5;51m# \x1B[0m.\x1B[0m\n");
+    }
+
+    #[test]
+    fn test_count_cells() {
+        let grid = vec![vec![true, false, false]];
+
+        assert_eq!(count_cells(&grid, &[vec![0, 1, 0]], "\x1B[1m"), "\x1B[1m0\x1B[0m 1 0\n");
+    }
+
+    #[test]
+    fn test_side_by_side_identical_grids() {
+        let grid = vec![vec![true, false, false], vec![false, true, false], vec![false, false, true]];
+
+        let output = display_side_by_side(&grid, &grid, "Gen 3", "Gen 3");
+
+        assert_eq!(output.lines().count(), 4);
+        for line in output.lines() {
+            let (left, right) = line.split_once("  |  ").unwrap();
+            assert_eq!(left.trim_end(), right);
+        }
+    }
+
+    #[test]
+    fn test_rule_comparison_shows_both_populations() {
+        let blinker = vec![vec![false; 3], vec![true; 3], vec![false; 3]];
+        let conway = Universe::new(blinker, crate::game::Topology::Finite);
+        let mut seeds = conway.fork("B2/S".parse().unwrap());
+        seeds.step_once();
+
+        let frame = rule_comparison_frame(&conway, &seeds, &DisplayOptions::default());
+
+        assert!(frame.starts_with("B3/S23  |  B2/S\n"), "{}", frame);
+        assert!(frame.ends_with("Iteration: 0  B3/S23: 3  B2/S: 4\n"), "{}", frame);
+    }
+
+    #[test]
+    fn test_lag_buffer_keeps_grid_from_lag_generations_ago() {
+        let mut lag = LagBuffer::new(2);
+        for generation in 0..5 {
+            lag.push(vec![vec![generation % 2 == 0], vec![generation == 2]]);
+        }
+
+        assert_eq!(lag.distance(), 2);
+        assert_eq!(lag.lagged(), Some(&vec![vec![true], vec![true]]));
+    }
+
+    #[test]
+    fn test_bordered_grid_frame() {
+        let grid = vec![vec![true, false], vec![false, true]];
+        let options = DisplayOptions { border: true, ..Default::default() };
+
+        assert_eq!(
+            grid_frame(&grid, 3, &options, None),
+            "┌─────┐\n\
+             │ # . │\n\
+             │ . # │\n\
+             └─────┘\n\
+             Iteration: 3\n"
+        );
+    }
+
+    #[test]
+    fn test_start_paused_unless_headless() {
+        assert!(!DisplayOptions::default().starts_paused());
+        assert!(DisplayOptions { start_paused: true, ..Default::default() }.starts_paused());
+        assert!(!DisplayOptions { start_paused: true, headless: true, ..Default::default() }.starts_paused());
+    }
+
+    #[test]
+    fn test_hold_first_applies_only_to_first_frame() {
+        let options = DisplayOptions {
+            delay: Some(Duration::from_millis(100)),
+            hold_first: Some(Duration::from_secs(2)),
+            ..Default::default()
+        };
+
+        assert_eq!(options.frame_time(true), Some(Duration::from_secs(2)));
+        assert_eq!(options.frame_time(false), Some(Duration::from_millis(100)));
+        assert_eq!(DisplayOptions { delay: None, ..options }.frame_time(false), None);
+        assert_eq!(DisplayOptions::default().frame_time(true), None);
+    }
+
+    #[test]
+    fn test_wait_for_start_reads_one_line() {
+        let mut input = io::Cursor::new("\nrest\n");
+
+        wait_for_start(&mut input).unwrap();
+
+        assert_eq!(input.position(), 1);
+    }
+
+    #[test]
+    fn test_grid_frame_with_inverted_display() {
+        let grid = vec![vec![true, false, false]];
+
+        assert_eq!(
+            grid_frame(&grid, 1, &DisplayOptions { invert_display: true, ..Default::default() }, None),
+            ". # #\nIteration: 1\n"
+        );
+        let options = DisplayOptions { glyphs: Theme::Dark.glyphs(), invert_display: true, ..Default::default() };
+        assert_eq!(grid_frame(&grid, 1, &options, None), "  █ █\nIteration: 1\n");
+    }
+
+    #[test]
+    fn test_theme_glyphs() {
+        assert_eq!(Glyphs::resolve(None, None, None), Glyphs { alive: '#', dead: '.' });
+        assert_eq!(Glyphs::resolve(Some(Theme::Dark), None, None), Glyphs { alive: '█', dead: ' ' });
+        assert_eq!(Glyphs::resolve(Some(Theme::Light), None, None), Glyphs { alive: ' ', dead: '█' });
+        assert_eq!(Glyphs::resolve(Some(Theme::Dark), Some('o'), None), Glyphs { alive: 'o', dead: ' ' });
+        assert_eq!(Glyphs::resolve(Some(Theme::Light), Some('@'), Some('-')), Glyphs { alive: '@', dead: '-' });
+    }
+
+    #[test]
+    fn test_grid_frame_with_glyphs() {
+        let grid = vec![vec![true, false]];
+        let options = DisplayOptions { glyphs: Theme::Dark.glyphs(), ..Default::default() };
+
+        assert_eq!(grid_frame(&grid, 1, &options, None), "█  \nIteration: 1\n");
+    }
+
+    #[test]
+    fn test_autocorrelation_line() {
+        assert_eq!(autocorrelation_line(4, 0.98765), "Autocorrelation (lag 4): 0.988");
+        assert_eq!(autocorrelation_line(1, -0.5), "Autocorrelation (lag 1): -0.500");
+    }
+
+    #[test]
+    fn test_births_line() {
+        assert_eq!(births_line(12, 7), "Born: 12 Died: 7 Net: +5");
+        assert_eq!(births_line(0, 3), "Born: 0 Died: 3 Net: -3");
+        assert_eq!(births_line(0, 0), "Born: 0 Died: 0 Net: +0");
+    }
+
+    #[test]
+    fn test_status_line() {
+        let grid = vec![vec![true, false], vec![false, true]];
+
+        assert_eq!(status_line(&grid, 7, &DisplayOptions::default()), "Iteration: 7");
+        assert_eq!(
+            status_line(&grid, 7, &DisplayOptions { show_entropy: true, ..Default::default() }),
+            "Iteration: 7  H: 1.000"
+        );
+        assert_eq!(
+            status_line(&grid, 7, &DisplayOptions { show_bbox: true, ..Default::default() }),
+            "Iteration: 7  BBox: 2x2 at (0,0)"
+        );
+        assert_eq!(
+            status_line(&vec![vec![false; 2]], 7, &DisplayOptions { show_bbox: true, ..Default::default() }),
+            "Iteration: 7  BBox: empty"
+        );
+        assert_eq!(
+            status_line(&grid, 7, &DisplayOptions { region: Some((1, 0, 1, 1)), ..Default::default() }),
+            "Iteration: 7  Region: 1"
+        );
+    }
+}
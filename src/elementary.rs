@@ -0,0 +1,161 @@
+#[cfg(feature = "terminal")]
+use std::io::Write;
+#[cfg(feature = "terminal")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "terminal")]
+use std::thread;
+#[cfg(feature = "terminal")]
+use std::time::Instant;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::game::SimulationConfig;
+#[cfg(feature = "terminal")]
+use crate::game::{detect_steady_state, print_termination_message, GridHashCache, SLEEP_DURATION};
+#[cfg(feature = "terminal")]
+use crate::metrics::{PopulationTracker, SimulationMetrics, TerminationReason};
+
+/// Computes the next generation of an elementary (Wolfram) cellular automaton.
+///
+/// Bit `n` of the rule is the next state of a cell whose left, center and right cells
+/// spell `n` in binary. Cells beyond the edges are treated as dead.
+pub fn compute_1d_generation(row: &[bool], rule: u8) -> Vec<bool> {
+    (0..row.len())
+        .map(|i| {
+            let left = i > 0 && row[i - 1];
+            let right = i + 1 < row.len() && row[i + 1];
+            let pattern = (left as u8) << 2 | (row[i] as u8) << 1 | right as u8;
+            rule >> pattern & 1 == 1
+        })
+        .collect()
+}
+
+/// Creates the first row: random cells with the configured density, or a single live center cell.
+pub fn create_initial_row(config: &SimulationConfig) -> Vec<bool> {
+    let (_, width) = config.dimensions();
+    match config.density {
+        Some(density) => {
+            let mut rng = StdRng::seed_from_u64(config.seed);
+            (0..width).map(|_| rng.gen_bool(density)).collect()
+        }
+        None => {
+            let mut row = vec![false; width];
+            if let Some(center) = row.get_mut(width / 2) {
+                *center = true;
+            }
+            row
+        }
+    }
+}
+
+/// Runs the elementary automaton, printing each generation below the previous one as a space-time diagram.
+#[cfg(feature = "terminal")]
+pub fn run_1d_simulation(config: &SimulationConfig, rule: u8, interrupted: &AtomicBool) -> SimulationMetrics {
+    let started = Instant::now();
+    let mut row = create_initial_row(config);
+    let mut history: GridHashCache<Vec<bool>> = GridHashCache::new();
+    let mut tracker = PopulationTracker::default();
+    let mut iteration = 0;
+
+    println!("Rule {}", rule);
+
+    let termination_reason = loop {
+        if iteration >= config.iteration_limit() {
+            print_termination_message(TerminationReason::MaxIterations, iteration);
+            break TerminationReason::MaxIterations;
+        }
+
+        display_row(&row);
+
+        let population = row.iter().filter(|&&cell| cell).count();
+        tracker.record(iteration, population, row.len());
+
+        if config.exit_steady {
+            let termination = if population == 0 {
+                Some(TerminationReason::Extinction)
+            } else {
+                detect_steady_state(&row, &mut history).map(TerminationReason::SteadyState)
+            };
+            if let Some(reason) = termination {
+                print_termination_message(reason, iteration);
+                break reason;
+            }
+        }
+
+        if interrupted.load(Ordering::SeqCst) {
+            break TerminationReason::UserInterrupt;
+        }
+
+        row = compute_1d_generation(&row, rule);
+        iteration += 1;
+
+        thread::sleep(SLEEP_DURATION);
+    };
+
+    let final_population = row.iter().filter(|&&cell| cell).count();
+    tracker.finish(iteration, final_population, started.elapsed(), termination_reason)
+}
+
+/// Prints a single generation as a new line of the space-time diagram.
+#[cfg(feature = "terminal")]
+fn display_row(row: &[bool]) {
+    let output: String = row.iter().map(|&cell| if cell { "# " } else { ". " }).collect();
+    println!("{}", output);
+    std::io::stdout().flush().expect("Failed to flush stdout");
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_row(row: &str) -> Vec<bool> {
+        row.chars().map(|c| c == '#').collect()
+    }
+
+    fn assert_generations(rule: u8, expected: &[&str]) {
+        let mut row = parse_row(expected[0]);
+        for generation in &expected[1..] {
+            row = compute_1d_generation(&row, rule);
+            assert_eq!(row, parse_row(generation));
+        }
+    }
+
+    #[test]
+    fn test_rule_30() {
+        assert_generations(30, &[
+            ".....#.....",
+            "....###....",
+            "...##..#...",
+            "..##.####..",
+            ".##..#...#.",
+            "##.####.###",
+        ]);
+    }
+
+    #[test]
+    fn test_rule_110() {
+        assert_generations(110, &[
+            "...........#",
+            "..........##",
+            ".........###",
+            "........##.#",
+            ".......#####",
+            "......##...#",
+        ]);
+    }
+
+    #[test]
+    fn test_create_initial_row_with_single_center_cell() {
+        let config = SimulationConfig { grid_size: 5, ..Default::default() };
+
+        assert_eq!(create_initial_row(&config), parse_row("..#.."));
+    }
+
+    #[test]
+    fn test_create_initial_row_with_density() {
+        let config = SimulationConfig { grid_size: 8, density: Some(1.0), ..Default::default() };
+
+        assert!(create_initial_row(&config).iter().all(|&cell| cell));
+    }
+}
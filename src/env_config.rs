@@ -0,0 +1,81 @@
+use std::env;
+use std::str::FromStr;
+
+use crate::builder::ConfigError;
+use crate::game::Topology;
+use crate::rules::Rules;
+
+/// Settings read from `RGOL_*` environment variables, each left unset when its variable is.
+///
+/// They are the lowest-priority configuration layer: anything given on the command line wins.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PartialSimulationConfig {
+    /// `RGOL_GRID_SIZE`
+    pub grid_size: Option<usize>,
+    /// `RGOL_DELAY_MS`, the time each frame is shown.
+    pub delay_ms: Option<u64>,
+    /// `RGOL_RULES`, in any form accepted by [`Rules`].
+    pub rule: Option<Rules>,
+    /// `RGOL_TOPOLOGY`, e.g. `toroidal` or `cylindrical-h`.
+    pub topology: Option<Topology>,
+    /// `RGOL_SEED`
+    pub seed: Option<u64>,
+    /// `RGOL_DENSITY`
+    pub density: Option<f64>,
+    /// `RGOL_MAX_ITERATIONS`
+    pub max_iterations: Option<u64>,
+}
+
+/// Reads the configuration from the `RGOL_*` environment variables.
+///
+/// Unset variables are skipped; a variable that is set but doesn't parse is an error.
+pub fn parse_env_config() -> Result<PartialSimulationConfig, ConfigError> {
+    Ok(PartialSimulationConfig {
+        grid_size: env_value("RGOL_GRID_SIZE")?,
+        delay_ms: env_value("RGOL_DELAY_MS")?,
+        rule: env_value("RGOL_RULES")?,
+        topology: env_var("RGOL_TOPOLOGY")
+            .map(|value| {
+                serde_json::from_value(serde_json::Value::String(value))
+                    .map_err(|_| ConfigError::Environment("RGOL_TOPOLOGY"))
+            })
+            .transpose()?,
+        seed: env_value("RGOL_SEED")?,
+        density: env_value("RGOL_DENSITY")?,
+        max_iterations: env_value("RGOL_MAX_ITERATIONS")?,
+    })
+}
+
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok()
+}
+
+fn env_value<T: FromStr>(name: &'static str) -> Result<Option<T>, ConfigError> {
+    env_var(name)
+        .map(|value| value.trim().parse().map_err(|_| ConfigError::Environment(name)))
+        .transpose()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_env_config() {
+        env::set_var("RGOL_GRID_SIZE", "42");
+        env::set_var("RGOL_TOPOLOGY", "cylindrical-h");
+
+        let config = parse_env_config().unwrap();
+
+        assert_eq!(config.grid_size, Some(42));
+        assert_eq!(config.topology, Some(Topology::CylindricalHorizontal));
+
+        env::set_var("RGOL_SEED", "seven");
+        assert_eq!(parse_env_config().unwrap_err(), ConfigError::Environment("RGOL_SEED"));
+
+        env::remove_var("RGOL_GRID_SIZE");
+        env::remove_var("RGOL_TOPOLOGY");
+        env::remove_var("RGOL_SEED");
+    }
+}
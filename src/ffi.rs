@@ -0,0 +1,72 @@
+//! C interface to the simulation, for use from C or through e.g. Python's `ctypes`.
+//!
+//! Grids are opaque pointers created with [`grid_new`] and released with [`grid_free`]; they
+//! step under Conway's rule with a finite topology. Out of range cells read as dead and writes
+//! to them are ignored. A C header can be generated with `cbindgen --config cbindgen.toml`.
+
+use crate::game::{compute_next_generation, count_population, initialize_grid, Grid, Topology};
+
+/// Creates a grid of dead cells, to be released with [`grid_free`].
+#[no_mangle]
+pub extern "C" fn grid_new(rows: usize, cols: usize) -> *mut Grid {
+    Box::into_raw(Box::new(initialize_grid(rows, cols)))
+}
+
+/// Releases a grid created with [`grid_new`]; a null pointer is ignored.
+///
+/// # Safety
+///
+/// `p` must be null or a pointer returned by [`grid_new`] that hasn't been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn grid_free(p: *mut Grid) {
+    if !p.is_null() {
+        drop(Box::from_raw(p));
+    }
+}
+
+/// Replaces the grid with its next generation.
+///
+/// # Safety
+///
+/// `p` must be a live pointer returned by [`grid_new`].
+#[no_mangle]
+pub unsafe extern "C" fn grid_step(p: *mut Grid) {
+    let grid = &mut *p;
+    let mut next = grid.clone();
+    compute_next_generation(grid, &mut next, Topology::Finite);
+    *grid = next;
+}
+
+/// Whether the cell at row `r` and column `c` is alive.
+///
+/// # Safety
+///
+/// `p` must be a live pointer returned by [`grid_new`].
+#[no_mangle]
+pub unsafe extern "C" fn grid_get(p: *const Grid, r: usize, c: usize) -> bool {
+    let grid = &*p;
+    grid.get(r).and_then(|row| row.get(c)).copied().unwrap_or(false)
+}
+
+/// Brings the cell at row `r` and column `c` to life or kills it.
+///
+/// # Safety
+///
+/// `p` must be a live pointer returned by [`grid_new`].
+#[no_mangle]
+pub unsafe extern "C" fn grid_set(p: *mut Grid, r: usize, c: usize, val: bool) {
+    let grid = &mut *p;
+    if let Some(cell) = grid.get_mut(r).and_then(|row| row.get_mut(c)) {
+        *cell = val;
+    }
+}
+
+/// Number of live cells of the grid.
+///
+/// # Safety
+///
+/// `p` must be a live pointer returned by [`grid_new`].
+#[no_mangle]
+pub unsafe extern "C" fn grid_live_count(p: *const Grid) -> usize {
+    count_population(&*p)
+}
@@ -1,211 +1,3543 @@
-use std::collections::VecDeque;
-use std::io::{Write};
-use std::{thread, time};
-use rand::Rng;
-
-const MAX_HISTORY: usize = 10;
-const MAX_ITERATIONS: i32 = i32::MAX;
-const SLEEP_DURATION: time::Duration = time::Duration::from_millis(50);
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::error::Error;
+use std::fmt;
+use std::io;
+#[cfg(feature = "terminal")]
+use std::io::Write;
+use std::str::FromStr;
+use std::hash::{Hash, Hasher};
+#[cfg(feature = "terminal")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "terminal")]
+use std::sync::mpsc::{self, Receiver, SyncSender};
+#[cfg(feature = "terminal")]
+use std::sync::Arc;
+#[cfg(feature = "terminal")]
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+#[cfg(feature = "terminal")]
+use std::thread;
+use rand::rngs::StdRng;
+#[cfg(feature = "terminal")]
+use clap::ValueEnum;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use tracing::{debug_span, trace, warn};
+#[cfg(feature = "terminal")]
+use tracing::{debug, info};
+
+#[cfg(feature = "terminal")]
+use crate::display::{
+    clear_screen, display_autocorrelation, display_births, display_comparison, display_diff, display_grid, display_heatmap, display_neighbor_counts,
+    display_rule_comparison, wait_for_start, DisplayOptions, LagBuffer,
+};
+#[cfg(feature = "terminal")]
+use crate::bell::{ring, BellWatcher};
+#[cfg(feature = "terminal")]
+use crate::checkpoint::{save_checkpoint, CheckpointRotation, CHECKPOINT_FILE};
+#[cfg(feature = "terminal")]
+use crate::color::CellAges;
+#[cfg(feature = "terminal")]
+use crate::heatmap::Heatmap;
+#[cfg(feature = "terminal")]
+use crate::terminal::{fit_grid_dimensions, query_terminal_size};
+#[cfg(feature = "tui")]
+use crate::tui::{Tui, TuiAction};
+use crate::checkpoint::CheckpointError;
+use crate::metrics::{binary_entropy, PopulationTracker, RunSummary, SimulationOutcome, TerminationReason};
+#[cfg(feature = "terminal")]
+use crate::metrics::POPULATION_WINDOW_LAGS;
+use crate::grid_ops::{count_births_and_deaths, count_live_in_region, grid_not};
+#[cfg(feature = "terminal")]
+use crate::grid_ops::grid_diff;
+use crate::packed::PackedGrid;
+use crate::ppm::GrayImage;
+use crate::rules::{RuleFn, Rules, SharedRuleFn};
+#[cfg(feature = "terminal")]
+use crate::svg::SvgAnimationWriter;
+#[cfg(feature = "terminal")]
+use crate::metrics::SimulationMetrics;
+
+pub const MAX_HISTORY: usize = 10;
+/// Population variance below which a full stabilization window counts as stable.
+pub const STABILIZATION_VARIANCE: f64 = 1.0;
+/// Probability of a live cell used by the seed modes other than `random` when no density is set.
+pub const DEFAULT_SEED_DENSITY: f64 = 0.2;
+#[cfg(feature = "terminal")]
+pub(crate) const SLEEP_DURATION: Duration = Duration::from_millis(50);
+
+/// Time left to sleep for a frame to last `target` when computing and drawing it took `elapsed`,
+/// `None` when the frame already overran its target.
+#[cfg(any(feature = "terminal", test))]
+fn remaining_frame_time(target: Duration, elapsed: Duration) -> Option<Duration> {
+    target.checked_sub(elapsed)
+}
+
+/// Spread of the intervals between drawn frames, telling how evenly they are paced.
+#[cfg(any(feature = "terminal", test))]
+#[derive(Clone, Copy, Debug, Default)]
+struct FrameJitter {
+    last: Option<Instant>,
+    intervals: u64,
+    sum: f64,
+    sum_squares: f64,
+}
+
+#[cfg(any(feature = "terminal", test))]
+impl FrameJitter {
+    /// Records a frame drawn at `now`.
+    fn tick(&mut self, now: Instant) {
+        if let Some(last) = self.last.replace(now) {
+            let interval = now.duration_since(last).as_secs_f64();
+            self.intervals += 1;
+            self.sum += interval;
+            self.sum_squares += interval * interval;
+        }
+    }
+
+    /// Standard deviation of the intervals between frames, zero until two intervals were recorded.
+    fn std_dev(&self) -> Duration {
+        if self.intervals < 2 {
+            return Duration::ZERO;
+        }
+        let mean = self.sum / self.intervals as f64;
+        let variance = (self.sum_squares / self.intervals as f64 - mean * mean).max(0.0);
+        Duration::from_secs_f64(variance.sqrt())
+    }
+}
+
 const NEIGHBOR_OFFSETS: [(i32, i32); 8] = [
     (-1, -1), (-1, 0), (-1, 1),
     (0, -1),          (0, 1),
     (1, -1), (1, 0), (1, 1),
 ];
 
-type Grid = Vec<Vec<bool>>;
+pub type Grid = Vec<Vec<bool>>;
+
+/// Describes which grid axes wrap around to the opposite edge.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "terminal", derive(ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum Topology {
+    /// Cells beyond any edge are treated as dead.
+    #[default]
+    Finite,
+    /// Columns wrap around, rows are bounded (horizontal tube).
+    #[serde(rename = "cylindrical-h")]
+    #[cfg_attr(feature = "terminal", value(name = "cylindrical-h"))]
+    CylindricalHorizontal,
+    /// Rows wrap around, columns are bounded (vertical tube).
+    #[serde(rename = "cylindrical-v")]
+    #[cfg_attr(feature = "terminal", value(name = "cylindrical-v"))]
+    CylindricalVertical,
+    /// Both axes wrap around (torus).
+    Toroidal,
+    /// Cells beyond any edge are treated as permanently alive, walling the grid in with life.
+    AliveBorder,
+}
+
+impl Topology {
+    /// Builds the topology from independent per-axis wrap flags.
+    pub fn from_axes(wrap_horizontal: bool, wrap_vertical: bool) -> Self {
+        match (wrap_horizontal, wrap_vertical) {
+            (false, false) => Topology::Finite,
+            (true, false) => Topology::CylindricalHorizontal,
+            (false, true) => Topology::CylindricalVertical,
+            (true, true) => Topology::Toroidal,
+        }
+    }
+
+    fn wraps_horizontally(self) -> bool {
+        matches!(self, Topology::CylindricalHorizontal | Topology::Toroidal)
+    }
+
+    fn wraps_vertically(self) -> bool {
+        matches!(self, Topology::CylindricalVertical | Topology::Toroidal)
+    }
+
+    /// State of the cells beyond a bounded edge.
+    fn off_grid_alive(self) -> bool {
+        self == Topology::AliveBorder
+    }
+}
+
+/// Symmetry imposed on the random initial grid by reflecting part of it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "terminal", derive(ValueEnum))]
+#[serde(rename_all = "kebab-case")]
+pub enum Mirror {
+    /// Left half reflected onto the right half.
+    Horizontal,
+    /// Top half reflected onto the bottom half.
+    Vertical,
+    /// Top-left quadrant reflected onto the other three quadrants.
+    Quad,
+}
+
+/// Where the random initial grid places its live cells.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SeedMode {
+    /// Live cells anywhere on the grid.
+    #[default]
+    Random,
+    /// Top-left quadrant seeded and reflected onto the other three.
+    Symmetric4Fold,
+    /// Live cells only in the band of `width` cells along the edges.
+    Edge { width: usize },
+    /// Live cells most likely near the center, following a 2D Gaussian.
+    DenseCenter,
+}
+
+impl fmt::Display for SeedMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SeedMode::Random => write!(f, "random"),
+            SeedMode::Symmetric4Fold => write!(f, "symmetric"),
+            SeedMode::Edge { width } => write!(f, "edge:{}", width),
+            SeedMode::DenseCenter => write!(f, "dense-center"),
+        }
+    }
+}
+
+/// Error returned when parsing an unknown seed mode.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseSeedModeError {
+    pub input: String,
+}
+
+impl fmt::Display for ParseSeedModeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Unknown seed mode `{}`, expected random, symmetric, edge:WIDTH or dense-center",
+            self.input
+        )
+    }
+}
+
+impl Error for ParseSeedModeError {}
+
+impl FromStr for SeedMode {
+    type Err = ParseSeedModeError;
+
+    /// Parses a mode written as by [`fmt::Display`]; `edge` alone is a band one cell wide.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "random" => Ok(SeedMode::Random),
+            "symmetric" => Ok(SeedMode::Symmetric4Fold),
+            "edge" => Ok(SeedMode::Edge { width: 1 }),
+            "dense-center" => Ok(SeedMode::DenseCenter),
+            _ => text
+                .strip_prefix("edge:")
+                .and_then(|width| width.parse().ok())
+                .filter(|&width| width > 0)
+                .map(|width| SeedMode::Edge { width })
+                .ok_or_else(|| ParseSeedModeError { input: text.to_string() }),
+        }
+    }
+}
+
+/// Settings that fully determine how a simulation evolves.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SimulationConfig {
+    /// Number of grid rows, and of columns too unless `grid_width` is set.
+    pub grid_size: usize,
+    /// Number of grid columns when the grid isn't square.
+    #[serde(default)]
+    pub grid_width: Option<usize>,
+    pub exit_steady: bool,
+    /// Report the first steady state or oscillation found by `exit_steady` and keep running instead of stopping.
+    #[serde(default)]
+    pub quiet_steady: bool,
+    pub topology: Topology,
+    /// Seed of the random generator used for the initial grid.
+    pub seed: u64,
+    /// Number of generations computed without rendering before the animation starts.
+    #[serde(default)]
+    pub warmup: u64,
+    /// Probability of each cell being alive initially; a random number of cells is seeded when unset.
+    #[serde(default)]
+    pub density: Option<f64>,
+    /// Flip every seeded cell, so the density applies to dead cells instead.
+    #[serde(default)]
+    pub invert: bool,
+    /// Symmetry imposed on the seeded grid.
+    #[serde(default)]
+    pub mirror: Option<Mirror>,
+    /// Where the seeded grid places its live cells.
+    #[serde(default)]
+    pub seed_mode: SeedMode,
+    /// Step the grid using packed `u64` rows when it is finite and at most 64 columns wide.
+    #[serde(default)]
+    pub packed: bool,
+    /// Birth and survival rule of the 2D grid, Conway's `B3/S23` by default.
+    #[serde(default)]
+    pub rule: Rules,
+    /// Rule deciding every cell's next state in place of `rule`, e.g. a Lua script. It can't be
+    /// saved, so recordings and checkpoints only keep `rule`.
+    #[serde(skip)]
+    pub rule_fn: Option<SharedRuleFn>,
+    /// Only recompute cells next to the ones that changed in the previous generation.
+    #[serde(default)]
+    pub active_cells: bool,
+    /// Keep the neighbor count of every cell, updating it only around the cells that changed.
+    #[serde(default)]
+    pub incremental_counts: bool,
+    /// Kill the cells of the outermost rows and columns after every generation.
+    #[serde(default)]
+    pub dead_border: bool,
+    /// Remember every state for steady state detection instead of only the last [`MAX_HISTORY`].
+    /// Each generation keeps a copy of the grid in memory until the run ends.
+    #[serde(default)]
+    pub full_history: bool,
+    /// Stop once the population varies less than [`STABILIZATION_VARIANCE`] over this many generations.
+    #[serde(default)]
+    pub stabilize: Option<usize>,
+    /// Stop as soon as a generation has no births and no deaths, without comparing it to earlier states.
+    #[serde(default)]
+    pub terminate_on_quiescence: bool,
+    /// Generation at which the run stops; the run is unlimited when unset.
+    #[serde(default)]
+    pub max_iterations: Option<u64>,
+    /// Wall-clock time after which the run stops, whatever generation it has reached.
+    #[serde(default)]
+    pub max_runtime: Option<Duration>,
+    /// Save a checkpoint every this many generations while the simulation is shown.
+    #[serde(default)]
+    pub checkpoint_interval: Option<u64>,
+    /// Save a separately numbered checkpoint every this many generations.
+    #[serde(default)]
+    pub checkpoint_every: Option<u64>,
+    /// Number of the newest numbered checkpoints left on disk; all are kept when unset.
+    #[serde(default)]
+    pub keep_checkpoints: Option<usize>,
+    /// Number of threads computing each generation, one when unset. Seeding always runs on a
+    /// single thread, so a seed gives the same run for any thread count.
+    #[serde(default)]
+    pub threads: Option<usize>,
+    /// Rectangle `(r1, c1, r2, c2)` whose population is tracked next to the whole grid's.
+    #[serde(default)]
+    pub region: Option<(usize, usize, usize, usize)>,
+    /// Cell `(x, y)`, at column x of row y, whose state and neighbor count are recorded every generation.
+    #[serde(default)]
+    pub trace: Option<(usize, usize)>,
+}
+
+impl SimulationConfig {
+    /// Returns the grid dimensions as `(rows, cols)`.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.grid_size, self.grid_width.unwrap_or(self.grid_size))
+    }
+
+    /// Generation at which the run stops unless it terminates earlier, `u64::MAX` when unlimited.
+    pub fn iteration_limit(&self) -> u64 {
+        self.max_iterations.unwrap_or(u64::MAX)
+    }
+}
+
+/// Initializes the grid with all cells set to `false`.
+pub fn initialize_grid(rows: usize, cols: usize) -> Grid {
+    vec![vec![false; cols]; rows]
+}
+
+/// Returns the grid dimensions as `(rows, cols)`.
+pub fn grid_dimensions(grid: &Grid) -> (usize, usize) {
+    (grid.len(), grid.first().map_or(0, |row| row.len()))
+}
+
+/// Plain-text rendering of a grid, returned by [`display_cells`].
+///
+/// Live cells are drawn as `#` and dead cells as `.`, separated by spaces, one row per line.
+#[derive(Clone, Copy, Debug)]
+pub struct GridDisplay<'a> {
+    grid: &'a Grid,
+}
+
+/// Returns an object that formats the grid as plain text with `{}`, e.g. for tests or logging.
+///
+/// `Grid` is a type alias, so it can't implement [`fmt::Display`] itself.
+pub fn display_cells(grid: &Grid) -> GridDisplay<'_> {
+    GridDisplay { grid }
+}
+
+impl fmt::Display for GridDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.grid {
+            for (col, &cell) in row.iter().enumerate() {
+                if col > 0 {
+                    f.write_str(" ")?;
+                }
+                f.write_str(if cell { "#" } else { "." })?;
+            }
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when parsing a grid from text fails.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// A character other than `#`, `.` or a space; holds the one-based line and the character.
+    UnexpectedChar { line: usize, character: char },
+    /// A row whose length differs from the first row.
+    RaggedRow { line: usize, expected: usize, found: usize },
+}
+
+impl fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseGridError::UnexpectedChar { line, character } => {
+                write!(f, "Unexpected character `{}` on line {}", character, line)
+            }
+            ParseGridError::RaggedRow { line, expected, found } => {
+                write!(f, "Row on line {} has {} cells, expected {}", line, found, expected)
+            }
+        }
+    }
+}
+
+impl Error for ParseGridError {}
+
+/// Error returned when reading a grid in, stepping it and writing it out fails.
+#[derive(Debug)]
+pub enum GameError {
+    Io(io::Error),
+    Parse(ParseGridError),
+    /// A checkpoint couldn't be encoded, or the file isn't a checkpoint of this version.
+    Checkpoint(CheckpointError),
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GameError::Io(err) => err.fmt(f),
+            GameError::Parse(err) => err.fmt(f),
+            GameError::Checkpoint(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for GameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            GameError::Io(err) => Some(err),
+            GameError::Parse(err) => Some(err),
+            GameError::Checkpoint(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for GameError {
+    fn from(err: io::Error) -> Self {
+        GameError::Io(err)
+    }
+}
+
+impl From<ParseGridError> for GameError {
+    fn from(err: ParseGridError) -> Self {
+        GameError::Parse(err)
+    }
+}
+
+impl From<CheckpointError> for GameError {
+    fn from(err: CheckpointError) -> Self {
+        GameError::Checkpoint(err)
+    }
+}
+
+/// Parses a grid from text in the format produced by [`display_cells`].
+///
+/// `#` is a live cell and `.` a dead one; spaces between cells are optional and blank lines are skipped.
+pub fn parse_grid(text: &str) -> Result<Grid, ParseGridError> {
+    let mut grid: Grid = Vec::new();
+    for (index, line) in text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()) {
+        let row = parse_row(line.chars().filter(|&character| character != ' '), '#', index + 1)?;
+
+        if let Some(first) = grid.first() {
+            if row.len() != first.len() {
+                return Err(ParseGridError::RaggedRow { line: index + 1, expected: first.len(), found: row.len() });
+            }
+        }
+        grid.push(row);
+    }
+    Ok(grid)
+}
+
+/// Parses the cells of one row of a text grid, `alive` marking live cells and `.` dead ones.
+///
+/// `line` is the one-based line number reported for any other character.
+pub(crate) fn parse_row(cells: impl Iterator<Item = char>, alive: char, line: usize) -> Result<Vec<bool>, ParseGridError> {
+    cells
+        .map(|character| match character {
+            '.' => Ok(false),
+            character if character == alive => Ok(true),
+            character => Err(ParseGridError::UnexpectedChar { line, character }),
+        })
+        .collect()
+}
+
+/// Copies the grid into a new one with the given dimensions, anchored at the top-left corner.
+///
+/// Live cells outside of the new dimensions are dropped; added cells are dead.
+pub fn resize_grid(grid: &Grid, rows: usize, cols: usize) -> Grid {
+    let mut resized = initialize_grid(rows, cols);
+    for (new_row, row) in resized.iter_mut().zip(grid) {
+        let width = cols.min(row.len());
+        new_row[..width].copy_from_slice(&row[..width]);
+    }
+    resized
+}
+
+/// Creates the initial grid for the simulation, seeded from the configured seed.
+pub fn create_seeded_grid(config: &SimulationConfig) -> Grid {
+    let (rows, cols) = config.dimensions();
+    let mut grid = initialize_grid(rows, cols);
+    let density = config.density.unwrap_or(DEFAULT_SEED_DENSITY);
+    match (config.seed_mode, config.density) {
+        (SeedMode::Random, Some(density)) => seed_grid_with_density(&mut grid, config.seed, density),
+        (SeedMode::Random, None) => seed_grid_with_random_cells(&mut grid, config.seed),
+        (SeedMode::Symmetric4Fold, _) => seed_symmetric_4fold(&mut grid, config.seed, density),
+        (SeedMode::Edge { width }, _) => seed_edge(&mut grid, config.seed, density, width),
+        (SeedMode::DenseCenter, _) => seed_dense_center(&mut grid, config.seed, density),
+    }
+    if let Some(mirror) = config.mirror {
+        apply_mirror(&mut grid, mirror);
+    }
+    if config.invert {
+        invert(&mut grid);
+    }
+    grid
+}
+
+/// Seed of the run following one seeded with `seed`, so a sequence of runs is reproducible.
+pub fn next_seed(seed: u64) -> u64 {
+    seed.wrapping_add(1)
+}
+
+/// Grid evolving generation by generation, free of any terminal I/O.
+///
+/// This is the core used when the crate is embedded, e.g. compiled to WebAssembly.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Universe {
+    grid: Grid,
+    next: Grid,
+    topology: Topology,
+    generation: u64,
+    rules: Rules,
+    /// Rule replacing `rules` and the specialized stepping backends when set.
+    rule_fn: Option<SharedRuleFn>,
+    packed: bool,
+    /// Cells that may change in the next generation, when active cell tracking is on.
+    active: Option<ActiveCells>,
+    /// Live neighbors of every cell, when incremental counting is on.
+    counts: Option<NeighborCounts>,
+    dead_border: bool,
+    threads: usize,
+    /// Cells born and died in the latest step, `None` before the first one.
+    changes: Option<(u64, u64)>,
+}
+
+impl Universe {
+    /// Creates a universe starting from the given grid.
+    pub fn new(grid: Grid, topology: Topology) -> Self {
+        Universe {
+            next: grid.clone(),
+            grid,
+            topology,
+            generation: 0,
+            rules: Rules::conway(),
+            rule_fn: None,
+            packed: false,
+            active: None,
+            counts: None,
+            dead_border: false,
+            threads: 1,
+            changes: None,
+        }
+    }
+
+    /// Evolves the grid with the given rule instead of Conway's `B3/S23`.
+    pub fn with_rules(mut self, rules: Rules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Evolves the grid by asking `rule` for every cell's next state, in place of the rulestring.
+    ///
+    /// A rule function is called cell by cell on a single thread, so the packed, active cell,
+    /// incremental and multi-threaded stepping are skipped while it is set.
+    pub fn with_rule_fn(mut self, rule: Option<SharedRuleFn>) -> Self {
+        self.rule_fn = rule;
+        self
+    }
+
+    /// Steps using the packed bitboard representation when the grid supports it.
+    ///
+    /// Packing is only possible for Conway's rule, a finite topology and at most
+    /// [`MAX_PACKED_WIDTH`](crate::packed::MAX_PACKED_WIDTH) columns;
+    /// other grids keep using the standard representation.
+    pub fn with_packed(mut self, packed: bool) -> Self {
+        self.packed = packed;
+        self
+    }
+
+    /// Splits the rows of each generation between the given number of threads.
+    ///
+    /// Every cell only depends on the previous generation, so the grids are identical for any
+    /// thread count. Packed and active cell stepping stay single-threaded.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Only recomputes cells that changed in the previous generation and their neighbors.
+    ///
+    /// The result is bit-identical to recomputing every cell; it just skips the settled parts of the grid.
+    pub fn with_active_cells(mut self, active_cells: bool) -> Self {
+        let (rows, cols) = grid_dimensions(&self.grid);
+        self.active = active_cells.then(|| ActiveCells::all(rows, cols));
+        self
+    }
+
+    /// Keeps the neighbor count of every cell between generations, adjusting the counts around the
+    /// cells that changed instead of counting every neighborhood again.
+    ///
+    /// The result is bit-identical to recounting; active cell tracking takes precedence when both are on.
+    pub fn with_incremental_counts(mut self, incremental_counts: bool) -> Self {
+        self.counts = incremental_counts.then(|| NeighborCounts::new(&self.grid, self.topology));
+        self
+    }
+
+    /// Kills the cells of the outermost rows and columns after every generation, a fixed dead boundary.
+    pub fn with_dead_border(mut self, dead_border: bool) -> Self {
+        self.dead_border = dead_border;
+        self
+    }
+
+    /// Starts counting generations from the given one, e.g. when continuing from a checkpoint.
+    pub fn with_generation(mut self, generation: u64) -> Self {
+        self.generation = generation;
+        self
+    }
+
+    /// Creates a universe with a random initial grid described by the configuration.
+    pub fn from_config(config: &SimulationConfig) -> Self {
+        Universe::new(create_seeded_grid(config), config.topology)
+            .with_rules(config.rule)
+            .with_rule_fn(config.rule_fn.clone())
+            .with_packed(config.packed)
+            .with_active_cells(config.active_cells)
+            .with_incremental_counts(config.incremental_counts)
+            .with_dead_border(config.dead_border)
+    }
+
+    /// Changes the grid dimensions, keeping the live cells that still fit (see [`resize_grid`]).
+    pub fn resize(&mut self, rows: usize, cols: usize) {
+        self.grid = resize_grid(&self.grid, rows, cols);
+        self.next = self.grid.clone();
+        self.changes = None;
+        self.reset_tracking();
+    }
+
+    /// Replaces the grid with a previously saved state of the same dimensions at the given generation.
+    pub fn restore(&mut self, grid: Grid, generation: u64) {
+        self.grid = grid;
+        self.generation = generation;
+        self.changes = None;
+        self.reset_tracking();
+    }
+
+    /// Brings the cell at the given row and column to life or kills it, e.g. when drawn on by hand;
+    /// cells outside of the grid are ignored.
+    ///
+    /// Only the tracked active cells and neighbor counts around the cell are updated, so editing
+    /// a cell costs the same on any grid.
+    pub fn set_cell(&mut self, row: usize, col: usize, alive: bool) {
+        let Some(cell) = self.grid.get_mut(row).and_then(|cells| cells.get_mut(col)) else {
+            return;
+        };
+        if *cell == alive {
+            return;
+        }
+        *cell = alive;
+        self.changes = None;
+        if let Some(active) = &mut self.active {
+            active.mark_neighborhood(row, col, self.topology);
+        }
+        if let Some(counts) = &mut self.counts {
+            counts.toggle(row, col, alive, self.topology);
+        }
+    }
+
+    /// Starts the active cell and neighbor count tracking over from the current grid.
+    fn reset_tracking(&mut self) {
+        let (rows, cols) = grid_dimensions(&self.grid);
+        if self.active.is_some() {
+            self.active = Some(ActiveCells::all(rows, cols));
+        }
+        if self.counts.is_some() {
+            self.counts = Some(NeighborCounts::new(&self.grid, self.topology));
+        }
+    }
+
+    /// Current state of the grid.
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    /// Number of generations computed so far.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Numbers of cells born and died in the latest step as `(births, deaths)`, `None` before the
+    /// first step or after the grid was replaced.
+    ///
+    /// The counts come from the cells the stepping backend looked at, so they cost little on top of the step.
+    pub fn changes(&self) -> Option<(u64, u64)> {
+        self.changes
+    }
+
+    /// Birth and survival rule the grid evolves under.
+    pub fn rules(&self) -> Rules {
+        self.rules
+    }
+
+    /// Copy of this universe at its current state that evolves under another rule from here on.
+    pub fn fork(&self, rules: Rules) -> Universe {
+        let mut fork = self.clone().with_rules(rules);
+        // The tracking wasn't kept up while a rule function stepped the grid
+        if fork.rule_fn.take().is_some() {
+            fork.reset_tracking();
+        }
+        fork
+    }
+
+    /// Advances the grid by one generation.
+    pub fn step_once(&mut self) {
+        let _span = debug_span!("generation", iteration = self.generation).entered();
+        let packed = match self.topology {
+            Topology::Finite if self.packed && self.rule_fn.is_none() && self.rules == Rules::conway() => PackedGrid::from_grid(&self.grid),
+            _ => None,
+        };
+        // After each step `next` holds the previous generation, which the counts and the dead border compare against
+        let changes = match (packed, &self.rule_fn, &mut self.active, &mut self.counts) {
+            (Some(packed), _, _, _) => {
+                let stepped = packed.step();
+                self.next = std::mem::replace(&mut self.grid, stepped.to_grid());
+                if self.counts.is_some() {
+                    self.counts = Some(NeighborCounts::new(&self.grid, self.topology));
+                }
+                packed.births_and_deaths(&stepped)
+            }
+            (None, Some(rule), _, _) => {
+                compute_next_generation_with_rule_fn(&self.grid, &mut self.next, self.topology, rule);
+                std::mem::swap(&mut self.grid, &mut self.next);
+                count_births_and_deaths(&self.next, &self.grid)
+            }
+            (None, None, Some(active), _) => {
+                let next_active = compute_next_generation_active(&self.grid, &mut self.next, self.topology, &self.rules, active);
+                let changes = count_changes_at(&self.grid, &self.next, &active.cells);
+                *active = next_active;
+                std::mem::swap(&mut self.grid, &mut self.next);
+                changes
+            }
+            (None, None, None, Some(counts)) => {
+                let changes = compute_next_generation_incremental(&self.grid, &mut self.next, self.topology, &self.rules, counts);
+                std::mem::swap(&mut self.grid, &mut self.next);
+                changes
+            }
+            (None, None, None, None) if self.threads > 1 => {
+                compute_next_generation_parallel(&self.grid, &mut self.next, self.topology, &self.rules, self.threads);
+                std::mem::swap(&mut self.grid, &mut self.next);
+                count_births_and_deaths(&self.next, &self.grid)
+            }
+            (None, None, None, None) => {
+                compute_next_generation_with_rules(&self.grid, &mut self.next, self.topology, &self.rules);
+                std::mem::swap(&mut self.grid, &mut self.next);
+                count_births_and_deaths(&self.next, &self.grid)
+            }
+        };
+        self.changes = Some(changes);
+        if self.dead_border {
+            self.kill_border();
+        }
+        self.generation += 1;
+    }
+
+    /// Enforces the dead border, keeping the tracked active cells and neighbor counts in step with the cells it kills.
+    fn kill_border(&mut self) {
+        let (rows, cols) = grid_dimensions(&self.grid);
+        let killed: Vec<(usize, usize)> = border_cells(rows, cols).filter(|&(row, col)| self.grid[row][col]).collect();
+        enforce_dead_border(&mut self.grid);
+        for (row, col) in killed {
+            // A killed cell that was alive before the step died; one that was just born never was
+            if let Some((births, deaths)) = &mut self.changes {
+                if self.next[row][col] {
+                    *deaths += 1;
+                } else {
+                    *births -= 1;
+                }
+            }
+            if let Some(active) = &mut self.active {
+                active.mark_neighborhood(row, col, self.topology);
+            }
+            if let Some(counts) = &mut self.counts {
+                counts.toggle(row, col, false, self.topology);
+            }
+        }
+    }
+}
+
+impl fmt::Display for Universe {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        display_cells(&self.grid).fmt(f)
+    }
+}
+
+impl FromStr for Universe {
+    type Err = ParseGridError;
+
+    /// Parses a universe with a finite topology from text, see [`parse_grid`].
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        Ok(Universe::new(parse_grid(text)?, Topology::Finite))
+    }
+}
+
+/// Runs the Conway's Game of Life simulation starting from the given grid.
+///
+/// Returns the run statistics together with the final grid.
+///
+/// The loop stops early when `interrupted` is set, e.g. by a `Ctrl+C` handler. In heatmap mode
+/// the grid itself isn't shown; the heatmap is drawn every `heatmap_interval` generations, if
+/// set, and once more when the run ends.
+#[cfg(feature = "terminal")]
+pub fn run_game_of_life_simulation(
+    config: &SimulationConfig,
+    display: &DisplayOptions,
+    initial: Grid,
+    interrupted: &AtomicBool,
+) -> (SimulationMetrics, Grid) {
+    resume_game_of_life_simulation(config, display, initial, 0, interrupted, &mut RunRecording::default())
+}
+
+/// Continues the simulation of a grid that has already reached the given generation.
+///
+/// With `checkpoint_interval` set, the grid is saved to [`CHECKPOINT_FILE`] every that many
+/// generations; a failed save is logged and the run goes on. Every generation, the final one
+/// included, is written to `recording` as it's computed.
+#[cfg(feature = "terminal")]
+pub fn resume_game_of_life_simulation(
+    config: &SimulationConfig,
+    display: &DisplayOptions,
+    grid: Grid,
+    generation: u64,
+    interrupted: &AtomicBool,
+    recording: &mut RunRecording,
+) -> (SimulationMetrics, Grid) {
+    let started = Instant::now();
+    let mut simulation = Simulation::resume(config, grid, generation);
+    simulation.recording = std::mem::take(recording);
+    if let Some(lag) = display.autocorrelation_lag {
+        simulation.tracker = new_tracker(config).with_populations(lag.saturating_mul(POPULATION_WINDOW_LAGS));
+    }
+    let mut rival = display.compare_rule.map(|rule| simulation.universe.fork(rule));
+    let mut termination = simulation.run_until(config.warmup);
+    if let Some(rival) = &mut rival {
+        while rival.generation() < simulation.universe.generation() {
+            rival.step_once();
+        }
+    }
+    debug!(seed = config.seed, warmup = config.warmup, "starting simulation");
+
+    let (rows, cols) = grid_dimensions(simulation.universe.grid());
+    let mut heatmap = display.heatmap.then(|| Heatmap::new(rows, cols));
+    let mut ages = display.color_by_age.then(|| CellAges::new(rows, cols));
+    let mut lag = display.compare_lag.map(LagBuffer::new);
+    let mut paused = display.starts_paused();
+    let mut diffs_left = display.debug_diff.unwrap_or(0);
+    let mut rotation = config.checkpoint_every.map(|_| CheckpointRotation::new(PathBuf::new(), config.keep_checkpoints));
+    let mut previous: Option<Grid> = None;
+
+    #[cfg(feature = "tui")]
+    let mut tui = match display.uses_tui().then(|| Tui::enter(display.scroll_step)).transpose() {
+        Ok(tui) => tui,
+        Err(err) => {
+            warn!(%err, "failed to start the TUI, drawing plain text instead");
+            clear_screen();
+            None
+        }
+    };
+    if !display.headless && !display.uses_tui() {
+        clear_screen();
+    }
+
+    let first_iteration = simulation.universe.generation();
+    let mut dropped_frames = 0u64;
+    let mut jitter = FrameJitter::default();
+    let mut bell = (!display.headless && !display.bell_on.is_empty()).then(|| BellWatcher::new(&display.bell_on));
+    let termination_reason = loop {
+        let mut frame_started = Instant::now();
+        let iteration = simulation.universe.generation();
+        let frame_time = display.frame_time(iteration == first_iteration).unwrap_or(SLEEP_DURATION);
+        if iteration >= config.iteration_limit() {
+            print_termination_message(TerminationReason::MaxIterations, iteration);
+            break TerminationReason::MaxIterations;
+        }
+
+        if display.fit && !display.headless {
+            if let Some((rows, cols)) = query_terminal_size().map(fit_grid_dimensions) {
+                if rows > 0 && cols > 0 && (rows, cols) != grid_dimensions(simulation.universe.grid()) {
+                    debug!(rows, cols, "terminal resized");
+                    simulation.universe.resize(rows, cols);
+                    heatmap = heatmap.map(|_| Heatmap::new(rows, cols));
+                    ages = ages.map(|_| CellAges::new(rows, cols));
+                    if !display.uses_tui() {
+                        clear_screen();
+                    }
+                }
+            }
+        }
+
+        let rendered = match &mut heatmap {
+            Some(heatmap) => {
+                heatmap.record(simulation.universe.grid());
+                let due = display.heatmap_interval.is_some_and(|interval| iteration.is_multiple_of(interval));
+                if due {
+                    display_heatmap(heatmap, display);
+                }
+                due
+            }
+            #[cfg(feature = "tui")]
+            None if tui.is_some() => {
+                if let Some(ages) = &mut ages {
+                    ages.update(simulation.universe.grid());
+                }
+                if let Some(tui) = &mut tui {
+                    match tui.show(&mut simulation.universe, display, ages.as_ref()) {
+                        Ok(TuiAction::Continue) => {}
+                        Ok(TuiAction::Quit) => interrupted.store(true, Ordering::SeqCst),
+                        Err(err) => warn!(%err, "failed to draw the TUI"),
+                    }
+                }
+                true
+            }
+            None if !display.headless => {
+                if let Some(ages) = &mut ages {
+                    ages.update(simulation.universe.grid());
+                }
+                let grid = simulation.universe.grid();
+                if let Some(rival) = &rival {
+                    display_rule_comparison(&simulation.universe, rival, display);
+                } else if let Some(lag) = &mut lag {
+                    lag.push(grid.clone());
+                    display_comparison(lag, grid, iteration, display);
+                } else if display.show_counts {
+                    display_neighbor_counts(grid, &neighbor_counts(grid, config.topology), iteration, display);
+                } else {
+                    display_grid(grid, iteration, display, ages.as_ref());
+                }
+                if let Some(lag) = display.autocorrelation_lag {
+                    display_autocorrelation(lag, simulation.tracker.autocorrelation(lag));
+                }
+                if display.show_births {
+                    let (births, deaths) = simulation.universe.changes().unwrap_or_default();
+                    display_births(births, deaths);
+                }
+                true
+            }
+            None => false,
+        };
+        if rendered {
+            jitter.tick(Instant::now());
+        }
+        if bell.as_mut().is_some_and(|bell| bell.observe(count_population(simulation.universe.grid()))) {
+            ring();
+        }
+
+        if rendered && display.debug_diff.is_some() {
+            let diffs = previous.take().and_then(|previous| grid_diff(&previous, simulation.universe.grid()).ok());
+            display_diff(&diffs.unwrap_or_default());
+        }
+
+        if paused && rendered {
+            paused = false;
+            if let Err(err) = wait_for_start(&mut io::stdin().lock()) {
+                warn!(%err, "failed to wait for start");
+            }
+            frame_started = Instant::now();
+        }
+
+        let announced = simulation.announced_cycle.is_some();
+        let stop = termination.take().or_else(|| simulation.observe());
+        let steady = matches!(stop, Some(TerminationReason::SteadyState(_))) || (!announced && simulation.announced_cycle.is_some());
+        if steady && bell.as_ref().is_some_and(BellWatcher::on_steady_state) {
+            ring();
+        }
+        if let Some(reason) = stop {
+            print_termination_message(reason, iteration);
+            break reason;
+        }
+
+        if interrupted.load(Ordering::SeqCst) {
+            break TerminationReason::UserInterrupt;
+        }
+
+        let checkpoint_due = config.checkpoint_interval.is_some_and(|interval| iteration.is_multiple_of(interval));
+        if checkpoint_due && iteration > generation {
+            let path = Path::new(CHECKPOINT_FILE);
+            match save_checkpoint(simulation.universe.grid(), iteration, config, path) {
+                Ok(()) => debug!(iteration, "saved checkpoint"),
+                Err(err) => warn!(iteration, %err, "failed to save checkpoint"),
+            }
+        }
+
+        if diffs_left > 0 {
+            diffs_left -= 1;
+            previous = Some(simulation.universe.grid().clone());
+        }
+        let numbered_due = config.checkpoint_every.is_some_and(|every| iteration.is_multiple_of(every));
+        if let Some(rotation) = rotation.as_mut().filter(|_| numbered_due && iteration > generation) {
+            match rotation.save(simulation.universe.grid(), iteration, config) {
+                Ok(()) => debug!(iteration, "saved numbered checkpoint"),
+                Err(err) => warn!(iteration, %err, "failed to save numbered checkpoint"),
+            }
+        }
+
+        simulation.universe.step_once();
+        if let Some(rival) = &mut rival {
+            rival.step_once();
+        }
+
+        if rendered {
+            match remaining_frame_time(frame_time, frame_started.elapsed()) {
+                Some(remaining) => thread::sleep(remaining),
+                None => dropped_frames += 1,
+            }
+        }
+    };
+
+    if let Some(heatmap) = &heatmap {
+        display_heatmap(heatmap, display);
+    }
+    if dropped_frames > 0 {
+        debug!(dropped_frames, "frames took longer than the frame time");
+    }
+    debug!(jitter = ?jitter.std_dev(), "frame pacing");
+
+    *recording = simulation.take_recording();
+    let last = simulation.universe.grid().clone();
+    (simulation.into_metrics(started, termination_reason), last)
+}
+
+/// Generation handed from the compute thread of [`run_threaded_simulation`] to its render thread.
+#[cfg(feature = "terminal")]
+enum RenderMessage {
+    Generation(Arc<Grid>, u64),
+    /// Sent once the run is over, after the last generation.
+    Finished,
+}
+
+/// Like [`resume_game_of_life_simulation`], computing the generations and drawing them on two threads.
+///
+/// The compute thread steps the universe, checks whether the run should stop and sends every
+/// generation through a channel holding a single one, so it gets at most a frame ahead of the
+/// render thread before waiting for it. Only the plain grid is drawn, colored by age if requested.
+#[cfg(feature = "terminal")]
+pub fn run_threaded_simulation(
+    config: &SimulationConfig,
+    display: &DisplayOptions,
+    grid: Grid,
+    generation: u64,
+    interrupted: &AtomicBool,
+    recording: &mut RunRecording,
+) -> (SimulationMetrics, Grid) {
+    let started = Instant::now();
+    let (sender, receiver) = mpsc::sync_channel(1);
+    clear_screen();
+    let started_recording = std::mem::take(recording);
+    let ((mut simulation, termination_reason), jitter) = thread::scope(|scope| {
+        let renderer = scope.spawn(move || render_generations(receiver, display));
+        let computer = scope.spawn(move || compute_generations(config, grid, generation, started_recording, sender, interrupted));
+        let computed = computer.join().expect("the compute thread panicked");
+        (computed, renderer.join().expect("the render thread panicked"))
+    });
+    if termination_reason != TerminationReason::UserInterrupt {
+        print_termination_message(termination_reason, simulation.universe.generation());
+    }
+    debug!(jitter = ?jitter.std_dev(), "frame pacing");
+
+    *recording = simulation.take_recording();
+    let last = simulation.universe.grid().clone();
+    (simulation.into_metrics(started, termination_reason), last)
+}
+
+/// Body of the compute thread of [`run_threaded_simulation`], returning the finished simulation.
+#[cfg(feature = "terminal")]
+fn compute_generations(
+    config: &SimulationConfig,
+    grid: Grid,
+    generation: u64,
+    recording: RunRecording,
+    sender: SyncSender<RenderMessage>,
+    interrupted: &AtomicBool,
+) -> (Simulation, TerminationReason) {
+    let mut simulation = Simulation::resume(config, grid, generation);
+    simulation.recording = recording;
+    let termination_reason = run_streamed(&mut simulation, config, interrupted, |grid, iteration| {
+        // Nothing is drawn anymore once the render thread is gone
+        sender.send(RenderMessage::Generation(Arc::new(grid.clone()), iteration)).is_ok()
+    });
+    // Fails only when the render thread is already gone
+    let _ = sender.send(RenderMessage::Finished);
+    (simulation, termination_reason)
+}
+
+/// Continues the simulation of a grid without drawing it, handing every generation to `observer`,
+/// e.g. to stream it elsewhere.
+///
+/// The run ends like with [`resume_game_of_life_simulation`], or when `interrupted` is set, and
+/// every generation is written to `recording`. Returns the run statistics together with the final grid.
+#[cfg(feature = "terminal")]
+pub fn run_streamed_simulation(
+    config: &SimulationConfig,
+    grid: Grid,
+    generation: u64,
+    interrupted: &AtomicBool,
+    recording: &mut RunRecording,
+    mut observer: impl FnMut(&Grid, u64),
+) -> (SimulationMetrics, Grid) {
+    let started = Instant::now();
+    let mut simulation = Simulation::resume(config, grid, generation);
+    simulation.recording = std::mem::take(recording);
+    let termination_reason = run_streamed(&mut simulation, config, interrupted, |grid, iteration| {
+        observer(grid, iteration);
+        true
+    });
+    if termination_reason != TerminationReason::UserInterrupt {
+        print_termination_message(termination_reason, simulation.universe.generation());
+    }
+
+    *recording = simulation.take_recording();
+    let last = simulation.universe.grid().clone();
+    (simulation.into_metrics(started, termination_reason), last)
+}
+
+/// Runs the warmup, then hands every generation to `observer` before checking whether the run
+/// should stop, until it terminates, `interrupted` is set or `observer` returns `false`.
+#[cfg(feature = "terminal")]
+fn run_streamed(
+    simulation: &mut Simulation,
+    config: &SimulationConfig,
+    interrupted: &AtomicBool,
+    mut observer: impl FnMut(&Grid, u64) -> bool,
+) -> TerminationReason {
+    let mut termination = simulation.run_until(config.warmup);
+    debug!(seed = config.seed, warmup = config.warmup, "starting streamed simulation");
+
+    loop {
+        let iteration = simulation.universe.generation();
+        if iteration >= config.iteration_limit() {
+            return TerminationReason::MaxIterations;
+        }
+        if !observer(simulation.universe.grid(), iteration) {
+            return TerminationReason::UserInterrupt;
+        }
+        if let Some(reason) = termination.take().or_else(|| simulation.observe()) {
+            return reason;
+        }
+        if interrupted.load(Ordering::SeqCst) {
+            return TerminationReason::UserInterrupt;
+        }
+        simulation.universe.step_once();
+    }
+}
+
+/// Body of the render thread of [`run_threaded_simulation`], drawing generations until told the run is over.
+#[cfg(feature = "terminal")]
+fn render_generations(receiver: Receiver<RenderMessage>, display: &DisplayOptions) -> FrameJitter {
+    let mut ages: Option<CellAges> = None;
+    let mut jitter = FrameJitter::default();
+    let mut first_frame = true;
+    while let Ok(RenderMessage::Generation(grid, iteration)) = receiver.recv() {
+        let frame_started = Instant::now();
+        if display.color_by_age {
+            let (rows, cols) = grid_dimensions(&grid);
+            ages.get_or_insert_with(|| CellAges::new(rows, cols)).update(&grid);
+        }
+        display_grid(&grid, iteration, display, ages.as_ref());
+        jitter.tick(Instant::now());
+
+        let frame_time = display.frame_time(first_frame).unwrap_or(SLEEP_DURATION);
+        first_frame = false;
+        if let Some(remaining) = remaining_frame_time(frame_time, frame_started.elapsed()) {
+            thread::sleep(remaining);
+        }
+    }
+    jitter
+}
+
+/// Runs the simulation without any rendering or pacing until it terminates.
+///
+/// Steady states and extinction are always detected, regardless of `exit_steady`. The run stops
+/// with [`SimulationOutcome::MaxIterations`] after `max_iterations` generations. Returns the
+/// outcome together with the final grid.
+pub fn run_headless(config: &SimulationConfig, initial: Grid, max_iterations: u64) -> (SimulationOutcome, Grid) {
+    run_with_observer(config, initial, max_iterations, |_, _| {})
+}
+
+/// Runs the simulation like [`run_headless`], calling `observer` with the grid and its generation after every step.
+///
+/// This is the hook for presenting or analysing a run from library code without reimplementing
+/// the loop, e.g. collecting populations or drawing each generation.
+pub fn run_with_observer(
+    config: &SimulationConfig,
+    initial: Grid,
+    max_iterations: u64,
+    observer: impl FnMut(&Grid, u64),
+) -> (SimulationOutcome, Grid) {
+    let mut simulation = Simulation::new(config, initial);
+    simulation.exit_steady = true;
+    simulation.quiet_steady = false;
+
+    let reason = simulation.run_observed(max_iterations, observer).unwrap_or(TerminationReason::MaxIterations);
+    let outcome = SimulationOutcome::new(reason, simulation.universe.generation());
+    (outcome, simulation.universe.grid().clone())
+}
+
+/// Runs the simulation headless like [`run_headless`] until it terminates, returning the statistics of the run.
+#[cfg(feature = "terminal")]
+pub fn run_headless_metrics(config: &SimulationConfig, initial: Grid) -> SimulationMetrics {
+    let started = Instant::now();
+    let mut simulation = Simulation::new(config, initial);
+    simulation.exit_steady = true;
+    simulation.quiet_steady = false;
+
+    let reason = simulation.run_until(config.iteration_limit()).unwrap_or(TerminationReason::MaxIterations);
+    simulation.into_metrics(started, reason)
+}
+
+/// Computes the given number of generations after the initial grid, returning every grid including the initial one.
+pub fn collect_frames(config: &SimulationConfig, initial: Grid, generations: u64) -> Vec<Grid> {
+    let mut universe = Universe::new(initial, config.topology)
+        .with_rules(config.rule)
+        .with_rule_fn(config.rule_fn.clone())
+        .with_dead_border(config.dead_border);
+    let mut frames = vec![universe.grid().clone()];
+    for _ in 0..generations {
+        universe.step_once();
+        frames.push(universe.grid().clone());
+    }
+    frames
+}
+
+/// Evolves one seeded grid under two configurations, e.g. to see how two rules treat the same start.
+///
+/// The grid is seeded with `shared_seed` as `config_a` describes; each copy then follows the
+/// topology, rule and dead border of its own configuration. Returns both grids of every generation
+/// up to `generations`, the initial ones included.
+pub fn run_ab_comparison(
+    config_a: &SimulationConfig,
+    config_b: &SimulationConfig,
+    shared_seed: u64,
+    generations: u64,
+) -> Vec<(Grid, Grid)> {
+    let initial = create_seeded_grid(&SimulationConfig { seed: shared_seed, ..config_a.clone() });
+    let universe = |config: &SimulationConfig| {
+        Universe::new(initial.clone(), config.topology)
+            .with_rules(config.rule)
+            .with_rule_fn(config.rule_fn.clone())
+            .with_dead_border(config.dead_border)
+    };
+    let (mut a, mut b) = (universe(config_a), universe(config_b));
+    let mut frames = vec![(a.grid().clone(), b.grid().clone())];
+    for _ in 0..generations {
+        a.step_once();
+        b.step_once();
+        frames.push((a.grid().clone(), b.grid().clone()));
+    }
+    frames
+}
+
+/// Outputs written generation by generation while a run goes on, e.g. the `--measure-entropy` CSV
+/// and the `--export-svg-anim` animation.
+///
+/// The simulation records every generation it observes, so edits made to the grid during the run
+/// show up as well. Failing to write stops the recording; the error is returned by [`RunRecording::finish`].
+#[cfg(feature = "terminal")]
+#[derive(Default)]
+pub struct RunRecording {
+    /// CSV of the density and block entropy of every generation.
+    entropy: Option<Box<dyn Write + Send>>,
+    /// Region whose population the entropy CSV adds.
+    region: Option<(usize, usize, usize, usize)>,
+    animation: Option<SvgAnimationWriter<Box<dyn Write + Send>>>,
+    /// First generation shown in the animation, e.g. the one after the warmup.
+    first_frame: u64,
+    /// Generation recorded last, so that one observed twice is recorded once.
+    last_generation: Option<u64>,
+    error: Option<io::Error>,
+}
+
+#[cfg(feature = "terminal")]
+impl RunRecording {
+    /// Writes the density and block entropy of every generation as CSV, with the total and region
+    /// populations when a region is given.
+    pub fn with_entropy_csv(self, mut writer: impl Write + Send + 'static, region: Option<(usize, usize, usize, usize)>) -> io::Result<Self> {
+        match region {
+            Some(_) => writeln!(writer, "generation,entropy,block_entropy,population,region_population")?,
+            None => writeln!(writer, "generation,entropy,block_entropy")?,
+        }
+        Ok(RunRecording { entropy: Some(Box::new(writer)), region, ..self })
+    }
+
+    /// Animates the generations from `first_frame` on.
+    pub fn with_svg_animation(self, writer: impl Write + Send + 'static, delay_ms: u64, cell_size: u32, first_frame: u64) -> Self {
+        RunRecording {
+            animation: Some(SvgAnimationWriter::new(Box::new(writer), delay_ms, cell_size)),
+            first_frame,
+            ..self
+        }
+    }
+
+    /// Records the grid of the given generation and its population; the `last` one of a run is always
+    /// animated if nothing else was.
+    fn record(&mut self, grid: &Grid, generation: u64, population: usize, last: bool) {
+        if self.error.is_some() {
+            return;
+        }
+        if let Err(err) = self.write_generation(grid, generation, population, last) {
+            warn!(generation, %err, "failed to record the generation, stopping the recording");
+            self.error = Some(err);
+        }
+    }
+
+    fn write_generation(&mut self, grid: &Grid, generation: u64, population: usize, last: bool) -> io::Result<()> {
+        let repeated = self.last_generation.replace(generation) == Some(generation);
+        if let Some(writer) = self.entropy.as_mut().filter(|_| !repeated) {
+            let (rows, cols) = grid_dimensions(grid);
+            write!(writer, "{},{:.6},{:.6}", generation, binary_entropy(population, rows * cols), block_entropy(grid))?;
+            if let Some((r1, c1, r2, c2)) = self.region {
+                write!(writer, ",{},{}", population, count_live_in_region(grid, r1, c1, r2, c2))?;
+            }
+            writeln!(writer)?;
+        }
+        if let Some(animation) = &mut self.animation {
+            let due = generation >= self.first_frame && !repeated;
+            if due || (last && animation.is_empty()) {
+                animation.push(grid)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Completes the outputs, returning the first error met while recording, if any.
+    pub fn finish(self) -> io::Result<()> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+        if let Some(mut writer) = self.entropy {
+            writer.flush()?;
+        }
+        if let Some(animation) = self.animation {
+            animation.finish()?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs the simulation headless up to the configured iteration limit and summarizes how it ended.
+pub fn summarize_run(config: &SimulationConfig, initial: Grid) -> RunSummary {
+    let (outcome, last) = run_headless(config, initial, config.iteration_limit());
+    RunSummary {
+        seed: config.seed,
+        final_population: count_population(&last),
+        outcome,
+    }
+}
+
+/// State of a running simulation together with the statistics gathered so far.
+struct Simulation {
+    universe: Universe,
+    history: GridHashCache,
+    exit_steady: bool,
+    quiet_steady: bool,
+    /// Iteration and period of the first cycle found while running on with `quiet_steady`.
+    announced_cycle: Option<(u64, usize)>,
+    stabilization: Option<PopulationWindow>,
+    region: Option<(usize, usize, usize, usize)>,
+    trace: Option<(usize, usize)>,
+    tracker: PopulationTracker,
+    /// When the run started and how long it may take, if it has a wall-clock limit.
+    runtime_limit: Option<(Instant, Duration)>,
+    terminate_on_quiescence: bool,
+    #[cfg(feature = "terminal")]
+    recording: RunRecording,
+}
+
+/// Tracker of the statistics the configuration asks for.
+fn new_tracker(config: &SimulationConfig) -> PopulationTracker {
+    let tracker = if config.region.is_some() { PopulationTracker::with_region() } else { PopulationTracker::default() };
+    match config.trace {
+        Some((x, y)) => tracker.with_trace(x, y),
+        None => tracker,
+    }
+}
+
+impl Simulation {
+    fn new(config: &SimulationConfig, initial: Grid) -> Self {
+        Simulation::resume(config, initial, 0)
+    }
+
+    /// Sets up a simulation of a grid that has already reached the given generation.
+    fn resume(config: &SimulationConfig, grid: Grid, generation: u64) -> Self {
+        Simulation {
+            universe: Universe::new(grid, config.topology)
+                .with_rules(config.rule)
+                .with_rule_fn(config.rule_fn.clone())
+                .with_packed(config.packed)
+                .with_active_cells(config.active_cells)
+                .with_incremental_counts(config.incremental_counts)
+                .with_dead_border(config.dead_border)
+                .with_threads(config.threads.unwrap_or(1))
+                .with_generation(generation),
+            history: if config.full_history { GridHashCache::unbounded() } else { GridHashCache::new() },
+            exit_steady: config.exit_steady,
+            quiet_steady: config.quiet_steady,
+            announced_cycle: None,
+            stabilization: config.stabilize.map(PopulationWindow::new),
+            region: config.region,
+            trace: config.trace,
+            tracker: new_tracker(config),
+            runtime_limit: config.max_runtime.map(|limit| (Instant::now(), limit)),
+            terminate_on_quiescence: config.terminate_on_quiescence,
+            #[cfg(feature = "terminal")]
+            recording: RunRecording::default(),
+        }
+    }
+
+    /// Updates the statistics with the current generation and checks whether the run should stop.
+    fn observe(&mut self) -> Option<TerminationReason> {
+        self.record_region();
+        self.record_cell();
+        let changes = self.universe.changes();
+        if let Some((births, deaths)) = changes {
+            self.tracker.record_changes(self.universe.generation(), births, deaths);
+        }
+        let grid = self.universe.grid();
+        let (rows, cols) = grid_dimensions(grid);
+        let population = count_population(grid);
+        self.tracker.record(self.universe.generation(), population, rows * cols);
+        #[cfg(feature = "terminal")]
+        self.recording.record(grid, self.universe.generation(), population, false);
+        trace!(iteration = self.universe.generation(), population, "observed generation");
+
+        if let Some(stable) = self.stabilization.as_mut().and_then(|window| window.push(population)) {
+            return Some(TerminationReason::Stabilized(stable));
+        }
+        if let Some((started, limit)) = self.runtime_limit {
+            let elapsed = started.elapsed();
+            if elapsed >= limit {
+                return Some(TerminationReason::MaxRuntime(elapsed));
+            }
+        }
+        if self.terminate_on_quiescence && changes == Some((0, 0)) {
+            return Some(if population == 0 { TerminationReason::Extinction } else { TerminationReason::SteadyState(1) });
+        }
+        if !self.exit_steady {
+            return None;
+        }
+        if is_extinct(grid) {
+            return Some(TerminationReason::Extinction);
+        }
+        let period = detect_steady_state(grid, &mut self.history)?;
+        if !self.quiet_steady {
+            return Some(TerminationReason::SteadyState(period));
+        }
+        if self.announced_cycle.is_none() {
+            let iteration = self.universe.generation();
+            warn!("Repeating or steady state with period {} detected at iteration {}, continuing.", period, iteration);
+            self.announced_cycle = Some((iteration, period));
+        }
+        None
+    }
+
+    /// Computes generations without rendering until the given one is reached, stopping early if the run terminates.
+    #[cfg(any(feature = "terminal", test))]
+    fn run_until(&mut self, generations: u64) -> Option<TerminationReason> {
+        self.run_observed(generations, |_, _| {})
+    }
+
+    /// Like [`Simulation::run_until`], calling `observer` with every newly computed generation.
+    fn run_observed(&mut self, generations: u64, mut observer: impl FnMut(&Grid, u64)) -> Option<TerminationReason> {
+        while self.universe.generation() < generations {
+            if let Some(reason) = self.observe() {
+                return Some(reason);
+            }
+            self.universe.step_once();
+            observer(self.universe.grid(), self.universe.generation());
+        }
+        None
+    }
+
+    /// Hands back the outputs recorded during the run, recording the final generation if it wasn't yet.
+    #[cfg(feature = "terminal")]
+    fn take_recording(&mut self) -> RunRecording {
+        let grid = self.universe.grid();
+        self.recording.record(grid, self.universe.generation(), count_population(grid), true);
+        std::mem::take(&mut self.recording)
+    }
+
+    fn record_region(&mut self) {
+        if let Some((r1, c1, r2, c2)) = self.region {
+            let population = count_live_in_region(self.universe.grid(), r1, c1, r2, c2);
+            self.tracker.record_region(self.universe.generation(), population);
+        }
+    }
+
+    fn record_cell(&mut self) {
+        if let Some((x, y)) = self.trace {
+            let grid = self.universe.grid();
+            let neighbors = count_live_neighbors(grid, y, x, self.universe.topology);
+            self.tracker.record_cell(self.universe.generation(), grid[y][x], neighbors);
+        }
+    }
+
+    #[cfg(feature = "terminal")]
+    fn into_metrics(mut self, started: Instant, termination_reason: TerminationReason) -> SimulationMetrics {
+        self.record_region();
+        self.record_cell();
+        let final_population = count_population(self.universe.grid());
+        self.tracker.finish(self.universe.generation(), final_population, started.elapsed(), termination_reason)
+    }
+}
+
+/// History of grid states, each stored with a hash fingerprint.
+///
+/// Comparing fingerprints first means the full, cell-by-cell comparison only runs on a hash match.
+/// A bounded cache keeps only the most recent states; an unbounded one keeps every state in a
+/// `HashMap` keyed by the grid, so cycles of any period are found in constant time.
+#[derive(Clone, Debug)]
+pub struct GridHashCache<T = Grid> {
+    hashes: VecDeque<u64>,
+    grids: VecDeque<T>,
+    capacity: usize,
+    /// Every state pushed so far with the step it was pushed at, when the cache is unbounded.
+    first_seen: Option<HashMap<T, u64>>,
+    steps: u64,
+}
+
+impl<T: Hash + Eq + Clone> GridHashCache<T> {
+    /// Creates a cache remembering the last [`MAX_HISTORY`] states.
+    pub fn new() -> Self {
+        GridHashCache::with_capacity(MAX_HISTORY)
+    }
+
+    /// Creates a cache remembering the last `capacity` states.
+    pub fn with_capacity(capacity: usize) -> Self {
+        GridHashCache {
+            hashes: VecDeque::with_capacity(capacity),
+            grids: VecDeque::with_capacity(capacity),
+            capacity,
+            first_seen: None,
+            steps: 0,
+        }
+    }
+
+    /// Creates a cache remembering every state it is given.
+    ///
+    /// Each new state is kept as a full copy, so the memory used grows by the size of the grid
+    /// with every generation that doesn't repeat an earlier one.
+    pub fn unbounded() -> Self {
+        GridHashCache {
+            first_seen: Some(HashMap::new()),
+            ..GridHashCache::with_capacity(0)
+        }
+    }
+
+    /// Number of states currently remembered.
+    pub fn len(&self) -> usize {
+        match &self.first_seen {
+            Some(first_seen) => first_seen.len(),
+            None => self.grids.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns how many states back the given state was last seen, remembering it instead if it's
+    /// new; a full bounded cache forgets its oldest state to make room.
+    fn find_or_push(&mut self, grid: &T) -> Option<usize> {
+        // The map hashes the grid itself as its key, so the unbounded cache needs no fingerprint
+        if let Some(first_seen) = &mut self.first_seen {
+            if let Some(&step) = first_seen.get(grid) {
+                return Some((self.steps - step) as usize);
+            }
+            first_seen.insert(grid.clone(), self.steps);
+            self.steps += 1;
+            return None;
+        }
+        let hash = fingerprint(grid);
+        if let Some(position) = (0..self.grids.len()).rev().find(|&position| self.hashes[position] == hash && &self.grids[position] == grid) {
+            return Some(self.grids.len() - position);
+        }
+        if self.capacity == 0 {
+            return None;
+        }
+        if self.len() == self.capacity {
+            self.hashes.pop_front();
+            self.grids.pop_front();
+        }
+        self.hashes.push_back(hash);
+        self.grids.push_back(grid.clone());
+        None
+    }
+}
+
+impl<T: Hash + Eq + Clone> Default for GridHashCache<T> {
+    fn default() -> Self {
+        GridHashCache::new()
+    }
+}
+
+/// Computes the hash fingerprint of a grid state.
+fn fingerprint<T: Hash>(grid: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    grid.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Detects if the current grid state matches any state in the history, indicating a steady state or oscillation.
+///
+/// Returns the period of the detected cycle, `1` meaning a still life.
+pub fn detect_steady_state<T: Hash + Eq + Clone>(grid: &T, history: &mut GridHashCache<T>) -> Option<usize> {
+    history.find_or_push(grid)
+}
+
+/// Sliding window over the most recent population counts, used to stop runs whose population
+/// hovers around a constant value without the grid ever repeating exactly.
+#[derive(Clone, Debug)]
+pub struct PopulationWindow {
+    counts: VecDeque<usize>,
+    window: usize,
+}
+
+impl PopulationWindow {
+    /// Creates a window over the last `window` generations; a window shorter than 2 is widened to 2.
+    pub fn new(window: usize) -> Self {
+        let window = window.max(2);
+        PopulationWindow { counts: VecDeque::with_capacity(window), window }
+    }
+
+    /// Records the population of the next generation.
+    ///
+    /// Once the window is full, returns the mean population (rounded) if the variance of the
+    /// window is below [`STABILIZATION_VARIANCE`].
+    pub fn push(&mut self, population: usize) -> Option<usize> {
+        if self.counts.len() == self.window {
+            self.counts.pop_front();
+        }
+        self.counts.push_back(population);
+        if self.counts.len() < self.window {
+            return None;
+        }
+
+        let len = self.counts.len() as f64;
+        let mean = self.counts.iter().sum::<usize>() as f64 / len;
+        let variance = self.counts.iter().map(|&count| (count as f64 - mean).powi(2)).sum::<f64>() / len;
+        (variance < STABILIZATION_VARIANCE).then(|| mean.round() as usize)
+    }
+}
+
+/// Makes the grid symmetric by reflecting its first half (or quadrant) onto the rest.
+pub fn apply_mirror(grid: &mut Grid, mirror: Mirror) {
+    let (rows, cols) = grid_dimensions(grid);
+    let mirror_columns = matches!(mirror, Mirror::Horizontal | Mirror::Quad);
+    let mirror_rows = matches!(mirror, Mirror::Vertical | Mirror::Quad);
+
+    if mirror_columns {
+        for row in grid.iter_mut() {
+            for col in 0..cols / 2 {
+                row[cols - 1 - col] = row[col];
+            }
+        }
+    }
+    if mirror_rows {
+        for row in 0..rows / 2 {
+            grid[rows - 1 - row] = grid[row].clone();
+        }
+    }
+}
+
+/// Flips every cell of the grid, turning live cells dead and dead cells alive.
+pub fn invert(grid: &mut Grid) {
+    for cell in grid.iter_mut().flatten() {
+        *cell = !*cell;
+    }
+}
+
+/// Returns a copy of the grid with every cell flipped, the complement of its pattern.
+pub fn grid_invert(grid: &Grid) -> Grid {
+    grid_not(grid)
+}
+
+/// Checks whether no live cells remain on the grid.
+pub fn is_extinct(grid: &Grid) -> bool {
+    grid.iter().all(|row| row.iter().all(|&cell| !cell))
+}
+
+/// Logs why the simulation stopped.
+#[cfg(feature = "terminal")]
+pub(crate) fn print_termination_message(reason: TerminationReason, iteration: u64) {
+    match reason {
+        TerminationReason::Extinction => warn!("Population extinct at iteration {}.", iteration),
+        TerminationReason::Stabilized(population) => {
+            info!("Population stabilized near {} at iteration {}.", population, iteration)
+        }
+        TerminationReason::SteadyState(_) => {
+            info!("Repeating or steady state detected. Terminating at iteration {}.", iteration)
+        }
+        TerminationReason::MaxIterations => info!("Iteration limit reached at iteration {}.", iteration),
+        TerminationReason::UserInterrupt => info!("Interrupted at iteration {}.", iteration),
+        TerminationReason::MaxRuntime(elapsed) => {
+            warn!("Stopped after {:.1} seconds at iteration {}.", elapsed.as_secs_f64(), iteration)
+        }
+    }
+}
+
+/// Computes the Shannon entropy of the live/dead cell distribution, in bits.
+pub fn grid_entropy(grid: &Grid) -> f64 {
+    let (rows, cols) = grid_dimensions(grid);
+    binary_entropy(count_population(grid), rows * cols)
+}
+
+/// Computes the Shannon entropy, in bits, of the patterns of all overlapping 2x2 blocks of cells.
+///
+/// Unlike [`grid_entropy`] this reflects the spatial structure: uniform or regularly tiled grids
+/// score low, chaotic ones up to 4 bits. Grids smaller than a block have an entropy of 0.
+pub fn block_entropy(grid: &Grid) -> f64 {
+    let mut frequencies = [0usize; 16];
+    for (upper, lower) in grid.iter().zip(grid.iter().skip(1)) {
+        for col in 1..upper.len() {
+            let block = [upper[col - 1], upper[col], lower[col - 1], lower[col]];
+            frequencies[block.iter().fold(0, |index, &alive| index << 1 | alive as usize)] += 1;
+        }
+    }
+    let total: usize = frequencies.iter().sum();
+    frequencies
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let share = count as f64 / total as f64;
+            -share * share.log2()
+        })
+        .sum::<f64>()
+        .max(0.0)
+}
+
+/// Counts the live cells on the grid.
+pub fn count_population(grid: &Grid) -> usize {
+    grid.iter().flatten().filter(|&&cell| cell).count()
+}
+
+/// Computes the next generation of the grid based on the current state, using Conway's rule.
+pub fn compute_next_generation(current: &Grid, next: &mut Grid, topology: Topology) {
+    compute_next_generation_with_rules(current, next, topology, &Rules::conway());
+}
+
+/// Returns whether a cell of a 2D grid with the given number of live neighbors is alive in the
+/// next generation, shared by every backend stepping a [`Grid`] under arbitrary rules.
+pub fn apply_rule(alive: bool, neighbors: u8, rule: &Rules) -> bool {
+    rule.next_state(alive, u32::from(neighbors))
+}
+
+/// Computes the next generation of the grid using the given birth and survival rule.
+pub fn compute_next_generation_with_rules(current: &Grid, next: &mut Grid, topology: Topology, rules: &Rules) {
+    let (rows, cols) = grid_dimensions(current);
+
+    for row in 0..rows {
+        for col in 0..cols {    
+            let live_neighbors = count_live_neighbors(current, row, col, topology);
+            next[row][col] = apply_rule(current[row][col], live_neighbors, rules);
+        }
+    }
+}
+
+/// Computes the next generation of the grid, asking `rule` for the next state of every cell.
+pub fn compute_next_generation_with_rule_fn(current: &Grid, next: &mut Grid, topology: Topology, rule: &dyn RuleFn) {
+    for (row, next_row) in next.iter_mut().enumerate() {
+        for (col, cell) in next_row.iter_mut().enumerate() {
+            *cell = rule.next_cell(current[row][col], count_live_neighbors(current, row, col, topology));
+        }
+    }
+}
+
+/// Computes the next generation like [`compute_next_generation_with_rules`], splitting the rows
+/// into one contiguous band per thread.
+pub fn compute_next_generation_parallel(current: &Grid, next: &mut Grid, topology: Topology, rules: &Rules, threads: usize) {
+    let band = current.len().div_ceil(threads.max(1)).max(1);
+    std::thread::scope(|scope| {
+        for (index, rows) in next.chunks_mut(band).enumerate() {
+            scope.spawn(move || {
+                for (offset, next_row) in rows.iter_mut().enumerate() {
+                    let row = index * band + offset;
+                    for (col, cell) in next_row.iter_mut().enumerate() {
+                        *cell = apply_rule(current[row][col], count_live_neighbors(current, row, col, topology), rules);
+                    }
+                }
+            });
+        }
+    });
+}
+
+/// Set of cells that may change in the next generation: the cells that changed in the last
+/// generation together with their neighbors. Every other cell keeps its state.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ActiveCells {
+    marked: Grid,
+    cells: Vec<(usize, usize)>,
+}
+
+impl ActiveCells {
+    /// Marks every cell of a grid with the given dimensions as active, e.g. before the first step.
+    pub fn all(rows: usize, cols: usize) -> Self {
+        ActiveCells {
+            marked: vec![vec![true; cols]; rows],
+            cells: (0..rows).flat_map(|row| (0..cols).map(move |col| (row, col))).collect(),
+        }
+    }
+
+    fn empty(rows: usize, cols: usize) -> Self {
+        ActiveCells { marked: initialize_grid(rows, cols), cells: Vec::new() }
+    }
+
+    /// Number of active cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Marks the cell and its neighbors as active.
+    fn mark_neighborhood(&mut self, row: usize, col: usize, topology: Topology) {
+        let (rows, cols) = grid_dimensions(&self.marked);
+        for (dx, dy) in NEIGHBOR_OFFSETS.iter().copied().chain(Some((0, 0))) {
+            let nx = resolve_coordinate(row as i32 + dx, rows as i32, topology.wraps_vertically());
+            let ny = resolve_coordinate(col as i32 + dy, cols as i32, topology.wraps_horizontally());
+            if let (Some(nx), Some(ny)) = (nx, ny) {
+                if !self.marked[nx][ny] {
+                    self.marked[nx][ny] = true;
+                    self.cells.push((nx, ny));
+                }
+            }
+        }
+    }
+}
+
+/// Computes the next generation recomputing only the active cells and copying the rest unchanged.
+///
+/// Output is bit-identical to [`compute_next_generation_with_rules`]. Returns the cells active
+/// in the following generation.
+pub fn compute_next_generation_active(
+    current: &Grid,
+    next: &mut Grid,
+    topology: Topology,
+    rules: &Rules,
+    active: &ActiveCells,
+) -> ActiveCells {
+    let (rows, cols) = grid_dimensions(current);
+    for (next_row, current_row) in next.iter_mut().zip(current) {
+        next_row.copy_from_slice(current_row);
+    }
+
+    let mut next_active = ActiveCells::empty(rows, cols);
+    for &(row, col) in &active.cells {
+        let alive = apply_rule(current[row][col], count_live_neighbors(current, row, col, topology), rules);
+        if alive != current[row][col] {
+            next[row][col] = alive;
+            next_active.mark_neighborhood(row, col, topology);
+        }
+    }
+    next_active
+}
+
+/// Kills every cell of the first and last row and column.
+pub fn enforce_dead_border(grid: &mut Grid) {
+    let (rows, cols) = grid_dimensions(grid);
+    for (row, col) in border_cells(rows, cols) {
+        grid[row][col] = false;
+    }
+}
+
+/// Positions of the cells in the outermost rows and columns, corners possibly more than once.
+fn border_cells(rows: usize, cols: usize) -> impl Iterator<Item = (usize, usize)> {
+    let (last_row, last_col) = (rows.saturating_sub(1), cols.saturating_sub(1));
+    let horizontal = (0..cols).flat_map(move |col| [(0, col), (last_row, col)]);
+    let vertical = (0..rows).flat_map(move |row| [(row, 0), (row, last_col)]);
+    horizontal.chain(vertical).filter(move |_| rows > 0 && cols > 0)
+}
+
+/// Number of live neighbors of every cell, kept up to date as cells change.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NeighborCounts {
+    counts: Vec<Vec<u8>>,
+}
+
+impl NeighborCounts {
+    /// Counts the live neighbors of every cell of the grid.
+    pub fn new(grid: &Grid, topology: Topology) -> Self {
+        NeighborCounts { counts: neighbor_counts(grid, topology) }
+    }
+
+    /// Number of live neighbors of the cell.
+    pub fn get(&self, row: usize, col: usize) -> u8 {
+        self.counts[row][col]
+    }
+
+    /// Adjusts the counts of the cell's neighbors after it was born or died.
+    ///
+    /// Neighbors beyond a bounded edge aren't cells, so their constant contribution never changes;
+    /// on tiny wrapped grids a neighbor reached through several offsets is adjusted once per offset,
+    /// matching how it is counted.
+    fn toggle(&mut self, row: usize, col: usize, alive: bool, topology: Topology) {
+        let (rows, cols) = (self.counts.len(), self.counts.first().map_or(0, Vec::len));
+        for &(dx, dy) in NEIGHBOR_OFFSETS.iter() {
+            let nx = resolve_coordinate(row as i32 + dx, rows as i32, topology.wraps_vertically());
+            let ny = resolve_coordinate(col as i32 + dy, cols as i32, topology.wraps_horizontally());
+            if let (Some(nx), Some(ny)) = (nx, ny) {
+                let count = &mut self.counts[nx][ny];
+                *count = if alive { *count + 1 } else { *count - 1 };
+            }
+        }
+    }
+}
+
+/// Computes the next generation from maintained neighbor counts instead of counting every neighborhood.
+///
+/// Output is bit-identical to [`compute_next_generation_with_rules`]. Once the new states are
+/// known the counts are adjusted around the cells that changed, ready for the following generation.
+/// Returns the numbers of cells born and died.
+pub fn compute_next_generation_incremental(
+    current: &Grid,
+    next: &mut Grid,
+    topology: Topology,
+    rules: &Rules,
+    counts: &mut NeighborCounts,
+) -> (u64, u64) {
+    let mut changed = Vec::new();
+    for (row, (next_row, current_row)) in next.iter_mut().zip(current).enumerate() {
+        for (col, (cell, &alive)) in next_row.iter_mut().zip(current_row).enumerate() {
+            *cell = apply_rule(alive, counts.get(row, col), rules);
+            if *cell != alive {
+                changed.push((row, col, *cell));
+            }
+        }
+    }
+    let births = changed.iter().filter(|&&(_, _, alive)| alive).count() as u64;
+    let deaths = changed.len() as u64 - births;
+    for (row, col, alive) in changed {
+        counts.toggle(row, col, alive, topology);
+    }
+    (births, deaths)
+}
+
+/// Numbers of cells born and died among the given ones going from `previous` to `next`.
+fn count_changes_at(previous: &Grid, next: &Grid, cells: &[(usize, usize)]) -> (u64, u64) {
+    cells.iter().fold((0, 0), |(births, deaths), &(row, col)| match (previous[row][col], next[row][col]) {
+        (false, true) => (births + 1, deaths),
+        (true, false) => (births, deaths + 1),
+        _ => (births, deaths),
+    })
+}
+
+/// Counts the live neighbors of every cell, e.g. to show why the grid evolves the way it does.
+pub fn neighbor_counts(grid: &Grid, topology: Topology) -> Vec<Vec<u8>> {
+    let (rows, cols) = grid_dimensions(grid);
+    (0..rows)
+        .map(|row| (0..cols).map(|col| count_live_neighbors(grid, row, col, topology)).collect())
+        .collect()
+}
+
+/// Counts how many of the neighboring cells are alive.
+fn count_live_neighbors(grid: &Grid, row: usize, col: usize, topology: Topology) -> u8 {
+    let (rows, cols) = grid_dimensions(grid);
+    NEIGHBOR_OFFSETS.iter().filter(|&&(dx, dy)| {
+        let nx = resolve_coordinate(row as i32 + dx, rows as i32, topology.wraps_vertically());
+        let ny = resolve_coordinate(col as i32 + dy, cols as i32, topology.wraps_horizontally());
+        match (nx, ny) {
+            (Some(nx), Some(ny)) => grid[nx][ny],
+            _ => topology.off_grid_alive(),
+        }
+    }).count() as u8
+}
+
+/// Maps a possibly out-of-range coordinate onto the grid, wrapping it if the axis wraps.
+fn resolve_coordinate(value: i32, size: i32, wrap: bool) -> Option<usize> {
+    if wrap {
+        Some(value.rem_euclid(size) as usize)
+    } else if (0..size).contains(&value) {
+        Some(value as usize)
+    } else {
+        None
+    }
+}
+
+/// Seeds the grid with random live cells, deterministically for a given seed.
+fn seed_grid_with_random_cells(grid: &mut Grid, seed: u64) {
+    let (rows, cols) = grid_dimensions(grid);
+    let mut rng = StdRng::seed_from_u64(seed);
+    // At most a fifth of the cells, which for the narrowest grids is fewer than one per row or column
+    let max = rows * cols / 5;
+    let live_cells = rng.gen_range(max.min(rows.min(cols))..=max);
+
+    for _ in 0..live_cells {
+        let x: usize = rng.gen_range(0..rows);
+        let y: usize = rng.gen_range(0..cols);
+        grid[x][y] = true;
+    }
+}
+
+/// Seeds each cell alive with a probability given by the darkness of the matching pixel of the image.
+///
+/// The image is scaled to the grid by nearest neighbor sampling; black pixels always seed a live
+/// cell and white pixels never do.
+pub fn seed_from_image(grid: &mut Grid, image: &GrayImage, rng: &mut impl Rng) {
+    let (rows, cols) = grid_dimensions(grid);
+    for (row, cells) in grid.iter_mut().enumerate() {
+        for (col, cell) in cells.iter_mut().enumerate() {
+            let brightness = image.pixel(col * image.width / cols, row * image.height / rows);
+            *cell = rng.gen_bool(1.0 - f64::from(brightness) / 255.0);
+        }
+    }
+}
+
+/// Seeds the grid so that each cell is alive with the given probability.
+fn seed_grid_with_density(grid: &mut Grid, seed: u64, density: f64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    for cell in grid.iter_mut().flatten() {
+        *cell = rng.gen_bool(density);
+    }
+}
+
+/// Seeds the top-left quadrant with the given density and reflects it onto the other three.
+///
+/// With an odd number of rows or columns the middle one belongs to the seeded quadrant.
+fn seed_symmetric_4fold(grid: &mut Grid, seed: u64, density: f64) {
+    let (rows, cols) = grid_dimensions(grid);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    for row in grid.iter_mut().take(rows.div_ceil(2)) {
+        for cell in row.iter_mut().take(cols.div_ceil(2)) {
+            *cell = rng.gen_bool(density);
+        }
+    }
+    apply_mirror(grid, Mirror::Quad);
+}
+
+/// Seeds the cells less than `width` cells away from the nearest edge with the given density.
+fn seed_edge(grid: &mut Grid, seed: u64, density: f64, width: usize) {
+    let (rows, cols) = grid_dimensions(grid);
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    for (row, cells) in grid.iter_mut().enumerate() {
+        for (col, cell) in cells.iter_mut().enumerate() {
+            let distance = row.min(rows - 1 - row).min(col).min(cols - 1 - col);
+            *cell = distance < width && rng.gen_bool(density);
+        }
+    }
+}
+
+/// Seeds cells with a probability following a 2D Gaussian centered on the grid, with a standard
+/// deviation of a quarter of each dimension.
+///
+/// The curve is scaled so that the expected share of live cells is `density`, except where the
+/// scaled probability would exceed one near the center.
+fn seed_dense_center(grid: &mut Grid, seed: u64, density: f64) {
+    let (rows, cols) = grid_dimensions(grid);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let weight = |index: usize, size: usize| {
+        let offset = index as f64 - (size as f64 - 1.0) / 2.0;
+        let deviation = (size as f64 / 4.0).max(0.5);
+        (-offset * offset / (2.0 * deviation * deviation)).exp()
+    };
+    let total: f64 = (0..rows).map(|row| weight(row, rows)).sum::<f64>() * (0..cols).map(|col| weight(col, cols)).sum::<f64>();
+    let scale = density * (rows * cols) as f64 / total;
+
+    for (row, cells) in grid.iter_mut().enumerate() {
+        for (col, cell) in cells.iter_mut().enumerate() {
+            *cell = rng.gen_bool((scale * weight(row, rows) * weight(col, cols)).min(1.0));
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_initialize_grid() {
+        let size = 5;
+        let grid = initialize_grid(size, size);
+
+        assert_eq!(grid.len(), size);
+        for row in &grid {
+            assert_eq!(row.len(), size);
+            assert!(row.iter().all(|&cell| !cell));
+        }
+    }
+
+    #[test]
+    fn test_detect_steady_state() {
+        let mut history = GridHashCache::new();
+        let grid = vec![vec![true, false], vec![false, true]];
+
+        // Initially, the grid is not in history
+        assert!(detect_steady_state(&grid, &mut history).is_none());
+
+        // After being added, the grid is in history
+        assert!(detect_steady_state(&grid, &mut history).is_some());
+    }
+
+    #[test]
+    fn test_detect_steady_state_reports_period() {
+        let mut history = GridHashCache::new();
+        let mut grid = vec![
+            vec![false, true, false],
+            vec![false, true, false],
+            vec![false, true, false],
+        ];
+        let mut next = initialize_grid(3, 3);
+
+        // Blinker oscillates with period 2
+        for _ in 0..2 {
+            assert_eq!(detect_steady_state(&grid, &mut history), None);
+            compute_next_generation(&grid, &mut next, Topology::Finite);
+            std::mem::swap(&mut grid, &mut next);
+        }
+        assert_eq!(detect_steady_state(&grid, &mut history), Some(2));
+    }
+
+    #[test]
+    fn test_invert() {
+        let mut grid = vec![vec![true, false], vec![false, false]];
+
+        invert(&mut grid);
+        assert_eq!(grid, vec![vec![false, true], vec![true, true]]);
+
+        invert(&mut grid);
+        assert_eq!(grid, vec![vec![true, false], vec![false, false]]);
+    }
+
+    #[test]
+    fn test_grid_invert_is_an_involution() {
+        for (seed, density) in [(1, 0.1), (2, 0.5), (3, 0.9)] {
+            let config = SimulationConfig { grid_size: 12, grid_width: Some(7), seed, density: Some(density), ..Default::default() };
+            let grid = create_seeded_grid(&config);
+
+            assert_eq!(grid_invert(&grid_invert(&grid)), grid);
+            assert!(grid_invert(&grid).iter().flatten().zip(grid.iter().flatten()).all(|(&a, &b)| a != b));
+        }
+        assert_eq!(grid_invert(&Vec::new()), Vec::<Vec<bool>>::new());
+    }
+
+    #[test]
+    fn test_day_and_night_complement_symmetry() {
+        // Day & Night is self-complementary; wrapping keeps the board free of a dead border that would break it
+        let rules: Rules = "B3678/S34678".parse().unwrap();
+        let config = SimulationConfig { grid_size: 16, seed: 5, ..Default::default() };
+        let pattern = create_seeded_grid(&config);
+        let run = |grid: Grid| {
+            let mut universe = Universe::new(grid, Topology::Toroidal).with_rules(rules);
+            for _ in 0..10 {
+                universe.step_once();
+            }
+            universe.grid().clone()
+        };
+
+        let original = run(pattern.clone());
+        let complement = run(grid_invert(&pattern));
+
+        assert_eq!(complement, grid_invert(&original));
+    }
+
+    #[test]
+    fn test_seed_mode_symmetric_4fold() {
+        for (rows, cols) in [(10, 10), (9, 14)] {
+            let config = SimulationConfig { grid_size: rows, grid_width: Some(cols), seed: 4, seed_mode: SeedMode::Symmetric4Fold, ..Default::default() };
+
+            let grid = create_seeded_grid(&config);
+
+            assert!(count_population(&grid) > 0);
+            for r in 0..rows {
+                for c in 0..cols {
+                    assert_eq!(grid[r][c], grid[rows - 1 - r][c]);
+                    assert_eq!(grid[r][c], grid[r][cols - 1 - c]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_seed_mode_edge() {
+        let config = SimulationConfig { grid_size: 12, grid_width: Some(15), seed: 8, density: Some(0.8), seed_mode: SeedMode::Edge { width: 2 }, ..Default::default() };
+
+        let grid = create_seeded_grid(&config);
+
+        assert!(count_population(&grid) > 0);
+        for (row, cells) in grid.iter().enumerate() {
+            for (col, &alive) in cells.iter().enumerate() {
+                let distance = row.min(11 - row).min(col).min(14 - col);
+                assert!(!alive || distance < 2, "({}, {}) is alive", row, col);
+            }
+        }
+    }
+
+    #[test]
+    fn test_seed_mode_dense_center() {
+        let config = SimulationConfig { grid_size: 40, seed: 1, density: Some(0.3), seed_mode: SeedMode::DenseCenter, ..Default::default() };
+
+        let grid = create_seeded_grid(&config);
+
+        let center = grid[10..30].iter().map(|row| row[10..30].iter().filter(|&&cell| cell).count()).sum::<usize>();
+        let population = count_population(&grid);
+        assert!(center * 2 > population, "{} of {} in the center", center, population);
+        assert!((population as f64 / 1600.0 - 0.3).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_parse_seed_mode() {
+        for mode in [SeedMode::Random, SeedMode::Symmetric4Fold, SeedMode::Edge { width: 3 }, SeedMode::DenseCenter] {
+            assert_eq!(mode.to_string().parse::<SeedMode>(), Ok(mode));
+        }
+        assert_eq!("edge".parse::<SeedMode>(), Ok(SeedMode::Edge { width: 1 }));
+        for invalid in ["spiral", "edge:0", "edge:x"] {
+            assert_eq!(invalid.parse::<SeedMode>(), Err(ParseSeedModeError { input: invalid.to_string() }));
+        }
+    }
+
+    #[test]
+    fn test_create_seeded_grid_inverted() {
+        let config = SimulationConfig { grid_size: 10, seed: 3, ..Default::default() };
+        let inverted = SimulationConfig { invert: true, ..config.clone() };
+
+        let mut grid = create_seeded_grid(&config);
+        invert(&mut grid);
+
+        assert_eq!(create_seeded_grid(&inverted), grid);
+    }
+
+    fn is_horizontally_symmetric(grid: &Grid) -> bool {
+        grid.iter().all(|row| row.iter().eq(row.iter().rev()))
+    }
+
+    fn is_vertically_symmetric(grid: &Grid) -> bool {
+        grid.iter().eq(grid.iter().rev())
+    }
+
+    #[test]
+    fn test_create_seeded_grid_mirrored() {
+        for size in [9, 10] {
+            let config = |mirror| SimulationConfig {
+                grid_size: size,
+                seed: 5,
+                density: Some(0.4),
+                mirror: Some(mirror),
+                ..Default::default()
+            };
+
+            let horizontal = create_seeded_grid(&config(Mirror::Horizontal));
+            let vertical = create_seeded_grid(&config(Mirror::Vertical));
+            let quad = create_seeded_grid(&config(Mirror::Quad));
+
+            assert!(is_horizontally_symmetric(&horizontal));
+            assert!(is_vertically_symmetric(&vertical));
+            assert!(is_horizontally_symmetric(&quad) && is_vertically_symmetric(&quad));
+        }
+    }
+
+    #[test]
+    fn test_apply_mirror_keeps_seeded_half() {
+        let mut grid = vec![
+            vec![true, false, false],
+            vec![false, true, false],
+        ];
+
+        apply_mirror(&mut grid, Mirror::Horizontal);
+
+        assert_eq!(grid, vec![vec![true, false, true], vec![false, true, false]]);
+    }
+
+    #[test]
+    fn test_is_extinct() {
+        assert!(is_extinct(&initialize_grid(4, 4)));
+        assert!(is_extinct(&initialize_grid(0, 0)));
+        assert!(!is_extinct(&vec![vec![false, false], vec![false, true]]));
+    }
+
+    #[test]
+    fn test_quiet_steady_runs_past_oscillation() {
+        let blinker = parse_grid(".....\n..#..\n..#..\n..#..\n.....").unwrap();
+        let config = SimulationConfig { grid_size: 5, exit_steady: true, quiet_steady: true, ..Default::default() };
+        let mut simulation = Simulation::new(&config, blinker);
+
+        assert_eq!(simulation.run_until(10), None);
+
+        assert_eq!(simulation.universe.generation(), 10);
+        // Announced once, when the cycle was first found
+        assert_eq!(simulation.announced_cycle, Some((2, 2)));
+    }
+
+    #[cfg(feature = "terminal")]
+    #[test]
+    fn test_quiet_steady_announces_cycle_once_at_default_level() {
+        use crate::logging::{subscriber, LogBuffer, LogLevel};
+
+        let blinker = parse_grid(".....\n..#..\n..#..\n..#..\n.....").unwrap();
+        let config = SimulationConfig { grid_size: 5, exit_steady: true, quiet_steady: true, ..Default::default() };
+        let mut simulation = Simulation::new(&config, blinker);
+        let buffer = LogBuffer::default();
+
+        tracing::subscriber::with_default(subscriber(LogLevel::default(), buffer.clone()), || simulation.run_until(10));
+
+        let logged = buffer.contents();
+        assert_eq!(logged.lines().count(), 1, "{}", logged);
+        assert!(logged.contains("period 2 detected at iteration 2, continuing"), "{}", logged);
+    }
+
+    #[test]
+    fn test_blinker_births_and_deaths() {
+        let blinker = parse_grid(".....\n..#..\n..#..\n..#..\n.....").unwrap();
+        let config = SimulationConfig { grid_size: 5, ..Default::default() };
+        let mut simulation = Simulation::new(&config, blinker);
+
+        assert_eq!(simulation.universe.changes(), None);
+        assert_eq!(simulation.run_until(6), None);
+        assert_eq!(simulation.universe.changes(), Some((2, 2)));
+
+        // Two cells are born and two die going into every generation after the first
+        assert_eq!(simulation.tracker.changes(0), None);
+        assert!((1..6).all(|iteration| simulation.tracker.changes(iteration) == Some((2, 2))));
+        assert!((0..6).all(|iter| simulation.tracker.net_births(iter) == 0));
+        let metrics = simulation.tracker.finish(6, 3, std::time::Duration::ZERO, TerminationReason::MaxIterations);
+        assert_eq!((metrics.births, metrics.deaths), (10, 10));
+    }
+
+    #[test]
+    fn test_every_backend_counts_the_same_changes() {
+        let config = SimulationConfig { grid_size: 40, seed: 9, ..Default::default() };
+        let initial = create_seeded_grid(&config);
+        let universe = || Universe::new(initial.clone(), Topology::Finite);
+        let mut universes = [
+            universe(),
+            universe().with_packed(true),
+            universe().with_active_cells(true),
+            universe().with_incremental_counts(true),
+            universe().with_threads(3),
+            universe().with_dead_border(true),
+            universe().with_packed(true).with_dead_border(true),
+            universe().with_active_cells(true).with_dead_border(true),
+        ];
+
+        for _ in 0..30 {
+            for universe in &mut universes {
+                let previous = universe.grid().clone();
+                universe.step_once();
+                assert_eq!(universe.changes(), Some(count_births_and_deaths(&previous, universe.grid())));
+            }
+        }
+    }
+
+    #[test]
+    fn test_terminate_on_quiescence() {
+        let block = parse_grid("....\n.##.\n.##.\n....").unwrap();
+        let config = SimulationConfig { grid_size: 4, terminate_on_quiescence: true, ..Default::default() };
+
+        assert_eq!(Simulation::new(&config, block).run_until(10), Some(TerminationReason::SteadyState(1)));
+
+        // A blinker keeps changing, and without the flag a still life runs on
+        let blinker = parse_grid(".....\n..#..\n..#..\n..#..\n.....").unwrap();
+        let config = SimulationConfig { grid_size: 5, terminate_on_quiescence: true, ..Default::default() };
+        assert_eq!(Simulation::new(&config, blinker).run_until(10), None);
+        let block = parse_grid("....\n.##.\n.##.\n....").unwrap();
+        let mut simulation = Simulation::new(&SimulationConfig { grid_size: 4, ..Default::default() }, block);
+        assert_eq!(simulation.run_until(10), None);
+    }
+
+    #[test]
+    fn test_trace_blinker_cell_alternates() {
+        let blinker = parse_grid(".....\n.....\n.###.\n.....\n.....").unwrap();
+        let config = SimulationConfig { grid_size: 5, trace: Some((1, 2)), ..Default::default() };
+        let mut simulation = Simulation::new(&config, blinker);
+
+        assert_eq!(simulation.run_until(6), None);
+        let metrics = simulation.tracker.finish(6, 3, std::time::Duration::ZERO, TerminationReason::MaxIterations);
+
+        let trace = metrics.trace.unwrap();
+        assert_eq!((trace.x, trace.y, trace.first_iteration), (1, 2, 0));
+        assert_eq!(trace.states(), "101010");
+        assert_eq!(trace.neighbor_counts(), "131313");
+    }
+
+    #[test]
+    fn test_observe_reports_extinction_before_history_repeats() {
+        let config = SimulationConfig { grid_size: 3, exit_steady: true, ..Default::default() };
+        let mut simulation = Simulation::new(&config, initialize_grid(3, 3));
+
+        // The empty grid is reported right away, without waiting for it to repeat
+        assert_eq!(simulation.observe(), Some(TerminationReason::Extinction));
+    }
+
+    #[test]
+    fn test_grid_entropy() {
+        let mut half_alive = initialize_grid(4, 4);
+        for row in half_alive.iter_mut().take(2) {
+            row.fill(true);
+        }
+        let mut all_alive = initialize_grid(4, 4);
+        invert(&mut all_alive);
+
+        assert_eq!(grid_entropy(&initialize_grid(4, 4)), 0.0);
+        assert_eq!(grid_entropy(&all_alive), 0.0);
+        assert!((grid_entropy(&half_alive) - 1.0).abs() < 1e-12);
+    }
+
+    fn checkerboard(rows: usize, cols: usize) -> Grid {
+        (0..rows).map(|row| (0..cols).map(|col| (row + col) % 2 == 0).collect()).collect()
+    }
+
+    #[test]
+    fn test_block_entropy() {
+        let mut all_alive = initialize_grid(6, 6);
+        invert(&mut all_alive);
+        let soup = create_seeded_grid(&SimulationConfig { grid_size: 30, seed: 3, density: Some(0.5), ..Default::default() });
+
+        assert_eq!(block_entropy(&initialize_grid(6, 6)), 0.0);
+        assert_eq!(block_entropy(&all_alive), 0.0);
+        assert_eq!(block_entropy(&vec![vec![true, false]]), 0.0);
+        // Checkerboard blocks come in two equally frequent patterns
+        assert!(grid_entropy(&checkerboard(6, 6)) > 0.0);
+        assert!((block_entropy(&checkerboard(7, 7)) - 1.0).abs() < 1e-12);
+        assert!(block_entropy(&soup) > 3.5);
+    }
+
+    #[cfg(feature = "terminal")]
+    #[test]
+    fn test_entropy_csv_is_recorded_while_running() {
+        let path = std::env::temp_dir().join(format!("rgol-test-entropy-{}.csv", std::process::id()));
+        let blinker = parse_grid(".....\n.....\n.###.\n.....\n.....").unwrap();
+        let mut simulation = Simulation::resume(&SimulationConfig { grid_size: 5, ..Default::default() }, blinker, 7);
+        simulation.recording = RunRecording::default().with_entropy_csv(std::fs::File::create(&path).unwrap(), None).unwrap();
+
+        simulation.run_until(9);
+        simulation.take_recording().finish().unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "generation,entropy,block_entropy");
+        assert!(lines[1].starts_with("7,0.529361,"));
+        assert!(lines[3].starts_with("9,"));
+        assert_eq!(lines[1][2..], lines[3][2..]);
+    }
+
+    /// Reference implementation of the steady state check comparing every stored grid in full.
+    fn detect_steady_state_naive(grid: &Grid, history: &mut VecDeque<Grid>, capacity: usize) -> Option<usize> {
+        if let Some(position) = history.iter().rposition(|previous| previous == grid) {
+            return Some(history.len() - position);
+        }
+        if history.len() == capacity {
+            history.pop_front();
+        }
+        history.push_back(grid.clone());
+        None
+    }
+
+    /// Builds a distinct 10x10 grid whose cells spell the binary representation of `n`.
+    fn numbered_grid(n: u64) -> Grid {
+        let mut grid = initialize_grid(10, 10);
+        for bit in 0..64 {
+            grid[bit / 10][bit % 10] = n >> bit & 1 == 1;
+        }
+        grid
+    }
+
+    #[test]
+    fn test_hashed_detection_matches_naive_check() {
+        let mut history = GridHashCache::with_capacity(50);
+        let mut naive_history = VecDeque::new();
+
+        for n in 0..1000 {
+            let grid = numbered_grid(n);
+            let expected = detect_steady_state_naive(&grid, &mut naive_history, 50);
+
+            assert_eq!(detect_steady_state(&grid, &mut history), expected);
+            assert_eq!(expected, None);
+        }
+
+        // A grid seen 3 states ago is still found
+        assert_eq!(detect_steady_state(&numbered_grid(997), &mut history), Some(3));
+    }
+
+    #[test]
+    fn test_hash_cache_forgets_oldest_state() {
+        let mut history = GridHashCache::with_capacity(2);
+
+        for n in 0..3 {
+            assert_eq!(detect_steady_state(&numbered_grid(n), &mut history), None);
+        }
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(detect_steady_state(&numbered_grid(0), &mut history), None);
+    }
+
+    #[test]
+    fn test_grid_hash_identifies_cells() {
+        let mut other = numbered_grid(5);
+
+        assert_eq!(fingerprint(&numbered_grid(5)), fingerprint(&other));
+        other[9][9] = true;
+        assert_ne!(fingerprint(&numbered_grid(5)), fingerprint(&other));
+    }
+
+    #[test]
+    fn test_unbounded_cache_finds_long_cycles() {
+        // Glider on an 8x8 torus returns to its starting cells after 32 generations
+        let mut grid = initialize_grid(8, 8);
+        for (row, col) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] {
+            grid[row][col] = true;
+        }
+        let mut universe = Universe::new(grid, Topology::Toroidal);
+        let mut bounded = GridHashCache::new();
+        let mut unbounded = GridHashCache::unbounded();
+
+        let mut period = None;
+        while period.is_none() && universe.generation() <= 32 {
+            assert_eq!(detect_steady_state(universe.grid(), &mut bounded), None);
+            period = detect_steady_state(universe.grid(), &mut unbounded);
+            universe.step_once();
+        }
+
+        assert_eq!(period, Some(32));
+        assert_eq!(unbounded.len(), 32);
+    }
+
+    /// Compares the hashed and naive checks; run with `cargo test --release -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_steady_state_detection() {
+        const SIZE: usize = 100;
+        const HISTORY: usize = 50;
+        const ROUNDS: u64 = 2000;
+
+        let config = SimulationConfig { grid_size: SIZE, seed: 11, density: Some(0.5), ..Default::default() };
+        let base = create_seeded_grid(&config);
+        let grids: Vec<Grid> = (0..ROUNDS)
+            .map(|n| {
+                let mut grid = base.clone();
+                // Differ only in the last row so the naive comparison has to scan most cells
+                grid[SIZE - 1][(n as usize) % SIZE] ^= true;
+                grid[SIZE - 1][(n as usize / SIZE) % SIZE] ^= true;
+                grid
+            })
+            .collect();
+
+        let started = std::time::Instant::now();
+        let mut naive_history = VecDeque::new();
+        for grid in &grids {
+            detect_steady_state_naive(grid, &mut naive_history, HISTORY);
+        }
+        let naive = started.elapsed();
+
+        let started = std::time::Instant::now();
+        let mut history = GridHashCache::with_capacity(HISTORY);
+        for grid in &grids {
+            detect_steady_state(grid, &mut history);
+        }
+        let hashed = started.elapsed();
+
+        println!("{}x{} grid, history {}: naive {:?}, hashed {:?}", SIZE, SIZE, HISTORY, naive, hashed);
+    }
+
+    #[test]
+    fn test_count_population() {
+        let grid = vec![vec![true, false], vec![true, true]];
+
+        assert_eq!(count_population(&grid), 3);
+        assert_eq!(count_population(&initialize_grid(4, 4)), 0);
+    }
+
+    #[test]
+    fn test_compute_next_generation() {
+        let current = vec![
+            vec![false, true, false],
+            vec![false, true, false],
+            vec![false, true, false],
+        ];
+        let mut next = initialize_grid(3, 3);
+
+        compute_next_generation(&current, &mut next, Topology::Finite);
+
+        let expected = vec![
+            vec![false, false, false],
+            vec![true, true, true],
+            vec![false, false, false],
+        ];
+        assert_eq!(next, expected);
+    }
+
+    #[test]
+    fn test_apply_rule_conway() {
+        let conway = Rules::conway();
+        for neighbors in 0..=8 {
+            assert_eq!(apply_rule(false, neighbors, &conway), neighbors == 3, "dead cell, {} neighbors", neighbors);
+            assert_eq!(apply_rule(true, neighbors, &conway), neighbors == 2 || neighbors == 3, "live cell, {} neighbors", neighbors);
+        }
+    }
+
+    #[test]
+    fn test_apply_rule_highlife() {
+        let highlife: Rules = "B36/S23".parse().unwrap();
+        for neighbors in 0..=8 {
+            assert_eq!(apply_rule(false, neighbors, &highlife), neighbors == 3 || neighbors == 6, "dead cell, {} neighbors", neighbors);
+            assert_eq!(apply_rule(true, neighbors, &highlife), neighbors == 2 || neighbors == 3, "live cell, {} neighbors", neighbors);
+        }
+    }
+
+    #[test]
+    fn test_rule_fn_matches_rules() {
+        let highlife: Rules = "B36/S23".parse().unwrap();
+        let current = create_seeded_grid(&SimulationConfig { grid_size: 12, seed: 3, ..SimulationConfig::default() });
+        let mut expected = current.clone();
+        let mut next = current.clone();
+
+        compute_next_generation_with_rules(&current, &mut expected, Topology::Toroidal, &highlife);
+        compute_next_generation_with_rule_fn(&current, &mut next, Topology::Toroidal, &highlife);
+
+        assert_eq!(next, expected);
+    }
+
+    #[test]
+    fn test_count_live_neighbors() {
+        let grid = vec![
+            vec![true, false, true],
+            vec![false, true, false],
+            vec![true, false, true],
+        ];
+
+        assert_eq!(count_live_neighbors(&grid, 1, 1, Topology::Finite), 4);
+        assert_eq!(count_live_neighbors(&grid, 0, 0, Topology::Finite), 1);
+        assert_eq!(count_live_neighbors(&grid, 0, 2, Topology::Finite), 1);
+    }
+
+    #[test]
+    fn test_compute_next_generation_rectangular() {
+        let current = vec![
+            vec![false, false, false, false, false],
+            vec![false, true, true, true, false],
+            vec![false, false, false, false, false],
+        ];
+        let mut next = initialize_grid(3, 5);
+
+        compute_next_generation(&current, &mut next, Topology::Finite);
+
+        let expected = vec![
+            vec![false, false, true, false, false],
+            vec![false, false, true, false, false],
+            vec![false, false, true, false, false],
+        ];
+        assert_eq!(next, expected);
+    }
+
+    #[test]
+    fn test_create_seeded_grid_uses_dimensions() {
+        let config = SimulationConfig { grid_size: 4, grid_width: Some(9), seed: 1, ..Default::default() };
+
+        assert_eq!(grid_dimensions(&create_seeded_grid(&config)), (4, 9));
+    }
+
+    #[test]
+    fn test_random_seeding_of_degenerate_grids() {
+        for (rows, cols) in [(1, 1), (1, 4), (1, 12), (7, 1), (2, 2)] {
+            for seed in 0..20 {
+                let mut grid = initialize_grid(rows, cols);
+                seed_grid_with_random_cells(&mut grid, seed);
+                assert!(count_population(&grid) <= rows * cols / 5, "{}x{} grid, seed {}", rows, cols, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn test_count_live_neighbors_wrapping() {
+        let grid = vec![
+            vec![true, false, false, false],
+            vec![false, false, false, false],
+            vec![false, false, false, false],
+            vec![false, false, false, true],
+        ];
+
+        // Corner (0, 0) only sees (3, 3) when both axes wrap
+        assert_eq!(count_live_neighbors(&grid, 0, 3, Topology::Finite), 0);
+        assert_eq!(count_live_neighbors(&grid, 0, 3, Topology::CylindricalHorizontal), 1);
+        assert_eq!(count_live_neighbors(&grid, 0, 3, Topology::CylindricalVertical), 1);
+        assert_eq!(count_live_neighbors(&grid, 3, 0, Topology::CylindricalHorizontal), 1);
+        assert_eq!(count_live_neighbors(&grid, 3, 0, Topology::Toroidal), 2);
+    }
+
+    #[test]
+    fn test_next_seed_sequence_is_deterministic() {
+        let sequence = |start| std::iter::successors(Some(start), |&seed| Some(next_seed(seed))).take(4).collect::<Vec<u64>>();
+
+        assert_eq!(sequence(42), vec![42, 43, 44, 45]);
+        assert_eq!(sequence(42), sequence(42));
+        assert_eq!(next_seed(u64::MAX), 0);
+    }
+
+    #[test]
+    fn test_seeded_trajectory_is_identical_for_any_thread_count() {
+        let config = SimulationConfig { grid_size: 40, seed: 9, topology: Topology::Toroidal, ..Default::default() };
+        let mut single = Universe::from_config(&config).with_threads(1);
+        let mut parallel = Universe::from_config(&SimulationConfig { threads: Some(4), ..config.clone() }).with_threads(4);
+
+        for _ in 0..50 {
+            assert_eq!(single.grid(), parallel.grid());
+            single.step_once();
+            parallel.step_once();
+        }
+        assert_eq!(single.grid(), parallel.grid());
+    }
+
+    #[test]
+    fn test_parallel_step_with_more_threads_than_rows() {
+        let grid = vec![vec![false, true, false]; 3];
+        let mut serial = initialize_grid(3, 3);
+        let mut parallel = initialize_grid(3, 3);
+
+        compute_next_generation(&grid, &mut serial, Topology::Finite);
+        compute_next_generation_parallel(&grid, &mut parallel, Topology::Finite, &Rules::conway(), 8);
+
+        assert_eq!(parallel, serial);
+    }
+
+    #[test]
+    fn test_iteration_counter_passes_i32_max() {
+        let config = SimulationConfig { grid_size: 5, max_iterations: Some(i32::MAX as u64 + 3), ..Default::default() };
+        let blinker = parse_grid(".....\n..#..\n..#..\n..#..\n.....").unwrap();
+        let mut simulation = Simulation::resume(&config, blinker, i32::MAX as u64 - 2);
+
+        assert_eq!(simulation.run_until(config.iteration_limit()), None);
+        assert_eq!(simulation.universe.generation(), i32::MAX as u64 + 3);
+        assert_eq!(SimulationConfig::default().iteration_limit(), u64::MAX);
+    }
+
+    #[test]
+    fn test_seed_from_image() {
+        let image = GrayImage { width: 2, height: 2, pixels: vec![0, 255, 255, 0] };
+        let mut rng = StdRng::seed_from_u64(1);
+
+        for _ in 0..10 {
+            let mut grid = initialize_grid(4, 4);
+            seed_from_image(&mut grid, &image, &mut rng);
+
+            // Each pixel covers a 2x2 block of cells
+            let black = [(0, 0), (0, 1), (1, 0), (1, 1), (2, 2), (2, 3), (3, 2), (3, 3)];
+            for (row, cells) in grid.iter().enumerate() {
+                for (col, &alive) in cells.iter().enumerate() {
+                    assert_eq!(alive, black.contains(&(row, col)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_neighbor_counts() {
+        let grid = vec![
+            vec![true, true, false],
+            vec![false, true, true],
+            vec![true, false, false],
+        ];
 
-/// Initializes the grid with all cells set to `false`.
-fn initialize_grid(grid_size: usize) -> Grid {
-    vec![vec![false; grid_size]; grid_size]
-}
+        let counts = neighbor_counts(&grid, Topology::Finite);
 
-/// Runs the Conway's Game of Life simulation.
-pub fn run_game_of_life_simulation(grid_size: usize, exit_steady: bool) {
-    clear_screen();
+        assert_eq!(counts, vec![vec![2, 3, 3], vec![4, 4, 2], vec![1, 3, 2]]);
+        for (row, cells) in counts.iter().enumerate() {
+            for (col, &count) in cells.iter().enumerate() {
+                assert_eq!(count, count_live_neighbors(&grid, row, col, Topology::Finite));
+            }
+        }
+        assert_eq!(neighbor_counts(&grid, Topology::Toroidal)[0][0], 4);
+    }
 
-    let mut grid = initialize_grid(grid_size);
-    let mut new_grid = initialize_grid(grid_size);
-    let mut history: VecDeque<Grid> = VecDeque::with_capacity(MAX_HISTORY);
+    #[test]
+    fn test_count_live_neighbors_alive_border() {
+        let grid = vec![
+            vec![true, false, false],
+            vec![false, true, false],
+            vec![false, false, false],
+        ];
 
-    seed_grid_with_random_cells(&mut grid);
+        // The five off-grid neighbors of a corner count as alive
+        assert_eq!(count_live_neighbors(&grid, 0, 0, Topology::Finite), 1);
+        assert_eq!(count_live_neighbors(&grid, 0, 0, Topology::AliveBorder), 6);
+        assert_eq!(count_live_neighbors(&grid, 0, 1, Topology::AliveBorder), 5);
+        assert_eq!(count_live_neighbors(&grid, 1, 1, Topology::AliveBorder), 1);
+    }
 
-    for iteration in 0..MAX_ITERATIONS {
-        display_grid(&grid, iteration);
-        
-        if exit_steady && detect_steady_state(&grid, &mut history) {
-            println!("Repeating or steady state detected. Terminating at iteration {}.", iteration);
-            break;
-        }
+    #[test]
+    fn test_alive_border_grows_from_empty_grid() {
+        let grid = initialize_grid(4, 4);
+        let mut next = initialize_grid(4, 4);
+
+        compute_next_generation(&grid, &mut next, Topology::AliveBorder);
 
-        compute_next_generation(&grid, &mut new_grid);
-        std::mem::swap(&mut grid, &mut new_grid);
-    
-        thread::sleep(SLEEP_DURATION);
+        // Corners see five live wall cells, edge cells three, so only edges are born
+        assert!(!next[0][0] && next[0][1] && next[1][0] && !next[1][1]);
     }
-}
 
-/// Detects if the current grid state matches any state in the history, indicating a steady state or oscillation.
-fn detect_steady_state(grid: &Grid, history: &mut VecDeque<Grid>) -> bool {
-    if history.contains(grid) {
-        return true;
-    }
-    if history.len() == MAX_HISTORY {
-        history.pop_front();
-    }
-    history.push_back(grid.clone());
-    false
-}
-
-/// Computes the next generation of the grid based on the current state.
-fn compute_next_generation(current: &Grid, next: &mut Grid) {
-    let size = current.len();
-
-    for row in 0..size {
-        for col in 0..size {    
-            let live_neighbors = count_live_neighbors(current, row, col);
-            next[row][col] = match (current[row][col], live_neighbors) {
-                // Live cell survives with 2 or 3 neighbors.
-                (true, 2) | (true, 3) => true,
-                // Live cell dies with other number of neighbors.
-                (true, _) => false,
-                // Dead cell becomes alive with exactly 3 neighbors.
-                (false, 3) => true,
-                // Dead cell stays dead otherwise.
-                (false, _) => false,
-            };
+    #[test]
+    fn test_population_window_detects_stabilization() {
+        let mut window = PopulationWindow::new(4);
+
+        // Large swings first, then a series wobbling around 100
+        for population in [40, 90, 160, 70, 120, 99, 101] {
+            assert_eq!(window.push(population), None);
         }
+        assert_eq!(window.push(100), None);
+        assert_eq!(window.push(100), Some(100));
     }
-}
 
-/// Counts how many of the neighboring cells are alive.
-fn count_live_neighbors(grid: &Grid, row: usize, col: usize) -> i32 {
-    let size = grid.len() as i32;
-    NEIGHBOR_OFFSETS.iter().filter(|&&(dx, dy)| {
-        let nx = row as i32 + dx;
-        let ny = col as i32 + dy;
-        nx >= 0 && ny >= 0 && nx < size && ny < size && grid[nx as usize][ny as usize]
-    }).count() as i32
-}
+    #[test]
+    fn test_stabilize_terminates_simulation() {
+        // Glider on a torus keeps 5 live cells but never repeats within the default history
+        let mut grid = initialize_grid(8, 8);
+        for (row, col) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] {
+            grid[row][col] = true;
+        }
+        let config = SimulationConfig {
+            grid_size: 8,
+            topology: Topology::Toroidal,
+            stabilize: Some(6),
+            ..Default::default()
+        };
+        let mut simulation = Simulation::new(&config, grid);
 
-/// Clears the terminal screen.
-fn clear_screen() {
-    print!("\x1B[2J\x1B[H");
-    std::io::stdout().flush().expect("Failed to flush stdout");
-}
+        assert_eq!(simulation.run_until(100), Some(TerminationReason::Stabilized(5)));
+        assert_eq!(simulation.universe.generation(), 5);
+    }
+
+    #[test]
+    fn test_display_cells() {
+        assert_eq!(display_cells(&vec![vec![true, true], vec![true, true]]).to_string(), "# #\n# #\n");
+        assert_eq!(display_cells(&vec![vec![false, true, false]]).to_string(), ". # .\n");
+        assert_eq!(display_cells(&initialize_grid(0, 0)).to_string(), "");
+    }
+
+    #[test]
+    fn test_resize_grid_shrink() {
+        let grid = parse_grid("#..#\n.#..\n..##").unwrap();
+
+        assert_eq!(resize_grid(&grid, 2, 2), parse_grid("#.\n.#").unwrap());
+        assert_eq!(resize_grid(&grid, 0, 0), initialize_grid(0, 0));
+    }
+
+    #[test]
+    fn test_resize_grid_grow() {
+        let grid = parse_grid("#.\n.#").unwrap();
+
+        assert_eq!(resize_grid(&grid, 3, 4), parse_grid("#...\n.#..\n....").unwrap());
+        assert_eq!(resize_grid(&grid, 1, 3), parse_grid("#..").unwrap());
+    }
+
+    #[test]
+    fn test_universe_resize_keeps_stepping() {
+        let mut universe: Universe = ".....\n.###.\n.....".parse().unwrap();
+
+        universe.resize(5, 5);
+        universe.step_once();
+
+        assert_eq!(universe.grid(), &parse_grid("..#..\n..#..\n..#..\n.....\n.....").unwrap());
+    }
+
+    #[test]
+    fn test_parse_grid() {
+        assert_eq!(parse_grid("##\n..").unwrap(), vec![vec![true, true], vec![false, false]]);
+        assert_eq!(parse_grid("# .\n. #\n\n").unwrap(), vec![vec![true, false], vec![false, true]]);
+        assert_eq!(parse_grid("").unwrap(), initialize_grid(0, 0));
+        assert_eq!(
+            parse_grid("##\n#").unwrap_err(),
+            ParseGridError::RaggedRow { line: 2, expected: 2, found: 1 }
+        );
+        assert_eq!(
+            parse_grid("#o").unwrap_err(),
+            ParseGridError::UnexpectedChar { line: 1, character: 'o' }
+        );
+    }
+
+    #[test]
+    fn test_grid_text_round_trip() {
+        let config = SimulationConfig { grid_size: 12, grid_width: Some(17), seed: 4, ..Default::default() };
+        let grid = create_seeded_grid(&config);
 
-/// Displays the grid in the terminal.
-fn display_grid(grid: &Grid, iteration: i32) {
-    print!("\x1B[H");
+        assert_eq!(parse_grid(&display_cells(&grid).to_string()).unwrap(), grid);
 
-    let mut output = String::with_capacity(grid.len() * (grid.len() + 1) + 20);
-    for row in grid {
-        for &cell in row {
-            output.push_str(if cell { "# " } else { ". " });
+        let universe = Universe::new(grid, Topology::Finite);
+        assert_eq!(universe.to_string().parse::<Universe>().unwrap(), universe);
+    }
+
+    #[test]
+    fn test_run_headless_steady_state() {
+        // Block still life
+        let mut grid = initialize_grid(4, 4);
+        for (row, col) in [(1, 1), (1, 2), (2, 1), (2, 2)] {
+            grid[row][col] = true;
         }
-        output.push('\n');
+        let config = SimulationConfig { grid_size: 4, ..Default::default() };
+
+        let (outcome, last) = run_headless(&config, grid.clone(), 100);
+
+        assert_eq!(outcome, SimulationOutcome::SteadyState { iteration: 1, period: 1 });
+        assert_eq!(last, grid);
     }
-    output.push_str(&format!("Iteration: {}\n", iteration));
 
-    print!("{}", output);
-    std::io::stdout().flush().expect("Failed to flush stdout");
-}
+    #[test]
+    fn test_run_headless_oscillation() {
+        // Blinker
+        let mut grid = initialize_grid(5, 5);
+        for (row, col) in [(1, 2), (2, 2), (3, 2)] {
+            grid[row][col] = true;
+        }
+        let config = SimulationConfig { grid_size: 5, ..Default::default() };
 
-/// Seeds the grid with random live cells.
-fn seed_grid_with_random_cells(grid: &mut Grid) {
-    let size = grid.len();
-    let mut rng = rand::thread_rng();
-    let live_cells = rng.gen_range(size..=(size * size) / 5);
+        let (outcome, _) = run_headless(&config, grid, 100);
 
-    for _ in 0..live_cells {
-        let x: usize = rng.gen_range(0..size);
-        let y: usize = rng.gen_range(0..size);
-        grid[x][y] = true;
+        assert_eq!(outcome, SimulationOutcome::SteadyState { iteration: 2, period: 2 });
     }
-}
 
+    #[test]
+    fn test_run_headless_extinct() {
+        let mut grid = initialize_grid(4, 4);
+        grid[0][0] = true;
+        grid[3][3] = true;
+        let config = SimulationConfig { grid_size: 4, ..Default::default() };
+
+        let (outcome, last) = run_headless(&config, grid, 100);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+        assert_eq!(outcome, SimulationOutcome::Extinct { iteration: 1 });
+        assert!(is_extinct(&last));
+    }
 
     #[test]
-    fn test_initialize_grid() {
-        let size = 5;
-        let grid = initialize_grid(size);
+    fn test_run_headless_max_iterations() {
+        // Glider on a torus never repeats within 3 generations
+        let mut grid = initialize_grid(8, 8);
+        for (row, col) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] {
+            grid[row][col] = true;
+        }
+        let config = SimulationConfig { grid_size: 8, topology: Topology::Toroidal, ..Default::default() };
 
-        assert_eq!(grid.len(), size);
-        for row in &grid {
-            assert_eq!(row.len(), size);
-            assert!(row.iter().all(|&cell| !cell));
+        let (outcome, _) = run_headless(&config, grid, 3);
+
+        assert_eq!(outcome, SimulationOutcome::MaxIterations { iteration: 3 });
+    }
+
+    #[test]
+    fn test_run_headless_max_runtime() {
+        // Without an iteration limit, only the wall-clock limit stops the glider
+        let mut grid = initialize_grid(12, 12);
+        for (row, col) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] {
+            grid[row][col] = true;
+        }
+        let limit = std::time::Duration::from_millis(20);
+        let config = SimulationConfig { grid_size: 12, topology: Topology::Toroidal, max_runtime: Some(limit), ..Default::default() };
+
+        let started = std::time::Instant::now();
+        let (outcome, _) = run_headless(&config, grid.clone(), u64::MAX);
+
+        assert!(started.elapsed() >= limit);
+        assert!(started.elapsed() < std::time::Duration::from_secs(5), "{:?}", started.elapsed());
+        assert!(matches!(outcome, SimulationOutcome::MaxRuntime { iteration } if iteration > 0), "{:?}", outcome);
+
+        // The stop is reported at the default log level
+        #[cfg(feature = "terminal")]
+        {
+            use crate::logging::{subscriber, LogBuffer, LogLevel};
+
+            let display = DisplayOptions { headless: true, ..Default::default() };
+            let buffer = LogBuffer::default();
+            let (metrics, _) = tracing::subscriber::with_default(subscriber(LogLevel::default(), buffer.clone()), || {
+                run_game_of_life_simulation(&config, &display, grid, &AtomicBool::new(false))
+            });
+
+            let logged = buffer.contents();
+            assert!(logged.contains("Stopped after "), "{}", logged);
+            assert!(logged.contains(&format!(" seconds at iteration {}.", metrics.total_iterations)), "{}", logged);
         }
     }
 
     #[test]
-    fn test_detect_steady_state() {
-        let mut history = VecDeque::with_capacity(MAX_HISTORY);
-        let grid = vec![vec![true, false], vec![false, true]];
+    fn test_summarize_short_lived_seed() {
+        let config = SimulationConfig { grid_size: 6, seed: 3, density: Some(0.2), ..Default::default() };
 
-        // Initially, the grid is not in history
-        assert!(!detect_steady_state(&grid, &mut history));
+        let summary = summarize_run(&config, create_seeded_grid(&config));
 
-        // After being added, the grid is in history
-        assert!(detect_steady_state(&grid, &mut history));
+        assert_eq!(summary.outcome, SimulationOutcome::Extinct { iteration: 3 });
+        assert_eq!(summary.to_string(), "seed=3 iteration=3 population=0 outcome=extinct");
     }
 
     #[test]
-    fn test_compute_next_generation() {
-        let current = vec![
+    fn test_universe_steps_without_io() {
+        let blinker = vec![
             vec![false, true, false],
             vec![false, true, false],
             vec![false, true, false],
         ];
-        let mut next = initialize_grid(3);
+        let mut universe = Universe::new(blinker.clone(), Topology::Finite);
 
-        compute_next_generation(&current, &mut next);
+        universe.step_once();
+        assert_eq!(universe.generation(), 1);
+        assert_eq!(universe.grid()[1], vec![true, true, true]);
 
-        let expected = vec![
-            vec![false, false, false],
-            vec![true, true, true],
-            vec![false, false, false],
+        universe.step_once();
+        assert_eq!(universe.generation(), 2);
+        assert_eq!(universe.grid(), &blinker);
+    }
+
+    #[test]
+    fn test_active_cells_match_naive_computation() {
+        // Random soup on one side, a blinker and a block on the other
+        let config = SimulationConfig { grid_size: 24, grid_width: Some(40), seed: 9, density: Some(0.3), ..Default::default() };
+        let mut grid = create_seeded_grid(&config);
+        for row in grid.iter_mut() {
+            row[20..].fill(false);
+        }
+        for (row, col) in [(5, 30), (6, 30), (7, 30), (15, 30), (15, 31), (16, 30), (16, 31)] {
+            grid[row][col] = true;
+        }
+
+        for topology in [Topology::Finite, Topology::Toroidal] {
+            let mut naive = grid.clone();
+            let mut naive_next = grid.clone();
+            let mut tracked = grid.clone();
+            let mut tracked_next = grid.clone();
+            let mut active = ActiveCells::all(24, 40);
+
+            for _ in 0..50 {
+                compute_next_generation(&naive, &mut naive_next, topology);
+                std::mem::swap(&mut naive, &mut naive_next);
+                active = compute_next_generation_active(&tracked, &mut tracked_next, topology, &Rules::conway(), &active);
+                std::mem::swap(&mut tracked, &mut tracked_next);
+
+                assert_eq!(tracked, naive);
+            }
+            assert!(active.len() < 24 * 40);
+        }
+    }
+
+    #[test]
+    fn test_incremental_counts_match_naive_computation() {
+        for (seed, topology) in [(1, Topology::Finite), (2, Topology::Toroidal), (3, Topology::CylindricalVertical), (4, Topology::AliveBorder)] {
+            let config = SimulationConfig { grid_size: 20, grid_width: Some(33), seed, density: Some(0.35), topology, ..Default::default() };
+            let mut naive = create_seeded_grid(&config);
+            let mut naive_next = naive.clone();
+            let mut counted = naive.clone();
+            let mut counted_next = naive.clone();
+            let mut counts = NeighborCounts::new(&counted, topology);
+
+            for _ in 0..200 {
+                compute_next_generation_with_rules(&naive, &mut naive_next, topology, &Rules::conway());
+                std::mem::swap(&mut naive, &mut naive_next);
+                compute_next_generation_incremental(&counted, &mut counted_next, topology, &Rules::conway(), &mut counts);
+                std::mem::swap(&mut counted, &mut counted_next);
+
+                assert_eq!(counted, naive, "{:?}", topology);
+                assert_eq!(counts, NeighborCounts::new(&counted, topology));
+            }
+        }
+    }
+
+    #[test]
+    fn test_universe_incremental_counts_on_tiny_torus() {
+        // On a 2x2 torus every neighbor is reached through two offsets
+        let mut standard = Universe::new(vec![vec![true, false], vec![false, false]], Topology::Toroidal).with_rules("B2/S".parse().unwrap());
+        let mut counted = standard.clone().with_incremental_counts(true);
+
+        for _ in 0..10 {
+            standard.step_once();
+            counted.step_once();
+            assert_eq!(counted.grid(), standard.grid());
+        }
+    }
+
+    #[test]
+    fn test_dead_border_kills_surviving_border_cells() {
+        // The block on the top edge is a still life that survives a standard step
+        let mut grid = initialize_grid(8, 8);
+        for (row, col) in [(0, 5), (0, 6), (1, 5), (1, 6)] {
+            grid[row][col] = true;
+        }
+        let mut standard = Universe::new(grid.clone(), Topology::Finite);
+        let mut bordered = Universe::new(grid, Topology::Finite).with_dead_border(true);
+
+        standard.step_once();
+        bordered.step_once();
+
+        assert!(standard.grid()[0][5]);
+        assert!(!bordered.grid()[0][5]);
+        assert!(bordered.grid()[1][5]);
+    }
+
+    #[test]
+    fn test_enforce_dead_border() {
+        let mut grid = vec![vec![true; 4]; 3];
+
+        enforce_dead_border(&mut grid);
+
+        assert_eq!(grid, vec![vec![false; 4], vec![false, true, true, false], vec![false; 4]]);
+        enforce_dead_border(&mut Vec::new());
+    }
+
+    #[test]
+    fn test_dead_border_with_every_stepping_method() {
+        let config = SimulationConfig { grid_size: 20, seed: 6, density: Some(0.4), dead_border: true, ..Default::default() };
+        let mut standard = Universe::from_config(&config);
+        let mut others = [
+            Universe::from_config(&SimulationConfig { active_cells: true, ..config.clone() }),
+            Universe::from_config(&SimulationConfig { incremental_counts: true, ..config.clone() }),
+            Universe::from_config(&SimulationConfig { packed: true, ..config.clone() }),
+            Universe::from_config(&config).with_threads(3),
         ];
-        assert_eq!(next, expected);
+
+        for _ in 0..40 {
+            standard.step_once();
+            for other in &mut others {
+                other.step_once();
+                assert_eq!(other.grid(), standard.grid());
+            }
+        }
     }
 
     #[test]
-    fn test_count_live_neighbors() {
-        let grid = vec![
-            vec![true, false, true],
-            vec![false, true, false],
-            vec![true, false, true],
+    fn test_run_with_observer_sees_every_generation() {
+        let blinker = parse_grid(".....\n.....\n.###.\n.....\n.....").unwrap();
+        let config = SimulationConfig { grid_size: 5, ..Default::default() };
+        let mut populations = Vec::new();
+        let mut vertical = Vec::new();
+
+        let (outcome, last) = run_with_observer(&config, blinker.clone(), 4, |grid, generation| {
+            populations.push((generation, count_population(grid)));
+            vertical.push(grid[1][2]);
+        });
+
+        // The period two oscillation is detected once the initial state comes back
+        assert_eq!(outcome, SimulationOutcome::SteadyState { iteration: 2, period: 2 });
+        assert_eq!(populations, vec![(1, 3), (2, 3)]);
+        assert_eq!(vertical, vec![true, false]);
+        assert_eq!(last, blinker);
+    }
+
+    #[test]
+    fn test_universe_with_highlife_rule() {
+        // Under B36/S23 a dead cell with six neighbors is born
+        let mut grid = initialize_grid(5, 5);
+        for (row, col) in [(1, 1), (1, 2), (1, 3), (3, 1), (3, 2), (3, 3)] {
+            grid[row][col] = true;
+        }
+        let mut conway = Universe::new(grid.clone(), Topology::Finite);
+        let mut highlife = Universe::new(grid, Topology::Finite).with_rules("B36/S23".parse().unwrap());
+
+        conway.step_once();
+        highlife.step_once();
+
+        assert!(!conway.grid()[2][2]);
+        assert!(highlife.grid()[2][2]);
+    }
+
+    #[test]
+    fn test_set_cell_keeps_every_backend_in_step() {
+        let config = SimulationConfig { grid_size: 20, seed: 2, density: Some(0.3), ..Default::default() };
+        let mut universes = [
+            Universe::from_config(&config),
+            Universe::from_config(&SimulationConfig { active_cells: true, ..config.clone() }),
+            Universe::from_config(&SimulationConfig { incremental_counts: true, ..config.clone() }),
         ];
+        for generation in 0..40 {
+            for universe in &mut universes {
+                if generation % 5 == 0 {
+                    // A glider drawn into settled parts of the grid, plus a cell outside of it
+                    for (row, col) in [(1, 2), (2, 3), (3, 1), (3, 2), (3, 3)] {
+                        universe.set_cell(row + generation % 15, col + generation % 15, true);
+                    }
+                    universe.set_cell(25, 0, true);
+                }
+                universe.step_once();
+            }
+            assert_eq!(universes[1].grid(), universes[0].grid(), "active cells, generation {}", generation);
+            assert_eq!(universes[2].grid(), universes[0].grid(), "incremental counts, generation {}", generation);
+        }
+    }
+
+    #[test]
+    fn test_rule_fn_overrides_the_rule_and_backends() {
+        let highlife: Rules = "B36/S23".parse().unwrap();
+        let config = SimulationConfig { grid_size: 24, seed: 6, density: Some(0.4), active_cells: true, packed: true, ..Default::default() };
+        let mut native = Universe::from_config(&SimulationConfig { rule: highlife, active_cells: false, packed: false, ..config.clone() });
+        let mut scripted = Universe::from_config(&SimulationConfig { rule_fn: Some(SharedRuleFn::new(highlife)), ..config });
+
+        for _ in 0..30 {
+            native.step_once();
+            scripted.step_once();
+            assert_eq!(scripted.grid(), native.grid(), "generation {}", native.generation());
+            assert_eq!(scripted.changes(), native.changes());
+        }
+        // A fork evolves under its own rule again, with the active cells tracked from where it starts
+        let mut conway = scripted.fork(Rules::conway());
+        let mut reference = Universe::new(scripted.grid().clone(), Topology::Finite);
+        for _ in 0..10 {
+            conway.step_once();
+            reference.step_once();
+            assert_eq!(conway.grid(), reference.grid());
+        }
+    }
+
+    #[cfg(feature = "terminal")]
+    #[test]
+    fn test_streamed_run_stops_at_steady_state() {
+        let block = parse_grid("....\n.##.\n.##.\n....").unwrap();
+        let config = SimulationConfig { grid_size: 4, exit_steady: true, ..Default::default() };
+        let mut streamed = Vec::new();
+
+        let (metrics, last) = run_streamed_simulation(&config, block.clone(), 0, &AtomicBool::new(false), &mut RunRecording::default(), |_, iteration| {
+            streamed.push(iteration)
+        });
+
+        assert_eq!(metrics.termination_reason, TerminationReason::SteadyState(1));
+        assert_eq!(streamed, vec![0, 1]);
+        assert_eq!(last, block);
+    }
+
+    #[test]
+    fn test_ab_comparison_of_conway_and_seeds_diverges() {
+        let conway = SimulationConfig { grid_size: 10, ..Default::default() };
+        let seeds = SimulationConfig { rule: "B2/S".parse().unwrap(), ..conway.clone() };
+
+        let frames = run_ab_comparison(&conway, &seeds, 42, 5);
+
+        assert_eq!(frames.len(), 6);
+        assert_eq!(frames[0].0, frames[0].1);
+        assert_eq!(frames[0].0, create_seeded_grid(&SimulationConfig { seed: 42, ..conway.clone() }));
+        assert_ne!(frames[2].0, frames[2].1);
+        assert_eq!(frames.iter().map(|(a, _)| a.clone()).collect::<Vec<_>>(), collect_frames(&conway, frames[0].0.clone(), 5));
+    }
+
+    #[test]
+    fn test_frame_jitter() {
+        use std::time::Duration;
+
+        let start = std::time::Instant::now();
+        let mut even = FrameJitter::default();
+        let mut uneven = FrameJitter::default();
+        for (frame, offset) in [0, 50, 100, 150].into_iter().enumerate() {
+            even.tick(start + Duration::from_millis(offset));
+            uneven.tick(start + Duration::from_millis([0, 20, 100, 120][frame]));
+        }
+
+        assert!(even.std_dev() < Duration::from_micros(1), "{:?}", even.std_dev());
+        assert!((uneven.std_dev().as_secs_f64() - 0.0283).abs() < 1e-3, "{:?}", uneven.std_dev());
+        assert_eq!(FrameJitter::default().std_dev(), Duration::ZERO);
+    }
+
+    #[cfg(feature = "terminal")]
+    #[test]
+    fn test_threaded_run_finishes_both_threads() {
+        use std::time::Duration;
+
+        let config = SimulationConfig { grid_size: 12, seed: 3, max_iterations: Some(10), ..Default::default() };
+        let display = DisplayOptions { delay: Some(Duration::ZERO), ..Default::default() };
+        let initial = create_seeded_grid(&config);
+
+        let (metrics, last) = run_threaded_simulation(&config, &display, initial.clone(), 0, &AtomicBool::new(false), &mut RunRecording::default());
+
+        assert_eq!(metrics.termination_reason, TerminationReason::MaxIterations);
+        assert_eq!(metrics.total_iterations, 10);
+        assert_eq!(last, collect_frames(&config, initial, 10).pop().unwrap());
+    }
+
+    #[test]
+    fn test_remaining_frame_time() {
+        use std::time::Duration;
+
+        let target = Duration::from_millis(50);
+
+        assert_eq!(remaining_frame_time(target, Duration::from_millis(20)), Some(Duration::from_millis(30)));
+        assert_eq!(remaining_frame_time(target, target), Some(Duration::ZERO));
+        assert_eq!(remaining_frame_time(target, Duration::from_millis(80)), None);
+        assert_eq!(remaining_frame_time(Duration::ZERO, Duration::ZERO), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn test_universe_fork_starts_from_same_grid() {
+        let config = SimulationConfig { grid_size: 12, seed: 4, ..Default::default() };
+        let mut conway = Universe::from_config(&config);
+        let mut highlife = conway.fork("B36/S23".parse().unwrap());
+
+        assert_eq!(highlife.grid(), conway.grid());
+        assert_eq!(highlife.generation(), conway.generation());
+        assert_eq!(highlife.rules().to_string(), "B36/S23");
+
+        conway.step_once();
+        highlife.step_once();
+        assert_eq!(highlife.generation(), conway.generation());
+    }
+
+    #[test]
+    fn test_universe_active_cells_match_standard() {
+        let config = SimulationConfig { grid_size: 30, seed: 2, topology: Topology::CylindricalHorizontal, ..Default::default() };
+        let mut standard = Universe::from_config(&config);
+        let mut tracked = Universe::from_config(&SimulationConfig { active_cells: true, ..config });
+
+        for _ in 0..50 {
+            standard.step_once();
+            tracked.step_once();
+            assert_eq!(tracked.grid(), standard.grid());
+        }
+    }
+
+    /// Compares full and active cell stepping on a settled grid; run with `cargo test --release -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_active_cells() {
+        const GENERATIONS: usize = 1000;
+
+        // A 200x200 grid of blocks with a single blinker
+        let mut grid = initialize_grid(200, 200);
+        for row in (0..200).step_by(5) {
+            for col in (0..200).step_by(5) {
+                let blinker = row == 100 && col == 100;
+                grid[row][col] = !blinker;
+                grid[row][col + 1] = true;
+                grid[row + 1][col] = !blinker;
+                grid[row + 1][col + 1] = true;
+            }
+        }
+        grid[102][101] = true;
+        let mut standard = Universe::new(grid.clone(), Topology::Finite);
+        let mut tracked = Universe::new(grid, Topology::Finite).with_active_cells(true);
+
+        let started = std::time::Instant::now();
+        for _ in 0..GENERATIONS {
+            standard.step_once();
+        }
+        let full = started.elapsed();
+
+        let started = std::time::Instant::now();
+        for _ in 0..GENERATIONS {
+            tracked.step_once();
+        }
+        let active = started.elapsed();
+
+        assert_eq!(tracked.grid(), standard.grid());
+        println!(
+            "200x200, {} generations: full {:?}, active cells {:?} ({:.1}x)",
+            GENERATIONS,
+            full,
+            active,
+            full.as_secs_f64() / active.as_secs_f64()
+        );
+    }
+
+    #[test]
+    fn test_universe_packed_matches_standard() {
+        let config = SimulationConfig { grid_size: 12, seed: 21, density: Some(0.4), ..Default::default() };
+        let mut standard = Universe::from_config(&config);
+        let mut packed = Universe::from_config(&SimulationConfig { packed: true, ..config });
+
+        for _ in 0..20 {
+            standard.step_once();
+            packed.step_once();
+            assert_eq!(packed.grid(), standard.grid());
+        }
+    }
+
+    #[test]
+    fn test_create_seeded_grid_is_deterministic() {
+        let config = SimulationConfig {
+            grid_size: 10,
+            exit_steady: false,
+            topology: Topology::Finite,
+            seed: 42,
+            ..Default::default()
+        };
+
+        assert_eq!(create_seeded_grid(&config), create_seeded_grid(&config));
+    }
+
+    #[test]
+    fn test_create_seeded_grid_with_density() {
+        let full = SimulationConfig { grid_size: 6, density: Some(1.0), ..Default::default() };
+        let empty = SimulationConfig { grid_size: 6, density: Some(0.0), ..Default::default() };
+
+        assert_eq!(count_population(&create_seeded_grid(&full)), 36);
+        assert_eq!(count_population(&create_seeded_grid(&empty)), 0);
+    }
+
+    #[test]
+    fn test_warm_up_advances_iteration_counter() {
+        let config = SimulationConfig { grid_size: 5, ..Default::default() };
+        let mut grid = initialize_grid(5, 5);
+        grid[1][2] = true;
+        grid[2][2] = true;
+        grid[3][2] = true;
+        let mut simulation = Simulation::new(&config, grid.clone());
+
+        assert_eq!(simulation.run_until(5), None);
+
+        // The skipped generations are counted and the blinker is in its horizontal phase
+        let grid = simulation.universe.grid();
+        assert_eq!(simulation.universe.generation(), 5);
+        assert!(grid[2][1] && grid[2][2] && grid[2][3]);
+    }
+
+    #[test]
+    fn test_warm_up_stops_when_population_dies_out() {
+        let config = SimulationConfig { grid_size: 3, exit_steady: true, ..Default::default() };
+        let mut grid = initialize_grid(3, 3);
+        grid[1][1] = true;
+        let mut simulation = Simulation::new(&config, grid);
 
-        assert_eq!(count_live_neighbors(&grid, 1, 1), 4);
-        assert_eq!(count_live_neighbors(&grid, 0, 0), 1);
-        assert_eq!(count_live_neighbors(&grid, 0, 2), 1);
+        assert_eq!(simulation.run_until(100), Some(TerminationReason::Extinction));
+        assert_eq!(simulation.universe.generation(), 1);
+    }
+
+    #[test]
+    fn test_topology_from_axes() {
+        assert_eq!(Topology::from_axes(false, false), Topology::Finite);
+        assert_eq!(Topology::from_axes(true, false), Topology::CylindricalHorizontal);
+        assert_eq!(Topology::from_axes(false, true), Topology::CylindricalVertical);
+        assert_eq!(Topology::from_axes(true, true), Topology::Toroidal);
+    }
+
+    /// Places a glider with its bounding box top-left corner at (row, col) on the grid.
+    fn place_glider(grid: &mut Grid, row: usize, col: usize, cells: [(usize, usize); 5]) {
+        for (dr, dc) in cells {
+            grid[row + dr][col + dc] = true;
+        }
+    }
+
+    #[test]
+    fn test_glider_wraps_horizontally() {
+        let size = 8;
+        let mut grid = initialize_grid(size, size);
+        let mut next = initialize_grid(size, size);
+        // Glider travelling down and to the right, starting near the right edge
+        place_glider(&mut grid, 2, 5, [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+
+        for _ in 0..8 {
+            compute_next_generation(&grid, &mut next, Topology::CylindricalHorizontal);
+            std::mem::swap(&mut grid, &mut next);
+        }
+
+        // After 8 generations the glider moved two cells right and re-entered on the left
+        let mut expected = initialize_grid(size, size);
+        expected[4][0] = true;
+        expected[5][1] = true;
+        expected[6][7] = true;
+        expected[6][0] = true;
+        expected[6][1] = true;
+        assert_eq!(grid, expected);
+    }
+
+    #[test]
+    fn test_glider_disappears_at_bottom_edge_with_horizontal_wrap() {
+        let size = 8;
+        let mut grid = initialize_grid(size, size);
+        let mut next = initialize_grid(size, size);
+        // Glider travelling down and to the right, starting near the bottom edge
+        place_glider(&mut grid, 4, 2, [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+
+        for _ in 0..16 {
+            compute_next_generation(&grid, &mut next, Topology::CylindricalHorizontal);
+            std::mem::swap(&mut grid, &mut next);
+        }
+
+        // Rows don't wrap, so the glider breaks up against the bottom edge
+        assert_ne!(count_population(&grid), 5);
+    }
+
+    #[test]
+    fn test_glider_dies_at_top_edge_with_horizontal_wrap() {
+        let size = 8;
+        let mut grid = initialize_grid(size, size);
+        let mut next = initialize_grid(size, size);
+        // Glider travelling up and to the left, starting near the top edge
+        place_glider(&mut grid, 1, 3, [(0, 0), (0, 1), (0, 2), (1, 0), (2, 1)]);
+
+        for _ in 0..16 {
+            compute_next_generation(&grid, &mut next, Topology::CylindricalHorizontal);
+            std::mem::swap(&mut grid, &mut next);
+        }
+
+        // The glider can't pass the bounded top edge and decays into debris
+        assert_ne!(count_population(&grid), 5);
+
+        // The same glider survives intact when the rows wrap as well
+        let mut grid = initialize_grid(size, size);
+        place_glider(&mut grid, 1, 3, [(0, 0), (0, 1), (0, 2), (1, 0), (2, 1)]);
+        for _ in 0..16 {
+            compute_next_generation(&grid, &mut next, Topology::Toroidal);
+            std::mem::swap(&mut grid, &mut next);
+        }
+        assert_eq!(count_population(&grid), 5);
     }
 
     #[test]
     fn test_edge_case_empty_grid() {
-        let grid = initialize_grid(0);
+        let grid = initialize_grid(0, 0);
 
         // Detecting steady state should handle empty grid gracefully
-        let mut history = VecDeque::new();
-        assert!(!detect_steady_state(&grid, &mut history));
+        let mut history = GridHashCache::new();
+        assert!(detect_steady_state(&grid, &mut history).is_none());
 
         // Compute next generation on empty grid
-        let mut next = initialize_grid(0);
-        compute_next_generation(&grid, &mut next);
+        let mut next = initialize_grid(0, 0);
+        compute_next_generation(&grid, &mut next, Topology::Finite);
         assert_eq!(next.len(), 0);
     }
 
     #[test]
     fn test_edge_case_single_cell() {
         let grid = vec![vec![true]];
-        let mut next = initialize_grid(1);
+        let mut next = initialize_grid(1, 1);
 
-        compute_next_generation(&grid, &mut next);
+        compute_next_generation(&grid, &mut next, Topology::Finite);
 
         // Single live cell dies in the next generation
         assert_eq!(next, vec![vec![false]]);
     }
-}
\ No newline at end of file
+
+    #[cfg(feature = "terminal")]
+    #[test]
+    fn test_run_records_animation_after_warmup() {
+        let path = std::env::temp_dir().join(format!("rgol-test-animation-{}.svg", std::process::id()));
+        let config = SimulationConfig { grid_size: 12, seed: 3, warmup: 2, max_iterations: Some(5), ..Default::default() };
+        let display = DisplayOptions { headless: true, ..Default::default() };
+        let mut recording = RunRecording::default().with_svg_animation(std::fs::File::create(&path).unwrap(), 100, 1, config.warmup);
+
+        resume_game_of_life_simulation(&config, &display, create_seeded_grid(&config), 0, &AtomicBool::new(false), &mut recording);
+        recording.finish().unwrap();
+        let svg = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // Generations 2 to 5, the final one included
+        assert_eq!(svg.matches(r#"<set attributeName="visibility""#).count(), 4);
+        assert!(svg.contains(r#"begin="0s;cycle.end" dur="0.4s""#));
+    }
+}
@@ -0,0 +1,227 @@
+#[cfg(feature = "terminal")]
+use std::io::Write;
+#[cfg(feature = "terminal")]
+use std::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "terminal")]
+use std::thread;
+#[cfg(feature = "terminal")]
+use std::time::Instant;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+#[cfg(feature = "terminal")]
+use crate::game::{detect_steady_state, print_termination_message, GridHashCache, SimulationConfig, SLEEP_DURATION};
+#[cfg(feature = "terminal")]
+use crate::display::clear_screen;
+#[cfg(feature = "terminal")]
+use crate::metrics::{PopulationTracker, SimulationMetrics, TerminationReason};
+use crate::rules::Rules;
+
+/// Three-dimensional grid of cells stored in a flat vector, layer by layer.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid3D {
+    cells: Vec<bool>,
+    rows: usize,
+    cols: usize,
+    depth: usize,
+}
+
+impl Grid3D {
+    /// Creates a grid with all cells dead.
+    pub fn new(rows: usize, cols: usize, depth: usize) -> Self {
+        Grid3D {
+            cells: vec![false; rows * cols * depth],
+            rows,
+            cols,
+            depth,
+        }
+    }
+
+    fn index(&self, r: usize, c: usize, d: usize) -> usize {
+        (d * self.rows + r) * self.cols + c
+    }
+
+    pub fn get(&self, r: usize, c: usize, d: usize) -> bool {
+        self.cells[self.index(r, c, d)]
+    }
+
+    pub fn set(&mut self, r: usize, c: usize, d: usize, alive: bool) {
+        let index = self.index(r, c, d);
+        self.cells[index] = alive;
+    }
+
+    /// Counts the live cells in the whole grid.
+    pub fn population(&self) -> usize {
+        self.cells.iter().filter(|&&cell| cell).count()
+    }
+}
+
+/// Counts how many of the 26 neighboring cells are alive, treating cells beyond the edges as dead.
+pub fn count_live_neighbors_3d(grid: &Grid3D, r: usize, c: usize, d: usize) -> u32 {
+    let mut count = 0;
+    for dd in -1..=1_i64 {
+        for dr in -1..=1_i64 {
+            for dc in -1..=1_i64 {
+                if (dd, dr, dc) == (0, 0, 0) {
+                    continue;
+                }
+                let nr = r as i64 + dr;
+                let nc = c as i64 + dc;
+                let nd = d as i64 + dd;
+                let inside = (0..grid.rows as i64).contains(&nr)
+                    && (0..grid.cols as i64).contains(&nc)
+                    && (0..grid.depth as i64).contains(&nd);
+                if inside && grid.get(nr as usize, nc as usize, nd as usize) {
+                    count += 1;
+                }
+            }
+        }
+    }
+    count
+}
+
+/// Computes the next generation of the 3D grid based on the current state.
+pub fn compute_next_generation_3d(current: &Grid3D, next: &mut Grid3D, rules: &Rules) {
+    for d in 0..current.depth {
+        for r in 0..current.rows {
+            for c in 0..current.cols {
+                let live_neighbors = count_live_neighbors_3d(current, r, c, d);
+                next.set(r, c, d, rules.next_state(current.get(r, c, d), live_neighbors));
+            }
+        }
+    }
+}
+
+/// Runs the 3D simulation on a box with the configured rows and columns and the given depth.
+#[cfg(feature = "terminal")]
+pub fn run_3d_simulation(config: &SimulationConfig, depth: usize, interrupted: &AtomicBool) -> SimulationMetrics {
+    let started = Instant::now();
+    let rules = Rules::bays_5766();
+    let (rows, cols) = config.dimensions();
+    let mut grid = Grid3D::new(rows, cols, depth);
+    let mut new_grid = grid.clone();
+    let mut history: GridHashCache<Grid3D> = GridHashCache::new();
+    let mut tracker = PopulationTracker::default();
+    let mut iteration = 0;
+
+    seed_grid_3d(&mut grid, config.seed);
+    clear_screen();
+
+    let termination_reason = loop {
+        if iteration >= config.iteration_limit() {
+            print_termination_message(TerminationReason::MaxIterations, iteration);
+            break TerminationReason::MaxIterations;
+        }
+
+        display_grid_3d(&grid, iteration);
+
+        let population = grid.population();
+        tracker.record(iteration, population, grid.cells.len());
+
+        if config.exit_steady {
+            let termination = if population == 0 {
+                Some(TerminationReason::Extinction)
+            } else {
+                detect_steady_state(&grid, &mut history).map(TerminationReason::SteadyState)
+            };
+            if let Some(reason) = termination {
+                print_termination_message(reason, iteration);
+                break reason;
+            }
+        }
+
+        if interrupted.load(Ordering::SeqCst) {
+            break TerminationReason::UserInterrupt;
+        }
+
+        compute_next_generation_3d(&grid, &mut new_grid, &rules);
+        std::mem::swap(&mut grid, &mut new_grid);
+        iteration += 1;
+
+        thread::sleep(SLEEP_DURATION);
+    };
+
+    tracker.finish(iteration, grid.population(), started.elapsed(), termination_reason)
+}
+
+/// Displays the 3D grid as a sequence of 2D layers.
+#[cfg(feature = "terminal")]
+fn display_grid_3d(grid: &Grid3D, iteration: u64) {
+    print!("\x1B[H");
+
+    let mut output = String::new();
+    for d in 0..grid.depth {
+        output.push_str(&format!("--- depth {} ---\n", d));
+        for r in 0..grid.rows {
+            for c in 0..grid.cols {
+                output.push_str(if grid.get(r, c, d) { "# " } else { ". " });
+            }
+            output.push('\n');
+        }
+    }
+    output.push_str(&format!("Iteration: {}\n", iteration));
+
+    print!("{}", output);
+    std::io::stdout().flush().expect("Failed to flush stdout");
+}
+
+/// Seeds the 3D grid with random live cells, deterministically for a given seed.
+pub fn seed_grid_3d(grid: &mut Grid3D, seed: u64) {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let live_cells = grid.cells.len() / 5;
+
+    for _ in 0..live_cells {
+        let r = rng.gen_range(0..grid.rows);
+        let c = rng.gen_range(0..grid.cols);
+        let d = rng.gen_range(0..grid.depth);
+        grid.set(r, c, d, true);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_live_neighbors_3d() {
+        let mut grid = Grid3D::new(3, 3, 3);
+        for d in 0..3 {
+            for r in 0..3 {
+                for c in 0..3 {
+                    grid.set(r, c, d, true);
+                }
+            }
+        }
+
+        assert_eq!(count_live_neighbors_3d(&grid, 1, 1, 1), 26);
+        assert_eq!(count_live_neighbors_3d(&grid, 0, 0, 0), 7);
+    }
+
+    #[test]
+    fn test_cube_is_still_life() {
+        let mut grid = Grid3D::new(4, 4, 4);
+        for d in 1..3 {
+            for r in 1..3 {
+                for c in 1..3 {
+                    grid.set(r, c, d, true);
+                }
+            }
+        }
+        let mut next = Grid3D::new(4, 4, 4);
+
+        compute_next_generation_3d(&grid, &mut next, &Rules::bays_5766());
+
+        assert_eq!(next, grid);
+    }
+
+    #[test]
+    fn test_empty_grid_stays_empty() {
+        let grid = Grid3D::new(5, 5, 5);
+        let mut next = Grid3D::new(5, 5, 5);
+
+        compute_next_generation_3d(&grid, &mut next, &Rules::bays_5766());
+
+        assert_eq!(next.population(), 0);
+    }
+}
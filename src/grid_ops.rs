@@ -0,0 +1,634 @@
+use std::error::Error;
+use std::fmt;
+
+#[cfg(feature = "terminal")]
+use clap::ValueEnum;
+
+use crate::game::{grid_dimensions, initialize_grid, Grid};
+
+/// Error returned when combining grids of different dimensions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DimensionError {
+    /// Dimensions of the left operand as `(rows, cols)`.
+    pub left: (usize, usize),
+    /// Dimensions of the right operand as `(rows, cols)`.
+    pub right: (usize, usize),
+}
+
+impl fmt::Display for DimensionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Grid dimensions don't match: {}x{} and {}x{}",
+            self.left.0, self.left.1, self.right.0, self.right.1
+        )
+    }
+}
+
+impl Error for DimensionError {}
+
+/// Error returned for a region with swapped corners or reaching past the edge of the grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegionError {
+    /// Corners of the region as `(r1, c1, r2, c2)`.
+    pub region: (usize, usize, usize, usize),
+    /// Dimensions of the grid as `(rows, cols)`.
+    pub dimensions: (usize, usize),
+}
+
+impl fmt::Display for RegionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (r1, c1, r2, c2) = self.region;
+        write!(
+            f,
+            "Region from ({}, {}) to ({}, {}) doesn't fit in the {}x{} grid",
+            r1, c1, r2, c2, self.dimensions.0, self.dimensions.1
+        )
+    }
+}
+
+impl Error for RegionError {}
+
+/// Smallest rectangle of cells holding every live cell of a grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BoundingBox {
+    pub top: usize,
+    pub left: usize,
+    pub height: usize,
+    pub width: usize,
+}
+
+/// Combines two grids of equal dimensions cell by cell.
+fn combine(a: &Grid, b: &Grid, op: impl Fn(bool, bool) -> bool) -> Result<Grid, DimensionError> {
+    let (left, right) = (grid_dimensions(a), grid_dimensions(b));
+    if left != right {
+        return Err(DimensionError { left, right });
+    }
+
+    Ok(a.iter()
+        .zip(b)
+        .map(|(row_a, row_b)| row_a.iter().zip(row_b).map(|(&x, &y)| op(x, y)).collect())
+        .collect())
+}
+
+/// Cells alive in both grids.
+pub fn grid_and(a: &Grid, b: &Grid) -> Result<Grid, DimensionError> {
+    combine(a, b, |x, y| x && y)
+}
+
+/// Cells alive in either grid.
+pub fn grid_or(a: &Grid, b: &Grid) -> Result<Grid, DimensionError> {
+    combine(a, b, |x, y| x || y)
+}
+
+/// Cells alive in exactly one grid; for two consecutive generations these are the cells that changed.
+pub fn grid_xor(a: &Grid, b: &Grid) -> Result<Grid, DimensionError> {
+    combine(a, b, |x, y| x != y)
+}
+
+/// Cell that changed between two grids.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CellDiff {
+    pub row: usize,
+    pub col: usize,
+    pub from: bool,
+    pub to: bool,
+}
+
+/// Cells whose state differs between `a` and `b`, row by row.
+pub fn grid_diff(a: &Grid, b: &Grid) -> Result<Vec<CellDiff>, DimensionError> {
+    Ok(live_cells(&grid_xor(a, b)?)
+        .into_iter()
+        .map(|(row, col)| CellDiff { row, col, from: a[row][col], to: b[row][col] })
+        .collect())
+}
+
+/// Numbers of cells born and died going from `previous` to `next`, as `(births, deaths)`.
+///
+/// Only the cells the two grids have in common are compared.
+pub fn count_births_and_deaths(previous: &Grid, next: &Grid) -> (u64, u64) {
+    let cells = previous.iter().zip(next).flat_map(|(before, after)| before.iter().zip(after));
+    cells.fold((0, 0), |(births, deaths), (&before, &after)| match (before, after) {
+        (false, true) => (births + 1, deaths),
+        (true, false) => (births, deaths + 1),
+        _ => (births, deaths),
+    })
+}
+
+/// Lists the changed cells one per line, as `+ (row,col)` for births and `- (row,col)` for deaths.
+pub fn format_diff(diffs: &[CellDiff]) -> String {
+    diffs
+        .iter()
+        .map(|diff| format!("{} ({},{})\n", if diff.to { '+' } else { '-' }, diff.row, diff.col))
+        .collect()
+}
+
+/// Grid with every cell flipped.
+pub fn grid_not(a: &Grid) -> Grid {
+    a.iter().map(|row| row.iter().map(|&cell| !cell).collect()).collect()
+}
+
+/// Builds a `height` x `width` grid whose live cells are at the given `(row, col)` coordinates.
+///
+/// Coordinates outside of the grid are skipped.
+pub fn from_coords(width: usize, height: usize, coords: &[(usize, usize)]) -> Grid {
+    let mut grid = initialize_grid(height, width);
+    for &(row, col) in coords.iter().filter(|&&(row, col)| row < height && col < width) {
+        grid[row][col] = true;
+    }
+    grid
+}
+
+/// `(row, col)` coordinates of the live cells, row by row.
+pub fn live_cells(grid: &Grid) -> Vec<(usize, usize)> {
+    grid.iter()
+        .enumerate()
+        .flat_map(|(row, cells)| cells.iter().enumerate().filter(|(_, &alive)| alive).map(move |(col, _)| (row, col)))
+        .collect()
+}
+
+/// Kills every cell of the grid.
+pub fn grid_clear(grid: &mut Grid) {
+    grid.iter_mut().for_each(|row| row.fill(false));
+}
+
+/// Brings every cell of the grid to life.
+pub fn grid_fill(grid: &mut Grid) {
+    grid.iter_mut().for_each(|row| row.fill(true));
+}
+
+/// Sets the cells from `(r1, c1)` to `(r2, c2)`, both corners included, to `val`.
+///
+/// The grid is left untouched unless `r1 <= r2 < rows` and `c1 <= c2 < cols`.
+pub fn grid_fill_region(grid: &mut Grid, r1: usize, c1: usize, r2: usize, c2: usize, val: bool) -> Result<(), RegionError> {
+    check_region((r1, c1, r2, c2), grid_dimensions(grid))?;
+
+    for row in &mut grid[r1..=r2] {
+        row[c1..=c2].fill(val);
+    }
+    Ok(())
+}
+
+/// Checks that the region `(r1, c1, r2, c2)` has its corners in order and fits in a grid of the given `(rows, cols)`.
+pub fn check_region(region: (usize, usize, usize, usize), dimensions: (usize, usize)) -> Result<(), RegionError> {
+    let (r1, c1, r2, c2) = region;
+    if r1 > r2 || r2 >= dimensions.0 || c1 > c2 || c2 >= dimensions.1 {
+        return Err(RegionError { region, dimensions });
+    }
+    Ok(())
+}
+
+/// Number of live cells from `(r1, c1)` to `(r2, c2)`, both corners included.
+///
+/// Panics when the region doesn't fit in the grid, see [`check_region`].
+pub fn count_live_in_region(grid: &Grid, r1: usize, c1: usize, r2: usize, c2: usize) -> usize {
+    grid[r1..=r2].iter().map(|row| row[c1..=c2].iter().filter(|&&cell| cell).count()).sum()
+}
+
+/// Copies the live cells of `source` into `target` with its top left corner at the given cell.
+///
+/// Cells already alive in `target` stay alive and source cells falling outside of it are dropped.
+pub fn paste_region(target: &mut Grid, source: &Grid, top: usize, left: usize) {
+    for (target_row, source_row) in target.iter_mut().skip(top).zip(source) {
+        for (cell, &alive) in target_row.iter_mut().skip(left).zip(source_row) {
+            *cell |= alive;
+        }
+    }
+}
+
+/// Finds the bounding box of the live cells, `None` when there are none.
+pub fn bounding_box(grid: &Grid) -> Option<BoundingBox> {
+    let live_rows: Vec<(usize, usize, usize)> = grid
+        .iter()
+        .enumerate()
+        .filter_map(|(row, cells)| {
+            let first = cells.iter().position(|&alive| alive)?;
+            let last = cells.iter().rposition(|&alive| alive)?;
+            Some((row, first, last))
+        })
+        .collect();
+    let top = live_rows.first()?.0;
+    let bottom = live_rows.last()?.0;
+    let left = live_rows.iter().map(|&(_, first, _)| first).min()?;
+    let right = live_rows.iter().map(|&(_, _, last)| last).max()?;
+    Some(BoundingBox { top, left, height: bottom - top + 1, width: right - left + 1 })
+}
+
+/// Which neighboring live cells belong to the same object in [`count_objects`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "terminal", derive(ValueEnum))]
+pub enum Connectivity {
+    /// Only cells sharing an edge.
+    #[cfg_attr(feature = "terminal", value(name = "4"))]
+    Four,
+    /// Cells sharing an edge or a corner, as neighbors are counted by the rules.
+    #[default]
+    #[cfg_attr(feature = "terminal", value(name = "8"))]
+    Eight,
+}
+
+impl Connectivity {
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Connectivity::Eight => &[(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)],
+        }
+    }
+}
+
+/// Counts the objects on the grid, the groups of live cells connected to each other, e.g. the
+/// still lifes and oscillators left once a run settles. The grid's edges never join objects.
+pub fn count_objects(grid: &Grid, connectivity: Connectivity) -> usize {
+    let (rows, cols) = grid_dimensions(grid);
+    let mut visited = initialize_grid(rows, cols);
+    let mut objects = 0;
+    let mut stack = Vec::new();
+    for (row, col) in live_cells(grid) {
+        if visited[row][col] {
+            continue;
+        }
+        objects += 1;
+        visited[row][col] = true;
+        stack.push((row, col));
+        while let Some((row, col)) = stack.pop() {
+            for &(dr, dc) in connectivity.offsets() {
+                let (Some(r), Some(c)) = (row.checked_add_signed(dr), col.checked_add_signed(dc)) else {
+                    continue;
+                };
+                if r < rows && c < cols && grid[r][c] && !visited[r][c] {
+                    visited[r][c] = true;
+                    stack.push((r, c));
+                }
+            }
+        }
+    }
+    objects
+}
+
+/// Enlarges the grid by turning every cell into a `factor` × `factor` block of the same state.
+pub fn scale_pattern(grid: &Grid, factor: usize) -> Grid {
+    grid.iter()
+        .flat_map(|row| {
+            let scaled: Vec<bool> = row.iter().flat_map(|&cell| std::iter::repeat_n(cell, factor)).collect();
+            std::iter::repeat_n(scaled, factor)
+        })
+        .collect()
+}
+
+/// Shrinks the grid by turning every `factor` × `factor` block into one cell, alive when more than
+/// half of the block's cells are.
+///
+/// Blocks cut off by the right or bottom edge vote among the cells they have.
+pub fn scale_down(grid: &Grid, factor: usize) -> Grid {
+    let (rows, cols) = grid_dimensions(grid);
+    if factor == 0 {
+        return Vec::new();
+    }
+    (0..rows.div_ceil(factor))
+        .map(|block_row| {
+            let band = &grid[block_row * factor..rows.min((block_row + 1) * factor)];
+            (0..cols.div_ceil(factor))
+                .map(|block_col| {
+                    let columns = block_col * factor..cols.min((block_col + 1) * factor);
+                    let alive = band.iter().map(|row| row[columns.clone()].iter().filter(|&&cell| cell).count()).sum::<usize>();
+                    2 * alive > band.len() * columns.len()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// What to do when a pattern and its dead border don't fit in the configured grid.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "terminal", derive(ValueEnum))]
+pub enum BorderPolicy {
+    /// Enlarge the grid to fit.
+    #[default]
+    Grow,
+    /// Refuse to start.
+    Error,
+}
+
+/// Rows and columns of the smallest grid holding `pattern` centered with at least `border` dead
+/// cells on each side.
+pub fn dimensions_with_border(pattern: &Grid, border: usize) -> (usize, usize) {
+    let (height, width) = grid_dimensions(pattern);
+    (height + 2 * border, width + 2 * border)
+}
+
+/// Pastes the live cells of `pattern` into the middle of `grid`.
+///
+/// A pattern larger than the grid along an axis starts at its first row or column and is clipped.
+pub fn place_pattern_centered(grid: &mut Grid, pattern: &Grid) {
+    let (rows, cols) = grid_dimensions(grid);
+    let (height, width) = grid_dimensions(pattern);
+    paste_region(grid, pattern, rows.saturating_sub(height) / 2, cols.saturating_sub(width) / 2);
+}
+
+/// Row and column of the center of the pattern's live cells, rounded up and to the left.
+///
+/// A pattern without live cells is centered on its whole grid instead.
+pub fn center_of_pattern(pattern: &Grid) -> (usize, usize) {
+    match bounding_box(pattern) {
+        Some(bbox) => (bbox.top + (bbox.height - 1) / 2, bbox.left + (bbox.width - 1) / 2),
+        None => {
+            let (rows, cols) = grid_dimensions(pattern);
+            (rows.saturating_sub(1) / 2, cols.saturating_sub(1) / 2)
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{compute_next_generation, count_population, parse_grid, Topology};
+
+    fn sample_grid() -> Grid {
+        vec![
+            vec![true, false, true],
+            vec![false, true, true],
+        ]
+    }
+
+    #[test]
+    fn test_count_births_and_deaths() {
+        let previous = vec![vec![true, true, false], vec![false, false, false]];
+        let next = vec![vec![false, true, true], vec![true, false, false]];
+
+        assert_eq!(count_births_and_deaths(&previous, &next), (2, 1));
+        assert_eq!(count_births_and_deaths(&next, &previous), (1, 2));
+        assert_eq!(count_births_and_deaths(&next, &next), (0, 0));
+    }
+
+    #[test]
+    fn test_paste_region() {
+        let mut target = initialize_grid(3, 4);
+        target[0][0] = true;
+
+        paste_region(&mut target, &sample_grid(), 1, 2);
+
+        assert_eq!(
+            target,
+            vec![
+                vec![true, false, false, false],
+                vec![false, false, true, false],
+                vec![false, false, false, true],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_coords_round_trip() {
+        let grid = sample_grid();
+
+        let coords = live_cells(&grid);
+
+        assert_eq!(coords, vec![(0, 0), (0, 2), (1, 1), (1, 2)]);
+        assert_eq!(from_coords(3, 2, &coords), grid);
+    }
+
+    #[test]
+    fn test_from_coords_skips_out_of_range() {
+        let grid = from_coords(3, 2, &[(1, 2), (2, 0), (0, 3)]);
+
+        assert_eq!(live_cells(&grid), vec![(1, 2)]);
+        assert_eq!(grid_dimensions(&grid), (2, 3));
+    }
+
+    #[test]
+    fn test_count_live_in_corner_region() {
+        let grid = parse_grid("##..\n#..#\n..##\n...#").unwrap();
+
+        assert_eq!(count_live_in_region(&grid, 0, 0, 1, 1), 3);
+        assert_eq!(count_live_in_region(&grid, 2, 2, 3, 3), 3);
+        assert_eq!(count_live_in_region(&grid, 3, 0, 3, 0), 0);
+        assert_eq!(count_live_in_region(&grid, 0, 0, 3, 3), count_population(&grid));
+    }
+
+    #[test]
+    fn test_count_live_in_interior_region() {
+        let grid = parse_grid("##..\n#..#\n..##\n...#").unwrap();
+
+        assert_eq!(count_live_in_region(&grid, 1, 1, 2, 2), 1);
+        assert_eq!(count_live_in_region(&grid, 1, 0, 2, 3), 4);
+        assert!(check_region((1, 1, 2, 4), (4, 4)).is_err());
+        assert!(check_region((2, 1, 1, 2), (4, 4)).is_err());
+    }
+
+    #[test]
+    fn test_diff_of_identical_grids_is_empty() {
+        assert_eq!(grid_diff(&sample_grid(), &sample_grid()).unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn test_diff_of_blinker_step() {
+        let vertical = parse_grid(".....\n..#..\n..#..\n..#..\n.....").unwrap();
+        let mut horizontal = initialize_grid(5, 5);
+        compute_next_generation(&vertical, &mut horizontal, Topology::Finite);
+
+        let diffs = grid_diff(&vertical, &horizontal).unwrap();
+
+        assert_eq!(diffs.len(), 4);
+        assert_eq!(diffs.iter().filter(|diff| diff.to).count(), 2);
+        assert_eq!(format_diff(&diffs), "- (1,2)\n+ (2,1)\n+ (2,3)\n- (3,2)\n");
+    }
+
+    #[test]
+    fn test_grid_xor_with_itself_is_dead() {
+        let grid = sample_grid();
+
+        assert_eq!(grid_xor(&grid, &grid), Ok(initialize_grid(2, 3)));
+    }
+
+    #[test]
+    fn test_grid_or_with_dead_grid_is_identity() {
+        let grid = sample_grid();
+
+        assert_eq!(grid_or(&initialize_grid(2, 3), &grid), Ok(grid));
+    }
+
+    #[test]
+    fn test_grid_and() {
+        let other = vec![
+            vec![true, true, false],
+            vec![false, true, false],
+        ];
+
+        let expected = vec![
+            vec![true, false, false],
+            vec![false, true, false],
+        ];
+        assert_eq!(grid_and(&sample_grid(), &other), Ok(expected));
+    }
+
+    #[test]
+    fn test_grid_not_twice_is_identity() {
+        let grid = sample_grid();
+
+        assert_eq!(grid_not(&grid_not(&grid)), grid);
+        assert_eq!(grid_not(&grid)[0], vec![false, true, false]);
+    }
+
+    #[test]
+    fn test_dimension_mismatch() {
+        let error = grid_or(&sample_grid(), &initialize_grid(3, 3)).unwrap_err();
+
+        assert_eq!(error, DimensionError { left: (2, 3), right: (3, 3) });
+        assert_eq!(error.to_string(), "Grid dimensions don't match: 2x3 and 3x3");
+    }
+
+    #[test]
+    fn test_bounding_box_of_scattered_cells() {
+        let mut grid = initialize_grid(8, 10);
+        for (row, col) in [(2, 7), (5, 3), (6, 8), (3, 4)] {
+            grid[row][col] = true;
+        }
+
+        assert_eq!(bounding_box(&grid), Some(BoundingBox { top: 2, left: 3, height: 5, width: 6 }));
+        assert_eq!(bounding_box(&vec![vec![false, true]]), Some(BoundingBox { top: 0, left: 1, height: 1, width: 1 }));
+        assert_eq!(bounding_box(&initialize_grid(4, 4)), None);
+    }
+
+    #[test]
+    fn test_count_objects() {
+        let mut blocks = initialize_grid(8, 8);
+        for (row, col) in [(1, 1), (1, 2), (2, 1), (2, 2), (5, 5), (5, 6), (6, 5), (6, 6)] {
+            blocks[row][col] = true;
+        }
+        assert_eq!(count_objects(&blocks, Connectivity::Eight), 2);
+        assert_eq!(count_objects(&blocks, Connectivity::Four), 2);
+
+        assert_eq!(count_objects(&vec![vec![true, true, false]], Connectivity::Eight), 1);
+        assert_eq!(count_objects(&initialize_grid(3, 3), Connectivity::Eight), 0);
+    }
+
+    #[test]
+    fn test_diagonal_cells_join_only_with_eight_connectivity() {
+        let diagonal = vec![vec![true, false], vec![false, true]];
+
+        assert_eq!(count_objects(&diagonal, Connectivity::Eight), 1);
+        assert_eq!(count_objects(&diagonal, Connectivity::Four), 2);
+    }
+
+    #[test]
+    fn test_grid_clear_and_fill() {
+        let mut grid = sample_grid();
+
+        grid_clear(&mut grid);
+        assert_eq!(count_population(&grid), 0);
+
+        grid_fill(&mut grid);
+        assert_eq!(count_population(&grid), 2 * 3);
+    }
+
+    #[test]
+    fn test_grid_fill_region() {
+        let mut grid = initialize_grid(4, 4);
+
+        assert_eq!(grid_fill_region(&mut grid, 0, 0, 1, 1, true), Ok(()));
+        assert_eq!(count_population(&grid), 4);
+        assert!(grid[1][1] && !grid[2][2]);
+
+        assert_eq!(grid_fill_region(&mut grid, 0, 0, 3, 0, false), Ok(()));
+        assert_eq!(count_population(&grid), 2);
+    }
+
+    #[test]
+    fn test_grid_fill_region_rejects_invalid_regions() {
+        let mut grid = initialize_grid(4, 4);
+
+        for (r1, c1, r2, c2) in [(2, 0, 1, 3), (0, 3, 3, 2), (0, 0, 4, 1), (0, 0, 1, 4)] {
+            assert_eq!(
+                grid_fill_region(&mut grid, r1, c1, r2, c2, true),
+                Err(RegionError { region: (r1, c1, r2, c2), dimensions: (4, 4) })
+            );
+        }
+        assert_eq!(count_population(&grid), 0);
+    }
+
+    #[test]
+    fn test_place_pattern_centered() {
+        let mut grid = initialize_grid(5, 5);
+        place_pattern_centered(&mut grid, &vec![vec![true]]);
+        assert_eq!(bounding_box(&grid), Some(BoundingBox { top: 2, left: 2, height: 1, width: 1 }));
+
+        let blinker = vec![vec![false; 3], vec![true; 3], vec![false; 3]];
+        let mut grid = initialize_grid(7, 7);
+        place_pattern_centered(&mut grid, &blinker);
+        assert_eq!(grid[3][2..5], [true; 3]);
+        assert_eq!(count_population(&grid), 3);
+    }
+
+    #[test]
+    fn test_place_pattern_centered_clamps_large_patterns() {
+        let mut pattern = initialize_grid(5, 5);
+        pattern[0][0] = true;
+        pattern[4][4] = true;
+        let mut grid = initialize_grid(3, 3);
+
+        place_pattern_centered(&mut grid, &pattern);
+
+        assert!(grid[0][0]);
+        assert_eq!(count_population(&grid), 1);
+    }
+
+    #[test]
+    fn test_dimensions_with_border() {
+        let glider = from_coords(3, 3, &[(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)]);
+        let (rows, cols) = dimensions_with_border(&glider, 5);
+        let mut grid = initialize_grid(rows, cols);
+
+        place_pattern_centered(&mut grid, &glider);
+
+        assert_eq!((rows, cols), (13, 13));
+        let bbox = bounding_box(&grid).unwrap();
+        assert_eq!((bbox.top, bbox.left), (5, 5));
+        assert_eq!((rows - bbox.top - bbox.height, cols - bbox.left - bbox.width), (5, 5));
+    }
+
+    #[test]
+    fn test_center_of_pattern() {
+        let blinker = vec![vec![false; 3], vec![true; 3], vec![false; 3]];
+        let mut scattered = initialize_grid(8, 10);
+        scattered[2][7] = true;
+        scattered[6][3] = true;
+
+        assert_eq!(center_of_pattern(&blinker), (1, 1));
+        assert_eq!(center_of_pattern(&scattered), (4, 5));
+        assert_eq!(center_of_pattern(&initialize_grid(5, 4)), (2, 1));
+    }
+
+    #[test]
+    fn test_scale_pattern() {
+        let grid = sample_grid();
+
+        let scaled = scale_pattern(&grid, 2);
+
+        assert_eq!(grid_dimensions(&scaled), (4, 6));
+        assert_eq!(scaled[0], vec![true, true, false, false, true, true]);
+        assert_eq!(scaled[1], scaled[0]);
+        assert_eq!(count_population(&scaled), 4 * count_population(&grid));
+        assert_eq!(scale_pattern(&initialize_grid(3, 2), 3), initialize_grid(9, 6));
+    }
+
+    #[test]
+    fn test_scale_down_undoes_scale_pattern() {
+        for grid in [sample_grid(), vec![vec![false, true, true, false, true]], initialize_grid(2, 2)] {
+            for factor in [1, 2, 3] {
+                assert_eq!(scale_down(&scale_pattern(&grid, factor), factor), grid);
+            }
+        }
+    }
+
+    #[test]
+    fn test_scale_down_majority_vote() {
+        let grid = vec![
+            vec![true, true, false, false, true],
+            vec![true, false, false, true, true],
+        ];
+
+        // Three of four cells alive, two of four (not a majority) and two of two in the cut off block
+        assert_eq!(scale_down(&grid, 2), vec![vec![true, false, true]]);
+    }
+}
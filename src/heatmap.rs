@@ -0,0 +1,107 @@
+use crate::game::{grid_dimensions, Grid};
+
+/// Counts how many generations each cell has been alive for.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Heatmap {
+    visit_counts: Vec<u32>,
+    cols: usize,
+    generations: u64,
+}
+
+impl Heatmap {
+    /// Creates an empty heatmap for a grid with the given dimensions.
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Heatmap { visit_counts: vec![0; rows * cols], cols, generations: 0 }
+    }
+
+    /// Adds the live cells of one generation to the counts.
+    pub fn record(&mut self, grid: &Grid) {
+        debug_assert_eq!(grid_dimensions(grid).1, self.cols);
+        for (count, &cell) in self.visit_counts.iter_mut().zip(grid.iter().flatten()) {
+            *count += cell as u32;
+        }
+        self.generations += 1;
+    }
+
+    /// Number of generations recorded so far.
+    pub fn generations(&self) -> u64 {
+        self.generations
+    }
+
+    /// Number of generations the cell at the given position has been alive for.
+    pub fn count(&self, row: usize, col: usize) -> u32 {
+        self.visit_counts[row * self.cols + col]
+    }
+
+    /// Rows of visit counts, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[u32]> {
+        self.visit_counts.chunks(self.cols.max(1))
+    }
+}
+
+/// Maps how often a cell was alive to a digit: `0` for never, up to `9` for at least 90% of the generations.
+pub fn compute_heatmap_char(count: u32, total_iters: u64) -> char {
+    if count == 0 || total_iters == 0 {
+        return '0';
+    }
+    let decile = (u64::from(count) * 10 / total_iters).clamp(1, 9);
+    char::from_digit(decile as u32, 10).expect("Decile is a single digit")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{initialize_grid, Topology, Universe};
+
+    #[test]
+    fn test_compute_heatmap_char() {
+        assert_eq!(compute_heatmap_char(0, 100), '0');
+        assert_eq!(compute_heatmap_char(1, 100), '1');
+        assert_eq!(compute_heatmap_char(50, 100), '5');
+        assert_eq!(compute_heatmap_char(89, 100), '8');
+        assert_eq!(compute_heatmap_char(90, 100), '9');
+        assert_eq!(compute_heatmap_char(100, 100), '9');
+        assert_eq!(compute_heatmap_char(0, 0), '0');
+    }
+
+    #[test]
+    fn test_block_heatmap() {
+        let mut grid = initialize_grid(4, 4);
+        for (row, col) in [(1, 1), (1, 2), (2, 1), (2, 2)] {
+            grid[row][col] = true;
+        }
+        let mut universe = Universe::new(grid, Topology::Finite);
+        let mut heatmap = Heatmap::new(4, 4);
+
+        for _ in 0..100 {
+            heatmap.record(universe.grid());
+            universe.step_once();
+        }
+
+        assert_eq!(heatmap.generations(), 100);
+        for (row, col) in [(1, 1), (1, 2), (2, 1), (2, 2)] {
+            assert_eq!(heatmap.count(row, col), 100);
+            assert_eq!(compute_heatmap_char(heatmap.count(row, col), heatmap.generations()), '9');
+        }
+        assert_eq!(heatmap.count(0, 0), 0);
+    }
+
+    #[test]
+    fn test_blinker_heatmap() {
+        let mut grid = initialize_grid(5, 5);
+        grid[2][1..4].fill(true);
+        let mut universe = Universe::new(grid, Topology::Finite);
+        let mut heatmap = Heatmap::new(5, 5);
+
+        for _ in 0..10 {
+            heatmap.record(universe.grid());
+            universe.step_once();
+        }
+
+        assert_eq!(heatmap.count(2, 2), 10);
+        assert_eq!(heatmap.count(2, 1), 5);
+        assert_eq!(heatmap.count(1, 2), 5);
+        assert_eq!(heatmap.rows().count(), 5);
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt::Write;
+
+use crate::game::{grid_dimensions, Grid};
+
+/// Renders the grid as a standalone HTML5 page drawing it on a canvas once the page has loaded.
+///
+/// Live cells are black squares of `cell_size` pixels on a white background. The live cells are
+/// embedded as a list of `[col, row]` pairs, so the page needs nothing but a browser.
+pub fn export_html(grid: &Grid, cell_size: u32, title: &str) -> String {
+    let (rows, cols) = grid_dimensions(grid);
+    let live_cells: Vec<String> = grid
+        .iter()
+        .enumerate()
+        .flat_map(|(row, cells)| {
+            cells.iter().enumerate().filter(|(_, &alive)| alive).map(move |(col, _)| format!("[{},{}]", col, row))
+        })
+        .collect();
+
+    let mut html = String::new();
+    // Writing to a String never fails
+    let _ = write!(
+        html,
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>{title}</title>
+</head>
+<body>
+<canvas id="grid" width="{width}" height="{height}"></canvas>
+<script>
+window.addEventListener("load", () => {{
+  const size = {size};
+  const cells = [{cells}];
+  const context = document.getElementById("grid").getContext("2d");
+  context.fillStyle = "white";
+  context.fillRect(0, 0, {width}, {height});
+  context.fillStyle = "black";
+  for (const [col, row] of cells) {{
+    context.fillRect(col * size, row * size, size, size);
+  }}
+}});
+</script>
+</body>
+</html>
+"#,
+        title = escape_html(title),
+        width = cols as u64 * cell_size as u64,
+        height = rows as u64 * cell_size as u64,
+        size = cell_size,
+        cells = live_cells.join(","),
+    );
+    html
+}
+
+/// Escapes the characters with a special meaning in HTML text.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_html() {
+        let grid = vec![vec![true, false, false], vec![false, false, true]];
+
+        let html = export_html(&grid, 10, "Glider <1>");
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<canvas"));
+        assert!(html.contains(r#"width="30""#));
+        assert!(html.contains("fillRect"));
+        assert!(html.contains("const cells = [[0,0],[2,1]];"));
+        assert!(html.contains("<title>Glider &lt;1&gt;</title>"));
+        assert!(html.find("<script>").unwrap() < html.find("</html>").unwrap());
+    }
+}
@@ -0,0 +1,96 @@
+//! Ready-made experiments set up on an empty grid, chosen with `--lab`.
+
+#[cfg(feature = "terminal")]
+use clap::ValueEnum;
+
+use crate::cells::parse_cells;
+use crate::game::{initialize_grid, Grid};
+use crate::grid_ops::paste_region;
+
+/// Glider heading down and to the right, one cell diagonally every 4 generations.
+const GLIDER_SE: &str = ".O.\n..O\nOOO\n";
+/// The same glider turned around, heading up and to the left.
+const GLIDER_NW: &str = "OOO\nO..\n.O.\n";
+/// Dead cells kept between each glider and the nearest edges.
+const MARGIN: usize = 1;
+/// Side of the glider's bounding box.
+const GLIDER_SIZE: usize = 3;
+/// Shortest side of a grid leaving room for two gliders and a gap between them.
+pub const MIN_COLLISION_SIZE: usize = 2 * (MARGIN + GLIDER_SIZE) + 2;
+
+/// Experiment the initial grid is set up for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "terminal", derive(ValueEnum))]
+pub enum Lab {
+    /// Two gliders on a head-on course, see [`glider_collision`].
+    Collision,
+}
+
+impl Lab {
+    /// Sets the experiment up on a grid of the given dimensions, `None` when it doesn't fit.
+    pub fn grid(self, rows: usize, cols: usize) -> Option<Grid> {
+        match self {
+            Lab::Collision => glider_collision(rows, cols),
+        }
+    }
+}
+
+/// Two gliders flying toward each other along the grid's main diagonal, which meet halfway and
+/// annihilate or leave debris depending on their phases.
+///
+/// One starts in the top left corner and the other in the opposite corner of the largest square
+/// in the grid's top left. Returns `None` when a side is shorter than [`MIN_COLLISION_SIZE`].
+pub fn glider_collision(rows: usize, cols: usize) -> Option<Grid> {
+    let side = rows.min(cols);
+    if side < MIN_COLLISION_SIZE {
+        return None;
+    }
+    let mut grid = initialize_grid(rows, cols);
+    let far = side - MARGIN - GLIDER_SIZE;
+    paste_region(&mut grid, &parse_cells(GLIDER_SE).expect("the glider is valid"), MARGIN, MARGIN);
+    paste_region(&mut grid, &parse_cells(GLIDER_NW).expect("the glider is valid"), far, far);
+    Some(grid)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{count_population, Topology, Universe};
+    use crate::grid_ops::{bounding_box, count_objects, BoundingBox, Connectivity};
+
+    #[test]
+    fn test_gliders_converge_on_30x30_grid() {
+        let grid = glider_collision(30, 30).unwrap();
+        assert_eq!(count_objects(&grid, Connectivity::Eight), 2);
+        assert_eq!(bounding_box(&grid), Some(BoundingBox { top: 1, left: 1, height: 28, width: 28 }));
+
+        let mut universe = Universe::new(grid, Topology::Finite);
+        for _ in 0..4 {
+            universe.step_once();
+        }
+
+        // Each glider moved one cell toward the other along the diagonal
+        assert_eq!(count_population(universe.grid()), 10);
+        assert_eq!(bounding_box(universe.grid()), Some(BoundingBox { top: 2, left: 2, height: 26, width: 26 }));
+    }
+
+    #[test]
+    fn test_gliders_collide() {
+        let mut universe = Universe::new(glider_collision(30, 30).unwrap(), Topology::Finite);
+        for _ in 0..120 {
+            universe.step_once();
+        }
+
+        // Passing gliders would have reached the opposite corners by now
+        let bbox = bounding_box(universe.grid());
+        assert!(bbox.is_none_or(|bbox| bbox.top > 5 && bbox.height < 20), "{:?}", bbox);
+    }
+
+    #[test]
+    fn test_collision_needs_room() {
+        assert!(glider_collision(MIN_COLLISION_SIZE, 50).is_some());
+        assert_eq!(glider_collision(50, MIN_COLLISION_SIZE - 1), None);
+        assert_eq!(Lab::Collision.grid(20, 40).map(|grid| count_population(&grid)), Some(10));
+    }
+}
@@ -0,0 +1,53 @@
+//! Conway's Game of Life simulation engine.
+//!
+//! The `terminal` feature (enabled by default) adds terminal rendering and the
+//! command-line driver. Without it only the pure simulation core is built, which
+//! makes the crate usable as a WebAssembly library.
+
+#[cfg(feature = "terminal")]
+pub mod batch;
+#[cfg(feature = "terminal")]
+pub mod bell;
+pub mod builder;
+pub mod catalog;
+pub mod cells;
+pub mod checkpoint;
+pub mod color;
+#[cfg(feature = "terminal")]
+pub mod display;
+pub mod elementary;
+pub mod env_config;
+pub mod ffi;
+pub mod game;
+pub mod grid_ops;
+pub mod game3d;
+pub mod heatmap;
+pub mod html;
+pub mod lab;
+pub mod life105;
+#[cfg(feature = "terminal")]
+pub mod logging;
+#[cfg(feature = "lua")]
+pub mod lua;
+pub mod metrics;
+pub mod packed;
+pub mod ppm;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod record;
+pub mod rle;
+pub mod rules;
+pub mod search;
+#[cfg(feature = "server")]
+pub mod server;
+pub mod svg;
+#[cfg(feature = "terminal")]
+pub mod terminal;
+pub mod tikz;
+pub mod time_travel;
+#[cfg(feature = "tui")]
+pub mod tui;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "ws")]
+pub mod ws;
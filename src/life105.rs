@@ -0,0 +1,177 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::game::{grid_dimensions, initialize_grid, Grid};
+use crate::grid_ops::paste_region;
+use crate::rle::MAX_PATTERN_CELLS;
+
+/// First line of every Life 1.05 file.
+const HEADER: &str = "#Life 1.05";
+
+/// Error returned for malformed Life 1.05 input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseLife105Error {
+    /// The input doesn't start with the `#Life 1.05` line.
+    MissingHeader,
+    /// A `#` line of an unknown kind; holds the one-based line and its text.
+    UnknownHeader { line: usize, text: String },
+    /// A `#P` line without two valid coordinates.
+    InvalidPosition { line: usize },
+    /// A character other than `*` or `.` in a cell line.
+    UnexpectedChar { line: usize, character: char },
+    /// The blocks span more than [`MAX_PATTERN_CELLS`] cells, or reach past the coordinate range.
+    TooLarge,
+}
+
+impl fmt::Display for ParseLife105Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseLife105Error::MissingHeader => write!(f, "Missing `{}` header", HEADER),
+            ParseLife105Error::UnknownHeader { line, text } => write!(f, "Unknown line `{}` on line {}", text, line),
+            ParseLife105Error::InvalidPosition { line } => write!(f, "Invalid `#P x y` position on line {}", line),
+            ParseLife105Error::UnexpectedChar { line, character } => {
+                write!(f, "Unexpected character `{}` on line {}", character, line)
+            }
+            ParseLife105Error::TooLarge => write!(f, "The blocks span more than {} cells", MAX_PATTERN_CELLS),
+        }
+    }
+}
+
+impl Error for ParseLife105Error {}
+
+/// Cells of one `#P` block, with the position of its top left corner.
+struct Block {
+    x: i64,
+    y: i64,
+    cells: Grid,
+}
+
+/// Parses a pattern in the Life 1.05 format.
+///
+/// Each `#P x y` line starts a block of `*` (alive) and `.` (dead) lines whose top left cell is at
+/// the given position; the blocks are composited into a grid just large enough to hold them all.
+/// `#D` descriptions and `#N`/`#R` rule lines are skipped. Cell lines before the first `#P` form
+/// a block at the origin. Blocks spanning more than [`MAX_PATTERN_CELLS`] cells are rejected.
+pub fn load_life105(input: &str) -> Result<Grid, ParseLife105Error> {
+    let mut lines = input.lines().enumerate().map(|(index, line)| (index + 1, line.trim()));
+    if lines.by_ref().find(|(_, line)| !line.is_empty()).is_none_or(|(_, line)| line != HEADER) {
+        return Err(ParseLife105Error::MissingHeader);
+    }
+
+    let mut blocks = vec![Block { x: 0, y: 0, cells: Vec::new() }];
+    for (number, line) in lines {
+        if let Some(position) = line.strip_prefix("#P") {
+            let mut coordinates = position.split_whitespace().map(str::parse::<i64>);
+            let (Some(Ok(x)), Some(Ok(y)), None) = (coordinates.next(), coordinates.next(), coordinates.next()) else {
+                return Err(ParseLife105Error::InvalidPosition { line: number });
+            };
+            blocks.push(Block { x, y, cells: Vec::new() });
+        } else if line.starts_with("#D") || line.starts_with("#N") || line.starts_with("#R") {
+            continue;
+        } else if line.starts_with('#') {
+            return Err(ParseLife105Error::UnknownHeader { line: number, text: line.to_string() });
+        } else if !line.is_empty() {
+            let row = line
+                .chars()
+                .map(|character| match character {
+                    '*' => Ok(true),
+                    '.' => Ok(false),
+                    character => Err(ParseLife105Error::UnexpectedChar { line: number, character }),
+                })
+                .collect::<Result<Vec<bool>, _>>()?;
+            blocks.last_mut().expect("there is always a block").cells.push(row);
+        }
+    }
+
+    blocks.retain(|block| !block.cells.is_empty());
+    let width = |block: &Block| block.cells.iter().map(Vec::len).max().unwrap_or(0) as i64;
+    let (Some(left), Some(top)) = (blocks.iter().map(|block| block.x).min(), blocks.iter().map(|block| block.y).min()) else {
+        return Ok(Vec::new());
+    };
+    let right = blocks.iter().try_fold(left, |right, block| Some(right.max(block.x.checked_add(width(block))?)));
+    let bottom = blocks.iter().try_fold(top, |bottom, block| Some(bottom.max(block.y.checked_add(block.cells.len() as i64)?)));
+    let span = |start: i64, end: Option<i64>| usize::try_from(end?.checked_sub(start)?).ok();
+    let (Some(rows), Some(cols)) = (span(top, bottom), span(left, right)) else {
+        return Err(ParseLife105Error::TooLarge);
+    };
+    if rows.checked_mul(cols).is_none_or(|cells| cells > MAX_PATTERN_CELLS) {
+        return Err(ParseLife105Error::TooLarge);
+    }
+
+    let mut grid = initialize_grid(rows, cols);
+    for block in &blocks {
+        paste_region(&mut grid, &block.cells, (block.y - top) as usize, (block.x - left) as usize);
+    }
+    Ok(grid)
+}
+
+/// Formats the grid as a Life 1.05 file with a single block centered on the origin.
+pub fn save_life105(grid: &Grid, name: &str) -> String {
+    let (rows, cols) = grid_dimensions(grid);
+    let mut output = format!("{}\n#D {}\n#N\n#P {} {}\n", HEADER, name, -(cols as i64 / 2), -(rows as i64 / 2));
+    for row in grid {
+        output.extend(row.iter().map(|&alive| if alive { '*' } else { '.' }));
+        output.push('\n');
+    }
+    output
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid_ops::live_cells;
+
+    const TWO_GLIDERS: &str = "\
+#Life 1.05
+#D Two gliders
+#N
+#P -4 -2
+.*
+..*
+***
+#P 2 1
+***
+*
+.*
+";
+
+    #[test]
+    fn test_load_life105_composites_blocks() {
+        let grid = load_life105(TWO_GLIDERS).unwrap();
+
+        assert_eq!(grid_dimensions(&grid), (6, 9));
+        assert_eq!(live_cells(&grid), vec![(0, 1), (1, 2), (2, 0), (2, 1), (2, 2), (3, 6), (3, 7), (3, 8), (4, 6), (5, 7)]);
+    }
+
+    #[test]
+    fn test_load_life105_rejects_malformed_input() {
+        assert_eq!(load_life105("*.*\n").unwrap_err(), ParseLife105Error::MissingHeader);
+        assert_eq!(
+            load_life105("#Life 1.05\n#X what\n").unwrap_err(),
+            ParseLife105Error::UnknownHeader { line: 2, text: "#X what".to_string() }
+        );
+        assert_eq!(load_life105("#Life 1.05\n#P 1\n").unwrap_err(), ParseLife105Error::InvalidPosition { line: 2 });
+        assert_eq!(
+            load_life105("#Life 1.05\n*o\n").unwrap_err(),
+            ParseLife105Error::UnexpectedChar { line: 2, character: 'o' }
+        );
+    }
+
+    #[test]
+    fn test_load_life105_rejects_oversized_patterns() {
+        assert_eq!(load_life105("#Life 1.05\n#P 9223372036854775807 0\n*\n").unwrap_err(), ParseLife105Error::TooLarge);
+        assert_eq!(load_life105("#Life 1.05\n#P -9223372036854775808 0\n*\n#P 100 0\n*\n").unwrap_err(), ParseLife105Error::TooLarge);
+        assert_eq!(load_life105("#Life 1.05\n#P 0 0\n*\n#P 100000 100000\n*\n").unwrap_err(), ParseLife105Error::TooLarge);
+    }
+
+    #[test]
+    fn test_life105_round_trip() {
+        let grid = vec![vec![false, true, false], vec![false, false, true], vec![true, true, true]];
+
+        let text = save_life105(&grid, "Glider");
+
+        assert!(text.starts_with("#Life 1.05\n#D Glider\n#N\n#P -1 -1\n.*.\n"));
+        assert_eq!(load_life105(&text).unwrap(), grid);
+    }
+}
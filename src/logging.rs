@@ -0,0 +1,105 @@
+use std::fs::File;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+use clap::ValueEnum;
+use tracing::level_filters::LevelFilter;
+use tracing::Subscriber;
+use tracing_subscriber::fmt::MakeWriter;
+
+/// Most verbose level of log events that get written.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum LogLevel {
+    Error,
+    #[default]
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// Builds a subscriber writing events up to the given level, without colors, to `writer`.
+pub fn subscriber<W>(level: LogLevel, writer: W) -> impl Subscriber + Send + Sync
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    tracing_subscriber::fmt()
+        .with_max_level(LevelFilter::from(level))
+        .with_ansi(false)
+        .with_writer(writer)
+        .finish()
+}
+
+/// Installs the global subscriber, logging to the given file or to stderr when there is none.
+pub fn init_logging(level: LogLevel, log_file: Option<&Path>) -> io::Result<()> {
+    let result = match log_file {
+        Some(path) => tracing::subscriber::set_global_default(subscriber(level, Mutex::new(File::create(path)?))),
+        None => tracing::subscriber::set_global_default(subscriber(level, io::stderr)),
+    };
+    result.map_err(io::Error::other)
+}
+
+/// Writer collecting everything written to it in a shared buffer, to check what tests logged.
+#[cfg(test)]
+#[derive(Clone, Default)]
+pub(crate) struct LogBuffer(std::sync::Arc<Mutex<Vec<u8>>>);
+
+#[cfg(test)]
+impl LogBuffer {
+    /// Everything written so far.
+    pub(crate) fn contents(&self) -> String {
+        String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+    }
+}
+
+#[cfg(test)]
+impl io::Write for LogBuffer {
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        self.0.lock().unwrap().write(data)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl<'a> MakeWriter<'a> for LogBuffer {
+    type Writer = LogBuffer;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_level_filters_info_events() {
+        let buffer = LogBuffer::default();
+
+        tracing::subscriber::with_default(subscriber(LogLevel::Error, buffer.clone()), || {
+            tracing::info!("population extinct");
+            tracing::error!("failed to save metrics");
+        });
+
+        let logged = buffer.contents();
+        assert!(!logged.contains("population extinct"));
+        assert!(logged.contains("failed to save metrics"));
+    }
+}
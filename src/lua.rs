@@ -0,0 +1,133 @@
+//! Cell update rules written in Lua, loaded with `--lua-rules`.
+//!
+//! A script defines `next_cell(alive, neighbors)`, returning whether the cell is alive in the
+//! next generation. Conway's rule, for example:
+//!
+//! ```lua
+//! function next_cell(alive, neighbors)
+//!     return neighbors == 3 or (alive and neighbors == 2)
+//! end
+//! ```
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use mlua::{Function, Lua};
+
+use crate::rules::RuleFn;
+
+/// Rule calling the `next_cell` function of a Lua script for every cell.
+pub struct LuaRules {
+    /// Interpreter the function lives in, which must outlive it.
+    _lua: Lua,
+    next_cell: Function,
+}
+
+impl LuaRules {
+    /// Runs the script and looks up its `next_cell` function.
+    ///
+    /// The function is tried on every state and neighbor count up front, so a script that fails
+    /// for some cells is rejected here rather than in the middle of a run.
+    pub fn from_source(source: &str) -> Result<Self, LuaRulesError> {
+        let lua = Lua::new();
+        lua.load(source).exec()?;
+        let next_cell: Function = lua.globals().get("next_cell").map_err(|_| LuaRulesError::MissingFunction)?;
+        for alive in [false, true] {
+            for neighbors in 0..=8u8 {
+                next_cell.call::<bool>((alive, neighbors))?;
+            }
+        }
+        Ok(LuaRules { _lua: lua, next_cell })
+    }
+
+    /// Reads the script from a file, see [`LuaRules::from_source`].
+    pub fn load(path: &Path) -> Result<Self, LuaRulesError> {
+        LuaRules::from_source(&fs::read_to_string(path)?)
+    }
+}
+
+impl RuleFn for LuaRules {
+    /// Calls `next_cell`; a cell for which the script raises an error keeps its state.
+    fn next_cell(&self, alive: bool, neighbors: u8) -> bool {
+        self.next_cell.call((alive, neighbors)).unwrap_or(alive)
+    }
+}
+
+impl fmt::Debug for LuaRules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("LuaRules").finish_non_exhaustive()
+    }
+}
+
+/// Error returned when a Lua rule script can't be loaded.
+#[derive(Debug)]
+pub enum LuaRulesError {
+    /// The script file couldn't be read.
+    Io(io::Error),
+    /// The script doesn't define a `next_cell` function.
+    MissingFunction,
+    /// The script failed to run, or `next_cell` failed for some cell.
+    Lua(mlua::Error),
+}
+
+impl fmt::Display for LuaRulesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LuaRulesError::Io(err) => write!(f, "Failed to read the Lua script: {}", err),
+            LuaRulesError::MissingFunction => write!(f, "The Lua script doesn't define `next_cell(alive, neighbors)`"),
+            LuaRulesError::Lua(err) => write!(f, "Lua error: {}", err),
+        }
+    }
+}
+
+impl Error for LuaRulesError {}
+
+impl From<io::Error> for LuaRulesError {
+    fn from(err: io::Error) -> Self {
+        LuaRulesError::Io(err)
+    }
+}
+
+impl From<mlua::Error> for LuaRulesError {
+    fn from(err: mlua::Error) -> Self {
+        LuaRulesError::Lua(err)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{compute_next_generation_with_rule_fn, compute_next_generation_with_rules, create_seeded_grid, SimulationConfig, Topology};
+    use crate::rules::Rules;
+
+    const CONWAY: &str = "function next_cell(alive, neighbors)\n    return neighbors == 3 or (alive and neighbors == 2)\nend";
+
+    #[test]
+    fn test_conway_script_matches_native_rules() {
+        let rules = LuaRules::from_source(CONWAY).unwrap();
+        let config = SimulationConfig { grid_size: 20, seed: 42, ..SimulationConfig::default() };
+        let mut scripted = create_seeded_grid(&config);
+        let mut native = scripted.clone();
+        let mut next = scripted.clone();
+
+        for generation in 1..=20 {
+            compute_next_generation_with_rule_fn(&scripted, &mut next, Topology::Toroidal, &rules);
+            std::mem::swap(&mut scripted, &mut next);
+            compute_next_generation_with_rules(&native, &mut next, Topology::Toroidal, &Rules::conway());
+            std::mem::swap(&mut native, &mut next);
+            assert_eq!(scripted, native, "generation {}", generation);
+        }
+    }
+
+    #[test]
+    fn test_rejects_broken_scripts() {
+        assert!(matches!(LuaRules::from_source("x = 1"), Err(LuaRulesError::MissingFunction)));
+        assert!(matches!(LuaRules::from_source("function next_cell("), Err(LuaRulesError::Lua(_))));
+        let failing = "function next_cell(alive, neighbors)\n    if neighbors == 8 then error('too many') end\n    return alive\nend";
+        assert!(matches!(LuaRules::from_source(failing), Err(LuaRulesError::Lua(_))));
+    }
+}
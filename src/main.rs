@@ -1,6 +1,45 @@
-use clap::Parser;
-mod game;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, BufRead, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory, FromArgMatches, Parser, ValueEnum};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rusty_game_of_life::builder::SimulationBuilder;
+#[cfg(feature = "lua")]
+use rusty_game_of_life::lua;
+#[cfg(feature = "ws")]
+use rusty_game_of_life::ws;
+use rusty_game_of_life::{batch, bell, catalog, cells, checkpoint, color, display, elementary, env_config, game, game3d, grid_ops, html, lab, life105, logging, metrics, ppm, record, rle, rules, search, terminal, tikz, time_travel};
+use tracing::warn;
 
+/// Exit status used with `--fail-on-extinction` when the population dies out.
+const EXTINCTION_EXIT_CODE: i32 = 2;
+/// Colors of live and dead cells in images written with `--export-ppm`.
+const PPM_ALIVE_RGB: [u8; 3] = [255, 255, 255];
+const PPM_DEAD_RGB: [u8; 3] = [0, 0, 0];
+/// Time each frame is shown in animations written with `--export-svg-anim`, as in the terminal.
+const SVG_FRAME_DELAY_MS: u64 = 50;
+/// Size in pixels of each cell in animations written with `--export-svg-anim`.
+const SVG_CELL_SIZE: u32 = 8;
+/// Size in pixels of each cell in pages written with `--export-html`.
+const HTML_CELL_SIZE: u32 = 8;
+/// Pause between runs with `--loop`, long enough to see how the previous run ended.
+const RESTART_PAUSE: Duration = Duration::from_secs(1);
+/// Generations each pattern of `--demo` runs for unless `--max-iterations` is given.
+const DEMO_GENERATIONS: u64 = 150;
+/// Time the title of each `--demo` pattern is shown before it starts.
+const DEMO_TITLE_PAUSE: Duration = Duration::from_secs(2);
+/// Dead cells kept on each side of a `--demo` pattern too large for the configured grid.
+const DEMO_MARGIN: usize = 4;
+/// Time between generations streamed with `--ws-port` unless `--delay-ms` is given, as in the terminal.
+#[cfg(feature = "ws")]
+const FRAME_TIME: Duration = Duration::from_millis(50);
 
 /// Command-line arguments parser
 #[derive(Parser)]
@@ -10,9 +49,412 @@ struct Args {
     #[arg(short, long, default_value_t = 30, value_parser = validate_grid_size)]
     grid_size: usize,
 
+    /// Whether the grid size was set on the command line or in the environment rather than defaulted
+    #[arg(skip)]
+    grid_size_set: bool,
+
+    /// Size the grid to fill the terminal window, following it when the window is resized
+    #[arg(long, default_value_t = false, conflicts_with = "grid_size")]
+    fit: bool,
+
     /// Detect and stop at steady state or oscilation
     #[arg(short, long, default_value_t = false)]
-    exit_steady: bool
+    exit_steady: bool,
+
+    /// Report the first steady state or oscillation once and keep running instead of stopping
+    #[arg(long, default_value_t = false, requires = "exit_steady", conflicts_with = "loop_runs")]
+    quiet_steady: bool,
+
+    /// Birth and survival rule of the 2D grid, as B3/S23 or the survival/birth form 23/3
+    #[arg(long, value_name = "RULESTRING", default_value = "B3/S23", conflicts_with_all = ["three_d", "one_d_rule"])]
+    rule: rules::Rules,
+
+    /// Lua script whose `next_cell(alive, neighbors)` function decides every cell's next state, replacing --rule
+    #[cfg(feature = "lua")]
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["three_d", "one_d_rule", "record", "replay", "restore", "checkpoint_interval", "checkpoint_every"])]
+    lua_rules: Option<PathBuf>,
+
+    /// Grid topology deciding which edges wrap around
+    #[arg(long, value_enum, default_value_t = game::Topology::Finite, conflicts_with_all = ["wrap_horizontal", "wrap_vertical"])]
+    topology: game::Topology,
+
+    /// Wrap the grid horizontally (cells leaving the right edge re-enter on the left)
+    #[arg(long, default_value_t = false)]
+    wrap_horizontal: bool,
+
+    /// Wrap the grid vertically (cells leaving the bottom edge re-enter at the top)
+    #[arg(long, default_value_t = false)]
+    wrap_vertical: bool,
+
+    /// Seed for the random initial grid (random when omitted)
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// Start from a named pattern of the library or the built-in catalog, or else from an RLE, .cells or, with a .lif extension, Life 1.05 file, centered in the grid
+    #[arg(long, value_name = "NAME|PATH", conflicts_with_all = ["replay", "three_d", "one_d_rule"])]
+    pattern: Option<PathBuf>,
+
+    /// JSON file of `{"name": ..., "rle": ...}` objects naming patterns for --pattern, searched before the built-in ones
+    #[arg(long, value_name = "PATH", requires = "pattern")]
+    pattern_library: Option<PathBuf>,
+
+    /// Seed each cell alive with a probability given by the darkness of a grayscale PGM image scaled to the grid
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["pattern", "replay", "restore", "search", "three_d", "one_d_rule", "density", "mirror", "pipe"])]
+    seed_image: Option<PathBuf>,
+
+    /// Start from a methuselah centered in the grid, a small pattern evolving for hundreds to thousands of generations: R-pentomino, acorn or diehard
+    #[arg(long, value_name = "NAME", value_parser = parse_methuselah, conflicts_with_all = ["pattern", "seed_image", "replay", "restore", "search", "three_d", "one_d_rule", "density", "mirror", "pipe", "loop_runs"])]
+    seed_pattern: Option<catalog::DemoPattern>,
+
+    /// Start from a ready-made experiment: collision puts two gliders on a head-on course along the grid's diagonal
+    #[arg(long, value_enum, value_name = "PRESET", conflicts_with_all = ["pattern", "seed_image", "seed_pattern", "replay", "restore", "search", "batch", "three_d", "one_d_rule", "density", "mirror", "pipe", "loop_runs"])]
+    lab: Option<lab::Lab>,
+
+    /// Place the pattern's top left corner at column X and row Y instead of centering it
+    #[arg(long, value_name = "X,Y", value_parser = parse_offset, requires = "pattern")]
+    pattern_offset: Option<(usize, usize)>,
+
+    /// Enlarge the pattern so every cell becomes an N×N block (1-10)
+    #[arg(long, value_name = "N", default_value_t = 1, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..=10), requires = "pattern")]
+    scale: usize,
+
+    /// Keep at least N dead cells between the pattern and the grid's edges, see --border-policy
+    #[arg(long, value_name = "N", conflicts_with = "pattern_offset")]
+    border_cells: Option<usize>,
+
+    /// Whether a grid too small for --border-cells grows to fit or the run fails
+    #[arg(long, value_enum, value_name = "POLICY", default_value_t = grid_ops::BorderPolicy::Grow, requires = "border_cells")]
+    border_policy: grid_ops::BorderPolicy,
+
+    /// Print the name and comments of the loaded pattern
+    #[arg(short, long, default_value_t = false)]
+    verbose: bool,
+
+    /// Record the initial state and settings of the run to a JSON file
+    #[arg(long, value_name = "PATH")]
+    record: Option<PathBuf>,
+
+    /// Replay a run previously saved with --record
+    #[arg(long, value_name = "PATH")]
+    replay: Option<PathBuf>,
+
+    /// Step through the run one generation at a time from stdin: `[` goes back, `]` or Enter forward, `c` clears, `f` fills, `q` quits
+    #[arg(long, default_value_t = false, conflicts_with_all = ["three_d", "one_d_rule", "headless", "heatmap", "count_only", "pipe", "export_ppm", "export_html", "export_tikz", "count_objects", "show_metrics", "metrics_json"])]
+    time_travel: bool,
+
+    /// Number of past generations kept for stepping back with --time-travel
+    #[arg(long, value_name = "N", default_value_t = time_travel::DEFAULT_HISTORY_SIZE, requires = "time_travel")]
+    history_size: usize,
+
+    /// Save the grid to checkpoint.bin every N generations
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..), conflicts_with_all = ["three_d", "one_d_rule", "count_only", "pipe"])]
+    checkpoint_interval: Option<u64>,
+
+    /// Save the grid to a separate checkpoint_<N>.bin file every N generations
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..), conflicts_with_all = ["three_d", "one_d_rule", "count_only", "pipe"])]
+    checkpoint_every: Option<u64>,
+
+    /// Delete all but the newest N checkpoints saved with --checkpoint-every
+    #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..), requires = "checkpoint_every")]
+    keep_checkpoints: Option<usize>,
+
+    /// Continue a run from a checkpoint saved with --checkpoint-interval or --checkpoint-every
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["replay", "record", "pattern", "three_d", "one_d_rule", "count_only", "pipe"])]
+    restore: Option<PathBuf>,
+
+    /// Print a summary of run statistics when the simulation ends
+    #[arg(long, default_value_t = false)]
+    show_metrics: bool,
+
+    /// Save the final grid as a PPM image
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["three_d", "one_d_rule", "count_only", "pipe"])]
+    export_ppm: Option<PathBuf>,
+
+    /// Save the final grid as a standalone HTML page drawing it on a canvas
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["three_d", "one_d_rule", "count_only", "pipe", "search"])]
+    export_html: Option<PathBuf>,
+
+    /// Save the final grid as a standalone LaTeX document with a TikZ picture
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["three_d", "one_d_rule", "count_only", "pipe", "search"])]
+    export_tikz: Option<PathBuf>,
+
+    /// Size in centimeters of each cell in the TikZ picture
+    #[arg(long, value_name = "CM", default_value_t = 0.3, requires = "export_tikz", value_parser = validate_tikz_cell_size)]
+    tikz_cell_size: f64,
+
+    /// Save every generation after the warmup as a looping SVG animation, written as the run goes
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["three_d", "one_d_rule", "count_only", "pipe", "fit", "time_travel", "search"])]
+    export_svg_anim: Option<PathBuf>,
+
+    /// Write the density and 2x2 block entropy of every generation as CSV while the simulation runs, to see when the pattern freezes
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["three_d", "one_d_rule", "count_only", "pipe", "time_travel", "search", "loop_runs", "demo"])]
+    measure_entropy: Option<PathBuf>,
+
+    /// Size in pixels of each cell in the PPM image
+    #[arg(long, value_name = "PX", default_value_t = 8, requires = "export_ppm", value_parser = clap::value_parser!(u32).range(1..=64))]
+    ppm_cell_size: u32,
+
+    /// Save run statistics as JSON to the given file
+    #[arg(long, value_name = "PATH")]
+    metrics_json: Option<PathBuf>,
+
+    /// Compute this many generations without rendering before starting the animation
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    warmup: u64,
+
+    /// Time each generation is shown in milliseconds (50 unless set)
+    #[arg(long, value_name = "MS")]
+    delay_ms: Option<u64>,
+
+    /// Show the initial generation for MS milliseconds before the animation starts
+    #[arg(long, value_name = "MS")]
+    hold_first: Option<u64>,
+
+    /// Ring the terminal bell on these events: extinction, steady-state or population:N when the population crosses N
+    #[arg(long, value_name = "EVENT", value_delimiter = ',', conflicts_with_all = ["headless", "count_only", "pipe", "threaded", "three_d", "one_d_rule"])]
+    bell_on: Vec<bell::BellEvent>,
+
+    /// Run the 3D variant of the game on a cubic grid
+    #[arg(long = "3d", default_value_t = false, conflicts_with_all = ["record", "replay", "warmup"])]
+    three_d: bool,
+
+    /// Number of layers of the 3D grid
+    #[arg(long, default_value_t = 10, requires = "three_d", value_parser = validate_grid_size)]
+    depth: usize,
+
+    /// Run a 1D elementary cellular automaton with the given Wolfram rule (0-255)
+    #[arg(long = "1d-rule", value_name = "RULE", conflicts_with_all = ["three_d", "record", "replay", "warmup"])]
+    one_d_rule: Option<u8>,
+
+    /// Probability of each cell being alive in the initial state (0.0-1.0), applied before --invert
+    #[arg(long, value_parser = validate_density)]
+    density: Option<f64>,
+
+    /// Flip every cell of the initial grid, whether seeded randomly, from an image or from a pattern
+    #[arg(long, default_value_t = false)]
+    invert: bool,
+
+    /// Seed one half or quadrant randomly and reflect it to get a symmetric initial grid
+    #[arg(long, value_enum, value_name = "MODE")]
+    mirror: Option<game::Mirror>,
+
+    /// Where the random seed places live cells: random, symmetric (4-fold), edge:WIDTH or dense-center
+    #[arg(long, value_name = "MODE", default_value = "random", conflicts_with_all = ["pattern", "seed_image", "replay", "restore"])]
+    seed_mode: game::SeedMode,
+
+    /// Step the grid using packed 64-bit rows (finite topology, at most 64 columns)
+    #[arg(long, default_value_t = false)]
+    packed: bool,
+
+    /// Only recompute cells next to the ones that changed in the previous generation
+    #[arg(long, default_value_t = false)]
+    active_cells: bool,
+
+    /// Keep each cell's neighbor count between generations, updating it only around cells that changed
+    #[arg(long, default_value_t = false, conflicts_with = "active_cells")]
+    incremental_counts: bool,
+
+    /// Kill the cells of the outermost rows and columns after every generation, a fixed dead boundary
+    #[arg(long, default_value_t = false, conflicts_with_all = ["three_d", "one_d_rule"])]
+    dead_border: bool,
+
+    /// Number of threads computing each generation; a seed gives the same run for any thread count
+    #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+    threads: Option<usize>,
+
+    /// Compute generations and draw them on separate threads, so slow drawing doesn't hold up the computation
+    #[arg(long, default_value_t = false, conflicts_with_all = ["loop_runs", "demo", "time_travel", "headless", "heatmap", "show_counts", "compare_lag", "compare", "debug_diff", "fit", "start_paused", "checkpoint_interval", "checkpoint_every", "count_only", "pipe", "search", "three_d", "one_d_rule"])]
+    threaded: bool,
+
+    /// Start over with the next seed whenever the simulation ends, until interrupted; implies --exit-steady
+    #[arg(long = "loop", default_value_t = false, conflicts_with_all = ["pattern", "replay", "restore", "seed_image", "record", "search", "time_travel", "headless", "count_only", "pipe", "export_svg_anim", "three_d", "one_d_rule"])]
+    loop_runs: bool,
+
+    /// Show the built-in patterns one after another: glider, lightweight spaceship, pulsar, pentadecathlon and Gosper glider gun
+    #[arg(long, default_value_t = false, conflicts_with_all = ["pattern", "replay", "restore", "seed_image", "record", "search", "time_travel", "loop_runs", "headless", "count_only", "dry_run", "pipe", "export_svg_anim", "export_ppm", "export_html", "export_tikz", "three_d", "one_d_rule"])]
+    demo: bool,
+
+    /// Remember every grid state for steady state detection, catching cycles of any period; each
+    /// generation keeps a copy of the grid in memory, so long runs on large grids need a lot of it
+    #[arg(long, default_value_t = false)]
+    full_history: bool,
+
+    /// Stop once the population barely changes over this many generations (at least 2)
+    #[arg(long, value_name = "WINDOW", value_parser = validate_stabilize_window)]
+    stabilize: Option<usize>,
+
+    /// Stop as soon as a generation has no births and no deaths, i.e. the grid became a still life
+    #[arg(long, default_value_t = false, conflicts_with_all = ["three_d", "one_d_rule", "pipe"])]
+    terminate_on_quiescence: bool,
+
+    /// Stop after this many generations
+    #[arg(long, value_name = "N")]
+    max_iterations: Option<u64>,
+
+    /// Stop after this many seconds of wall-clock time, whatever generation the run has reached
+    #[arg(long, value_name = "SECONDS", value_parser = parse_runtime, conflicts_with_all = ["three_d", "one_d_rule", "pipe"])]
+    max_runtime: Option<Duration>,
+
+    /// Run at full speed without any rendering and print the run statistics as JSON
+    #[arg(long, default_value_t = false, conflicts_with_all = ["three_d", "one_d_rule", "count_only", "show_metrics"])]
+    headless: bool,
+
+    /// Read a grid in .cells format from stdin, write the next generation to stdout and exit
+    #[arg(long, default_value_t = false, conflicts_with_all = ["three_d", "one_d_rule", "replay", "record", "pattern", "headless", "count_only"])]
+    pipe: bool,
+
+    /// Validate the settings and input files, print the planned run and exit without simulating
+    #[arg(long, default_value_t = false, conflicts_with = "pipe")]
+    dry_run: bool,
+
+    /// Run without rendering until the simulation ends and print a one-line summary
+    #[arg(long, default_value_t = false, conflicts_with_all = ["three_d", "one_d_rule", "warmup"])]
+    count_only: bool,
+
+    /// Run N random soups headless and tally their outcomes; --seed sets the master seed the soup seeds derive from
+    #[arg(long, value_name = "N", conflicts_with_all = ["three_d", "one_d_rule", "replay", "restore", "record", "pattern", "pipe", "count_only", "headless", "time_travel", "heatmap", "export_ppm"])]
+    search: Option<usize>,
+
+    /// Run N simulations headless, seeded with --seed plus 0 to N-1, and print the spread of their iterations and peak populations and their extinction rate
+    #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..), conflicts_with_all = ["three_d", "one_d_rule", "replay", "restore", "record", "pattern", "pipe", "count_only", "headless", "search", "time_travel", "heatmap", "export_ppm"])]
+    batch: Option<usize>,
+
+    /// Spread the runs of --batch over all CPU cores
+    #[arg(long, default_value_t = false, requires = "batch")]
+    batch_parallel: bool,
+
+    /// Serve a REST API for creating and stepping simulations instead of running one
+    #[cfg(feature = "server")]
+    #[arg(long, default_value_t = false, conflicts_with_all = ["three_d", "one_d_rule", "replay", "restore", "record", "pattern", "pipe", "count_only", "headless", "search", "dry_run"])]
+    server: bool,
+
+    /// Port the REST API listens on, on the loopback interface
+    #[cfg(feature = "server")]
+    #[arg(long, value_name = "PORT", default_value_t = 3000, requires = "server")]
+    port: u16,
+
+    /// Stream every generation as JSON to WebSocket clients on the given loopback port instead of drawing it
+    #[cfg(feature = "ws")]
+    #[arg(long, value_name = "PORT", conflicts_with_all = ["three_d", "one_d_rule", "pipe", "count_only", "headless", "search", "time_travel", "demo", "loop_runs", "threaded", "bell_on", "checkpoint_interval", "checkpoint_every"])]
+    ws_port: Option<u16>,
+
+    /// Exit with status 2 when the population goes extinct
+    #[arg(long, default_value_t = false)]
+    fail_on_extinction: bool,
+
+    /// Show the Shannon entropy of the grid in the status line
+    #[arg(long, default_value_t = false)]
+    show_entropy: bool,
+
+    /// Show the autocorrelation of the population with itself LAG generations earlier, over the latest 16 x LAG generations, below the status line; near 1 when it oscillates with that period
+    #[arg(long, value_name = "LAG", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..), conflicts_with_all = ["headless", "heatmap", "count_only", "pipe", "threaded", "three_d", "one_d_rule"])]
+    show_autocorrelation: Option<usize>,
+
+    /// Show the cells born and died since the previous generation below the status line
+    #[arg(long, default_value_t = false, conflicts_with_all = ["headless", "heatmap", "count_only", "pipe", "threaded", "three_d", "one_d_rule"])]
+    show_births: bool,
+
+    /// Glyphs suited to the terminal background: `dark` draws live cells as blocks on blanks, `light` the reverse
+    #[arg(long, value_enum)]
+    theme: Option<display::Theme>,
+
+    /// Character drawn for live cells, overriding the theme
+    #[arg(long, value_name = "CHAR")]
+    alive_char: Option<char>,
+
+    /// Character drawn for dead cells, overriding the theme
+    #[arg(long, value_name = "CHAR")]
+    dead_char: Option<char>,
+
+    /// Swap the glyphs of live and dead cells on screen without changing the grid
+    #[arg(long, default_value_t = false)]
+    invert_display: bool,
+
+    /// Draw the grid and a statistics panel full screen with ratatui instead of plain text
+    #[cfg(feature = "tui")]
+    #[arg(long, default_value_t = false, conflicts_with_all = ["headless", "heatmap", "show_counts", "compare_lag", "compare", "ascii_border", "debug_diff", "start_paused", "time_travel", "demo", "count_only", "pipe", "three_d", "one_d_rule", "threaded", "show_autocorrelation", "show_births"])]
+    tui: bool,
+
+    /// Scroll the TUI by N cells with each arrow key press
+    #[cfg(feature = "tui")]
+    #[arg(long, value_name = "N", default_value_t = 1, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..), requires = "tui")]
+    scroll_step: usize,
+
+    /// Show the width, height and top left corner of the live cells' bounding box in the status line
+    #[arg(long, default_value_t = false)]
+    show_bbox: bool,
+
+    /// Track the population of the rectangle from row R1, column C1 to row R2, column C2 next to the whole grid's
+    #[arg(long, value_name = "R1,C1,R2,C2", value_parser = parse_region_arg, conflicts_with_all = ["fit", "three_d", "one_d_rule", "pipe"])]
+    region: Option<(usize, usize, usize, usize)>,
+
+    /// Record whether the cell at column X of row Y is alive and its number of live neighbors every generation, printing the timeline on exit
+    #[arg(long, value_name = "X,Y", value_parser = parse_offset, conflicts_with_all = ["fit", "three_d", "one_d_rule", "pipe"])]
+    trace: Option<(usize, usize)>,
+
+    /// Count the objects, groups of connected live cells, left on the final grid and print "Final objects: K"
+    #[arg(long, default_value_t = false, conflicts_with_all = ["three_d", "one_d_rule", "pipe", "count_only", "search"])]
+    count_objects: bool,
+
+    /// Whether live cells touching only at a corner belong to the same object for --count-objects
+    #[arg(long, value_name = "4|8", default_value = "8", requires = "count_objects")]
+    object_connectivity: grid_ops::Connectivity,
+
+    /// Show each cell's number of live neighbors (0-8) instead of the cell, highlighting live cells
+    #[arg(long, default_value_t = false, conflicts_with_all = ["headless", "heatmap", "truecolor", "count_only", "three_d", "one_d_rule"])]
+    show_counts: bool,
+
+    /// Draw a box-drawing frame around the grid
+    #[arg(long, default_value_t = false, conflicts_with_all = ["headless", "heatmap", "show_counts", "compare_lag", "compare", "fit", "count_only", "three_d", "one_d_rule"])]
+    ascii_border: bool,
+
+    /// Show the first generation and wait for Enter before animating
+    #[arg(long, default_value_t = false, conflicts_with_all = ["headless", "time_travel", "count_only", "pipe", "three_d", "one_d_rule"])]
+    start_paused: bool,
+
+    /// List the cells born (`+ (row,col)`) and died (`- (row,col)`) below the grid
+    #[arg(long, default_value_t = false, conflicts_with_all = ["headless", "heatmap", "time_travel", "count_only", "pipe", "three_d", "one_d_rule"])]
+    debug_diff: bool,
+
+    /// Number of generations listed with --debug-diff
+    #[arg(long, value_name = "N", default_value_t = 3, requires = "debug_diff")]
+    debug_diff_count: u64,
+
+    /// Show the grid from N generations ago next to the current one
+    #[arg(long, value_name = "N", value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..), conflicts_with_all = ["headless", "heatmap", "show_counts", "truecolor", "fit", "time_travel", "count_only", "three_d", "one_d_rule"])]
+    compare_lag: Option<usize>,
+
+    /// Evolve the same initial grid under a second rule too, shown next to the main one with both populations
+    #[arg(long, visible_alias = "compare-rules", value_name = "RULESTRING", conflicts_with_all = ["headless", "heatmap", "show_counts", "compare_lag", "truecolor", "fit", "time_travel", "count_only", "pipe", "three_d", "one_d_rule"])]
+    compare: Option<rules::Rules>,
+
+    /// Color live cells by age with a 24-bit gradient from green (newborn) to red (oldest); implies colors
+    #[arg(long, default_value_t = false, conflicts_with = "color")]
+    truecolor: bool,
+
+    /// Color live cells by age with a named 256-color palette: fire, ocean or grayscale; implies colors
+    #[arg(long, value_name = "NAME", value_parser = color::palettes::palette, conflicts_with_all = ["color", "truecolor", "headless", "heatmap", "show_counts", "count_only", "three_d", "one_d_rule"])]
+    palette: Option<color::palettes::Palette>,
+
+    /// Show how often each cell has been alive, as digits 0-9, instead of the grid itself
+    #[arg(long, default_value_t = false, conflicts_with_all = ["headless", "count_only", "three_d", "one_d_rule"])]
+    heatmap: bool,
+
+    /// Redraw the heatmap every N generations instead of only at the end
+    #[arg(long, value_name = "N", requires = "heatmap", value_parser = clap::value_parser!(u64).range(1..))]
+    heatmap_interval: Option<u64>,
+
+    /// When to draw live cells in color; `auto` honors NO_COLOR, FORCE_COLOR and the terminal type
+    #[arg(long, value_enum, default_value_t = terminal::ColorChoice::Auto)]
+    color: terminal::ColorChoice,
+
+    /// Most verbose level of log messages to write
+    #[arg(long, value_enum, default_value_t = logging::LogLevel::Warn)]
+    log_level: logging::LogLevel,
+
+    /// Write log messages to the given file instead of stderr
+    #[arg(long, value_name = "PATH")]
+    log_file: Option<PathBuf>,
 }
 
 fn validate_grid_size(value: &str) -> Result<usize, String> {
@@ -27,8 +469,822 @@ fn validate_grid_size(value: &str) -> Result<usize, String> {
     }
 }
 
+fn validate_density(value: &str) -> Result<f64, String> {
+    let density: f64 = value
+        .parse()
+        .map_err(|_| format!("`{}` isn't a valid number", value))?;
+
+    if (0.0..=1.0).contains(&density) {
+        Ok(density)
+    } else {
+        Err(format!("Density must be between 0.0 and 1.0, but got {}", density))
+    }
+}
+
+fn parse_offset(value: &str) -> Result<(usize, usize), String> {
+    let invalid = || format!("`{}` isn't a valid offset, expected two non-negative numbers as X,Y", value);
+    let (x, y) = value.split_once(',').ok_or_else(invalid)?;
+    let x = x.trim().parse().map_err(|_| invalid())?;
+    let y = y.trim().parse().map_err(|_| invalid())?;
+    Ok((x, y))
+}
+
+fn parse_runtime(value: &str) -> Result<Duration, String> {
+    let seconds: f64 = value
+        .parse()
+        .map_err(|_| format!("`{}` isn't a valid number", value))?;
+
+    Duration::try_from_secs_f64(seconds).map_err(|_| format!("Runtime must be a non-negative number of seconds, but got {}", seconds))
+}
+
+fn parse_methuselah(value: &str) -> Result<catalog::DemoPattern, String> {
+    catalog::methuselah(value).ok_or_else(|| {
+        let names: Vec<&str> = catalog::METHUSELAHS.iter().map(|pattern| pattern.name).collect();
+        format!("`{}` isn't a known methuselah, expected one of {}", value, names.join(", "))
+    })
+}
+
+fn parse_region_arg(value: &str) -> Result<(usize, usize, usize, usize), String> {
+    parse_region(value)
+        .ok_or_else(|| format!("`{}` isn't a valid region, expected four non-negative numbers as R1,C1,R2,C2", value))
+}
+
+fn validate_tikz_cell_size(value: &str) -> Result<f64, String> {
+    let size: f64 = value
+        .parse()
+        .map_err(|_| format!("`{}` isn't a valid number", value))?;
+
+    if size > 0.0 && size.is_finite() {
+        Ok(size)
+    } else {
+        Err(format!("Cell size must be a positive number of centimeters, but got {}", size))
+    }
+}
+
+fn validate_stabilize_window(value: &str) -> Result<usize, String> {
+    let window: usize = value
+        .parse()
+        .map_err(|_| format!("`{}` isn't a valid number", value))?;
+
+    if window >= 2 {
+        Ok(window)
+    } else {
+        Err(format!("Stabilization window must be at least 2, but got {}", window))
+    }
+}
+
 /// Main simulation loop.
 fn main() {    
-    let args: Args = Args::parse(); 
-    game::run_game_of_life_simulation(args.grid_size, args.exit_steady);
+    let matches = Args::command().get_matches();
+    let args = Args::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    let result = env_config::parse_env_config()
+        .map_err(io::Error::other)
+        .and_then(|env| run(merge_env_config(args, &matches, env)));
+    if let Err(err) = result {
+        eprintln!("Error: {}", err);
+        std::process::exit(1);
+    }
+}
+
+/// Fills in the settings not given on the command line from the `RGOL_*` environment variables.
+fn merge_env_config(mut args: Args, matches: &ArgMatches, env: env_config::PartialSimulationConfig) -> Args {
+    let from_command_line = |id| matches.value_source(id) == Some(ValueSource::CommandLine);
+    if let Some(grid_size) = env.grid_size.filter(|_| !from_command_line("grid_size")) {
+        args.grid_size = grid_size;
+    }
+    args.grid_size_set = from_command_line("grid_size") || env.grid_size.is_some();
+    if let Some(rule) = env.rule.filter(|_| !from_command_line("rule")) {
+        args.rule = rule;
+    }
+    if let Some(topology) = env.topology.filter(|_| !from_command_line("topology")) {
+        args.topology = topology;
+    }
+    args.delay_ms = args.delay_ms.or(env.delay_ms);
+    args.seed = args.seed.or(env.seed);
+    args.density = args.density.or(env.density);
+    args.max_iterations = args.max_iterations.or(env.max_iterations);
+    args
+}
+
+/// Prepares the initial grid (recording or replaying it if requested) and runs the simulation.
+fn run(args: Args) -> io::Result<()> {
+    if args.dry_run {
+        let outputs = [
+            &args.record,
+            &args.metrics_json,
+            &args.log_file,
+            &args.export_ppm,
+            &args.export_svg_anim,
+            &args.export_tikz,
+            &args.export_html,
+        ];
+        for path in outputs.into_iter().flatten() {
+            ensure_output_path(path)?;
+        }
+    }
+    // A dry run must not create any files, the log file included
+    let log_file = args.log_file.as_deref().filter(|_| !args.dry_run);
+    logging::init_logging(args.log_level, log_file)?;
+
+    if args.pipe {
+        let resize = args.grid_size_set;
+        return run_pipe_mode(&config_from_args(&args)?, resize).map_err(io::Error::other);
+    }
+
+    #[cfg(feature = "server")]
+    if args.server {
+        return tokio::runtime::Runtime::new()?.block_on(rusty_game_of_life::server::serve(args.port));
+    }
+
+    let interrupted = Arc::new(AtomicBool::new(false));
+    let handler_flag = Arc::clone(&interrupted);
+    ctrlc::set_handler(move || handler_flag.store(true, Ordering::SeqCst))
+        .map_err(io::Error::other)?;
+
+    if let Some(rule) = args.one_d_rule {
+        let config = config_from_args(&args)?;
+        if args.dry_run {
+            print_plan(&args, &config, &format!("1D, rule {}", rule), None);
+            return Ok(());
+        }
+        let metrics = elementary::run_1d_simulation(&config, rule, &interrupted);
+        return report_metrics(&args, &metrics);
+    }
+
+    if args.three_d {
+        let config = config_from_args(&args)?;
+        if args.dry_run {
+            print_plan(&args, &config, &format!("3D, depth {}", args.depth), None);
+            return Ok(());
+        }
+        let metrics = game3d::run_3d_simulation(&config, args.depth, &interrupted);
+        return report_metrics(&args, &metrics);
+    }
+
+    if let Some(count) = args.search {
+        let config = config_from_args(&args)?;
+        config.validate_seeding().map_err(io::Error::other)?;
+        if args.dry_run {
+            print_plan(&args, &config, &format!("soup search, {} soups", count), None);
+            return Ok(());
+        }
+        print!("{}", search::soup_search(&config, count, config.seed));
+        return Ok(());
+    }
+    if let Some(count) = args.batch {
+        let config = config_from_args(&args)?;
+        config.validate_seeding().map_err(io::Error::other)?;
+        if args.dry_run {
+            print_plan(&args, &config, &format!("batch of {} runs", count), None);
+            return Ok(());
+        }
+        let runs = batch::run_batch(config, count, args.batch_parallel);
+        if let Some(summary) = batch::BatchSummary::new(&runs) {
+            println!("{}", summary);
+        }
+        return Ok(());
+    }
+
+    let (mut config, initial, generation) = match (&args.restore, &args.replay) {
+        (Some(path), _) => {
+            let state = checkpoint::load_checkpoint(path).map_err(io::Error::other)?;
+            (state.config, state.grid, state.iteration)
+        }
+        (None, Some(path)) => {
+            let replayer = record::SimulationReplayer::load(path)?;
+            (replayer.config, replayer.initial, 0)
+        }
+        (None, None) => {
+            let mut config = config_from_args(&args)?;
+            let border = args.border_cells.map(|cells| (cells, args.border_policy));
+            let library = match &args.pattern_library {
+                Some(path) => {
+                    Some(catalog::load_pattern_library(path).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?)
+                }
+                None => None,
+            };
+            let loaded = match (&args.pattern, &args.seed_image) {
+                (Some(path), _) => Some(load_pattern(
+                    path,
+                    library.as_ref(),
+                    &mut config,
+                    args.pattern_offset,
+                    args.scale,
+                    border,
+                    args.verbose || args.dry_run,
+                )?),
+                (None, Some(path)) => Some(seed_from_image(path, &config)?),
+                (None, None) => match args.seed_pattern {
+                    Some(pattern) => {
+                        let cells = pattern.grid();
+                        if let Some((cells_around, policy)) = border {
+                            ensure_border(&mut config, &cells, cells_around, policy)?;
+                        }
+                        let (rows, cols) = config.dimensions();
+                        let mut grid = game::initialize_grid(rows, cols);
+                        grid_ops::place_pattern_centered(&mut grid, &cells);
+                        Some(grid)
+                    }
+                    None => match args.lab {
+                        Some(preset) => {
+                            let (rows, cols) = config.dimensions();
+                            let message = format!("The grid needs at least {0}x{0} cells for the collision lab", lab::MIN_COLLISION_SIZE);
+                            Some(preset.grid(rows, cols).ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, message))?)
+                        }
+                        None => None,
+                    },
+                },
+            };
+            let initial = match loaded {
+                Some(grid) if config.invert => game::grid_invert(&grid),
+                Some(grid) => grid,
+                // Random seeds are inverted while seeding
+                None => {
+                    config.validate_seeding().map_err(io::Error::other)?;
+                    game::create_seeded_grid(&config)
+                }
+            };
+            (config, initial, 0)
+        }
+    };
+    if args.checkpoint_interval.is_some() {
+        config.checkpoint_interval = args.checkpoint_interval;
+    }
+    if args.checkpoint_every.is_some() {
+        config.checkpoint_every = args.checkpoint_every;
+        config.keep_checkpoints = args.keep_checkpoints;
+    }
+
+    config.validate().map_err(io::Error::other)?;
+
+    if args.dry_run {
+        if !args.headless && !args.count_only {
+            if let Some(size) = terminal::query_terminal_size() {
+                terminal::ensure_grid_fits(displayed_dimensions(&args, &config), size).map_err(io::Error::other)?;
+            }
+        }
+        print_plan(&args, &config, "2D", Some(&initial));
+        return Ok(());
+    }
+
+    if let Some(path) = &args.record {
+        record::SimulationRecorder::new(&config, &initial).save(path)?;
+    }
+
+    if args.count_only {
+        println!("{}", game::summarize_run(&config, initial));
+        return Ok(());
+    }
+
+    #[cfg(feature = "ws")]
+    if let Some(port) = args.ws_port {
+        let frame_time = args.delay_ms.map(Duration::from_millis).unwrap_or(FRAME_TIME);
+        let mut recording = start_recording(&args, &config)?;
+        let (metrics, last) = stream_generations(port, &config, initial, generation, frame_time, &interrupted, &mut recording)?;
+        return finish_run(&args, recording, metrics, last);
+    }
+
+    if !args.headless {
+        if let Some(size) = terminal::query_terminal_size() {
+            terminal::ensure_grid_fits(displayed_dimensions(&args, &config), size).map_err(io::Error::other)?;
+        }
+    }
+
+    let display = display::DisplayOptions {
+        show_entropy: args.show_entropy,
+        autocorrelation_lag: args.show_autocorrelation,
+        show_births: args.show_births,
+        headless: args.headless,
+        fit: args.fit,
+        color: if args.truecolor {
+            terminal::ColorMode::TrueColor
+        } else if args.palette.is_some() {
+            terminal::ColorMode::Ansi256
+        } else {
+            args.color.resolve(terminal::detect_color_support())
+        },
+        color_by_age: args.truecolor || args.palette.is_some(),
+        heatmap: args.heatmap,
+        heatmap_interval: args.heatmap_interval,
+        show_counts: args.show_counts,
+        compare_lag: args.compare_lag,
+        compare_rule: args.compare,
+        invert_display: args.invert_display,
+        border: args.ascii_border,
+        show_bbox: args.show_bbox,
+        palette: args.palette,
+        glyphs: display::Glyphs::resolve(args.theme, args.alive_char, args.dead_char),
+        start_paused: args.start_paused,
+        delay: args.delay_ms.map(Duration::from_millis),
+        hold_first: args.hold_first.map(Duration::from_millis),
+        bell_on: args.bell_on.clone(),
+        debug_diff: args.debug_diff.then_some(args.debug_diff_count),
+        region: args.region,
+        #[cfg(feature = "tui")]
+        tui: args.tui,
+        #[cfg(feature = "tui")]
+        scroll_step: args.scroll_step,
+    };
+    if args.demo {
+        return run_demo(&config, &display, &interrupted);
+    }
+    if args.time_travel {
+        return run_time_travel(&config, &display, initial, generation, args.history_size);
+    }
+    let mut recording = start_recording(&args, &config)?;
+    let (metrics, last) = if args.loop_runs {
+        run_looped(config.clone(), &display, initial, &interrupted)
+    } else if args.threaded {
+        game::run_threaded_simulation(&config, &display, initial, generation, &interrupted, &mut recording)
+    } else {
+        game::resume_game_of_life_simulation(&config, &display, initial, generation, &interrupted, &mut recording)
+    };
+    finish_run(&args, recording, metrics, last)
+}
+
+/// Opens the files of the outputs written generation by generation as the run goes on.
+fn start_recording(args: &Args, config: &game::SimulationConfig) -> io::Result<game::RunRecording> {
+    let mut recording = game::RunRecording::default();
+    if let Some(path) = &args.measure_entropy {
+        recording = recording.with_entropy_csv(io::BufWriter::new(fs::File::create(path)?), config.region)?;
+    }
+    if let Some(path) = &args.export_svg_anim {
+        let writer = io::BufWriter::new(fs::File::create(path)?);
+        recording = recording.with_svg_animation(writer, SVG_FRAME_DELAY_MS, SVG_CELL_SIZE, config.warmup);
+    }
+    Ok(recording)
+}
+
+/// Completes the outputs of a finished 2D run: the recording, the final grid exports and the metrics.
+fn finish_run(args: &Args, recording: game::RunRecording, mut metrics: metrics::SimulationMetrics, last: game::Grid) -> io::Result<()> {
+    recording.finish()?;
+    if args.count_objects {
+        metrics.final_objects = Some(grid_ops::count_objects(&last, args.object_connectivity));
+    }
+    if let Some(path) = &args.export_ppm {
+        ppm::export_ppm(&last, path, args.ppm_cell_size, PPM_ALIVE_RGB, PPM_DEAD_RGB)?;
+    }
+    if let Some(path) = &args.export_html {
+        let title = format!("Game of Life, generation {}", metrics.total_iterations);
+        fs::write(path, html::export_html(&last, HTML_CELL_SIZE, &title))?;
+    }
+    if let Some(path) = &args.export_tikz {
+        fs::write(path, tikz::export_tikz(&last, args.tikz_cell_size, true))?;
+    }
+    report_metrics(args, &metrics)
+}
+
+/// Runs every pattern of the built-in catalog in turn, centered in the grid after a title banner.
+fn run_demo(config: &game::SimulationConfig, display: &display::DisplayOptions, interrupted: &AtomicBool) -> io::Result<()> {
+    for pattern in catalog::DEMO_CATALOG {
+        let cells = pattern.grid();
+        let (height, width) = game::grid_dimensions(&cells);
+        let (rows, cols) = config.dimensions();
+        let config = game::SimulationConfig {
+            grid_size: rows.max(height + 2 * DEMO_MARGIN),
+            grid_width: Some(cols.max(width + 2 * DEMO_MARGIN)),
+            max_iterations: config.max_iterations.or(Some(DEMO_GENERATIONS)),
+            ..config.clone()
+        };
+        let (rows, cols) = config.dimensions();
+        let mut initial = game::initialize_grid(rows, cols);
+        grid_ops::place_pattern_centered(&mut initial, &cells);
+
+        display::clear_screen();
+        println!("=== {} ===", pattern.name);
+        thread::sleep(DEMO_TITLE_PAUSE);
+        let (metrics, _) = game::run_game_of_life_simulation(&config, display, initial, interrupted);
+        if metrics.termination_reason == metrics::TerminationReason::UserInterrupt {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Runs the simulation again with the next seed each time it ends, until interrupted.
+///
+/// Returns the metrics and final grid of the last run. Only the first run starts paused.
+fn run_looped(
+    mut config: game::SimulationConfig,
+    display: &display::DisplayOptions,
+    mut initial: game::Grid,
+    interrupted: &AtomicBool,
+) -> (metrics::SimulationMetrics, game::Grid) {
+    let mut display = display.clone();
+    loop {
+        let (metrics, last) = game::run_game_of_life_simulation(&config, &display, initial, interrupted);
+        display.start_paused = false;
+        if metrics.termination_reason == metrics::TerminationReason::UserInterrupt {
+            return (metrics, last);
+        }
+        println!("Restarting...");
+        thread::sleep(RESTART_PAUSE);
+        if interrupted.load(Ordering::SeqCst) {
+            return (metrics, last);
+        }
+        config.seed = game::next_seed(config.seed);
+        initial = game::create_seeded_grid(&config);
+    }
+}
+
+/// Reads a grid in `.cells` format from stdin and writes its next generation to stdout.
+///
+/// The configured topology decides how the edges of the grid wrap. With `resize`, the grid is cut
+/// or padded with dead cells on the right and bottom to the configured dimensions before stepping;
+/// otherwise it keeps the dimensions of the input.
+fn run_pipe_mode(config: &game::SimulationConfig, resize: bool) -> Result<(), game::GameError> {
+    let mut input = String::new();
+    io::stdin().read_to_string(&mut input)?;
+    let current = pipe_input_grid(cells::parse_cells(&input)?, config, resize);
+
+    let mut next = current.clone();
+    match &config.rule_fn {
+        Some(rule) => game::compute_next_generation_with_rule_fn(&current, &mut next, config.topology, rule),
+        None => game::compute_next_generation_with_rules(&current, &mut next, config.topology, &config.rule),
+    }
+    if config.dead_border {
+        game::enforce_dead_border(&mut next);
+    }
+
+    let mut stdout = io::stdout().lock();
+    stdout.write_all(cells::format_cells(&next).as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+/// Fits the grid read by [`run_pipe_mode`] to the configured dimensions when `resize` is set.
+fn pipe_input_grid(grid: game::Grid, config: &game::SimulationConfig, resize: bool) -> game::Grid {
+    if !resize {
+        return grid;
+    }
+    let (rows, cols) = config.dimensions();
+    let mut resized = game::initialize_grid(rows, cols);
+    for (resized_row, row) in resized.iter_mut().zip(&grid) {
+        for (resized_cell, &cell) in resized_row.iter_mut().zip(row) {
+            *resized_cell = cell;
+        }
+    }
+    resized
+}
+
+/// Broadcasts every generation to the WebSocket clients until the simulation ends or Ctrl+C,
+/// returning the run statistics together with the final grid.
+#[cfg(feature = "ws")]
+fn stream_generations(
+    port: u16,
+    config: &game::SimulationConfig,
+    initial: game::Grid,
+    generation: u64,
+    frame_time: Duration,
+    interrupted: &AtomicBool,
+    recording: &mut game::RunRecording,
+) -> io::Result<(metrics::SimulationMetrics, game::Grid)> {
+    let (server, address) = ws::WsServer::start(port)?;
+    println!("Streaming generations on ws://{}, press Ctrl+C to stop", address);
+    Ok(game::run_streamed_simulation(config, initial, generation, interrupted, recording, |grid, iteration| {
+        server.broadcast(iteration, grid);
+        thread::sleep(frame_time);
+    }))
+}
+
+/// Steps through the simulation as commanded by lines read from stdin, keeping past grids to go back to.
+fn run_time_travel(
+    config: &game::SimulationConfig,
+    display: &display::DisplayOptions,
+    initial: game::Grid,
+    generation: u64,
+    history_size: usize,
+) -> io::Result<()> {
+    let mut universe = game::Universe::new(initial, config.topology)
+        .with_rules(config.rule)
+        .with_rule_fn(config.rule_fn.clone())
+        .with_packed(config.packed)
+        .with_active_cells(config.active_cells)
+        .with_incremental_counts(config.incremental_counts)
+        .with_dead_border(config.dead_border)
+        .with_generation(generation);
+    let mut state = time_travel::TimeTravelState::new(history_size);
+    let mut input = io::stdin().lock();
+    let mut message = None;
+
+    loop {
+        display::clear_screen();
+        let grid = universe.grid();
+        if display.show_counts {
+            let counts = game::neighbor_counts(grid, config.topology);
+            display::display_neighbor_counts(grid, &counts, universe.generation(), display);
+        } else {
+            display::display_grid(grid, universe.generation(), display, None);
+        }
+        println!("[ back, ] or Enter forward, c clear, f fill, q quit; `clear` and `fill [r1,c1,r2,c2]` edit the grid");
+        if let Some(message) = message.take() {
+            println!("{}", message);
+        }
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let commands = line.trim();
+        if commands.is_empty() {
+            state.step_forward(&mut universe);
+        } else if commands == "clear" {
+            edit_grid(&mut state, &mut universe, grid_ops::grid_clear);
+        } else if commands == "fill" {
+            edit_grid(&mut state, &mut universe, grid_ops::grid_fill);
+        } else if let Some(region) = commands.strip_prefix("fill ") {
+            message = fill_region(&mut state, &mut universe, region).err();
+        } else {
+            for command in commands.chars() {
+                match command {
+                    '[' => {
+                        state.step_back(&mut universe);
+                    }
+                    ']' => state.step_forward(&mut universe),
+                    'c' => edit_grid(&mut state, &mut universe, grid_ops::grid_clear),
+                    'f' => edit_grid(&mut state, &mut universe, grid_ops::grid_fill),
+                    'q' => return Ok(()),
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Applies an edit to a copy of the current grid and makes it the current one.
+fn edit_grid(state: &mut time_travel::TimeTravelState, universe: &mut game::Universe, edit: impl FnOnce(&mut game::Grid)) {
+    let mut grid = universe.grid().clone();
+    edit(&mut grid);
+    state.edit(universe, grid);
+}
+
+/// Brings the cells of a `r1,c1,r2,c2` region to life, describing the problem when the region is invalid.
+fn fill_region(state: &mut time_travel::TimeTravelState, universe: &mut game::Universe, region: &str) -> Result<(), String> {
+    let (r1, c1, r2, c2) = parse_region(region).ok_or_else(|| format!("Invalid region `{}`, expected r1,c1,r2,c2", region))?;
+    let mut grid = universe.grid().clone();
+    grid_ops::grid_fill_region(&mut grid, r1, c1, r2, c2, true).map_err(|err| err.to_string())?;
+    state.edit(universe, grid);
+    Ok(())
+}
+
+/// Parses a `r1,c1,r2,c2` region of cells.
+fn parse_region(text: &str) -> Option<(usize, usize, usize, usize)> {
+    let corners: Vec<usize> = text.split(',').map(|value| value.trim().parse().ok()).collect::<Option<_>>()?;
+    match corners[..] {
+        [r1, c1, r2, c2] => Some((r1, c1, r2, c2)),
+        _ => None,
+    }
+}
+
+/// Loads a pattern, scaled up by `scale`, and places it in a grid of the configured size, centered unless an `(x, y)` offset is given.
+fn load_pattern(
+    path: &Path,
+    library: Option<&HashMap<String, game::Grid>>,
+    config: &mut game::SimulationConfig,
+    offset: Option<(usize, usize)>,
+    scale: usize,
+    border: Option<(usize, grid_ops::BorderPolicy)>,
+    verbose: bool,
+) -> io::Result<game::Grid> {
+    let mut pattern = read_pattern(path, library)?;
+    if verbose {
+        let metadata = &pattern.metadata;
+        let (height, width) = game::grid_dimensions(&pattern.cells);
+        println!("Pattern: {} ({}x{})", metadata.name.as_deref().unwrap_or("unnamed"), height, width);
+        if let Some(author) = &metadata.author {
+            println!("Author: {}", author);
+        }
+        for comment in &metadata.comments {
+            println!("{}", comment);
+        }
+    }
+
+    if scale > 1 {
+        pattern.cells = grid_ops::scale_pattern(&pattern.cells, scale);
+    }
+    if let Some((cells, policy)) = border {
+        ensure_border(config, &pattern.cells, cells, policy)?;
+    }
+
+    let (rows, cols) = config.dimensions();
+    let placed = match offset {
+        Some((x, y)) => pattern.placed_at(rows, cols, y, x),
+        None => pattern.centered_in(rows, cols),
+    };
+    placed.ok_or_else(|| {
+        io::Error::other(format!("Pattern {} doesn't fit in the {}x{} grid", path.display(), rows, cols))
+    })
+}
+
+/// Makes sure the configured grid leaves `border` dead cells around the centered pattern,
+/// growing it with a warning or failing, depending on the policy.
+fn ensure_border(config: &mut game::SimulationConfig, pattern: &game::Grid, border: usize, policy: grid_ops::BorderPolicy) -> io::Result<()> {
+    let (rows, cols) = config.dimensions();
+    let (needed_rows, needed_cols) = grid_ops::dimensions_with_border(pattern, border);
+    if needed_rows <= rows && needed_cols <= cols {
+        return Ok(());
+    }
+    let message = format!(
+        "The pattern with a border of {} cells needs a {}x{} grid, but the grid is {}x{}",
+        border, needed_rows, needed_cols, rows, cols
+    );
+    match policy {
+        grid_ops::BorderPolicy::Error => Err(io::Error::other(message)),
+        grid_ops::BorderPolicy::Grow => {
+            warn!("{}, growing it", message);
+            config.grid_size = rows.max(needed_rows);
+            config.grid_width = Some(cols.max(needed_cols));
+            Ok(())
+        }
+    }
+}
+
+/// Finds the pattern named `source` in the user library, then in the built-in catalog, and
+/// otherwise reads the RLE, plaintext `.cells` or Life 1.05 file at that path.
+fn read_pattern(source: &Path, library: Option<&HashMap<String, game::Grid>>) -> io::Result<rle::Pattern> {
+    let named = |cells, name: &str| rle::Pattern {
+        cells,
+        metadata: rle::PatternMetadata { name: Some(name.to_string()), ..Default::default() },
+    };
+    if let Some(name) = source.to_str() {
+        if let Some(cells) = library.and_then(|library| library.get(name)) {
+            return Ok(named(cells.clone(), name));
+        }
+        if let Some(pattern) = catalog::builtin_pattern(name) {
+            return Ok(named(pattern.grid(), pattern.name));
+        }
+    }
+
+    match source.extension().and_then(|extension| extension.to_str()) {
+        Some("cells") => cells::load_cells(source),
+        Some("lif" | "life") => {
+            let cells = life105::load_life105(&fs::read_to_string(source)?)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+            Ok(rle::Pattern { cells, metadata: rle::PatternMetadata::default() })
+        }
+        _ => rle::load_rle(source),
+    }
+}
+
+/// Seeds a grid of the configured size from the darkness of a PGM image, drawing from the configured seed.
+fn seed_from_image(path: &Path, config: &game::SimulationConfig) -> io::Result<game::Grid> {
+    let image = ppm::load_pgm(path)?;
+    let (rows, cols) = config.dimensions();
+    let mut grid = game::initialize_grid(rows, cols);
+    game::seed_from_image(&mut grid, &image, &mut StdRng::seed_from_u64(config.seed));
+    Ok(grid)
+}
+
+/// Checks that a file can be created at the given path, i.e. that its directory exists.
+fn ensure_output_path(path: &Path) -> io::Result<()> {
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    if directory.is_dir() {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Directory of output file {} doesn't exist", path.display()),
+        ))
+    }
+}
+
+/// Prints what a run with the given settings would do.
+fn print_plan(args: &Args, config: &game::SimulationConfig, mode: &str, initial: Option<&game::Grid>) {
+    let (rows, cols) = config.dimensions();
+    println!("Dry run, nothing was simulated:");
+    println!("  Mode:               {}", mode);
+    let topology = config.topology.to_possible_value().expect("Topology values are never skipped");
+    println!("  Grid:               {}x{}, {} topology", rows, cols, topology.get_name());
+    match &config.rule_fn {
+        Some(_) => println!("  Rule:               scripted"),
+        None => println!("  Rule:               {}", config.rule),
+    }
+    println!("  Seed:               {}", config.seed);
+    if let Some(initial) = initial {
+        println!("  Initial population: {}", game::count_population(initial));
+    }
+    match config.max_iterations {
+        Some(limit) => println!("  Max iterations:     {}", limit),
+        None => println!("  Max iterations:     unlimited"),
+    }
+    if let Some(limit) = config.max_runtime {
+        println!("  Max runtime:        {} seconds", limit.as_secs_f64());
+    }
+    if let Some(interval) = config.checkpoint_interval {
+        println!("  Checkpoint:         every {} generations to {}", interval, checkpoint::CHECKPOINT_FILE);
+    }
+    if let Some(every) = config.checkpoint_every {
+        println!("  Checkpoints:        every {} generations to checkpoint_<N>.bin", every);
+    }
+    let outputs = [
+        ("Record", &args.record),
+        ("Metrics JSON", &args.metrics_json),
+        ("PPM image", &args.export_ppm),
+        ("SVG animation", &args.export_svg_anim),
+        ("TikZ picture", &args.export_tikz),
+        ("HTML page", &args.export_html),
+        ("Log file", &args.log_file),
+    ];
+    for (name, path) in outputs {
+        if let Some(path) = path {
+            println!("  {:<20}{}", format!("{}:", name), path.display());
+        }
+    }
+}
+
+/// Dimensions in cells of what is drawn each frame; the comparison view shows two grids and a
+/// label line, and the border adds a line above and below and two columns on each side.
+fn displayed_dimensions(args: &Args, config: &game::SimulationConfig) -> (usize, usize) {
+    let (rows, cols) = config.dimensions();
+    match (args.compare_lag.is_some() || args.compare.is_some(), args.ascii_border) {
+        (true, _) => (rows + 1, 2 * cols + 3),
+        (false, true) => (rows + 2, cols + 2),
+        (false, false) => (rows, cols),
+    }
+}
+
+/// Builds the simulation settings from the command line, picking a random seed if none was given.
+fn config_from_args(args: &Args) -> io::Result<game::SimulationConfig> {
+    let (grid_size, grid_width) = if args.fit {
+        let size = terminal::query_terminal_size()
+            .ok_or_else(|| io::Error::other("Unable to determine the terminal size for --fit"))?;
+        let (rows, cols) = terminal::fit_grid_dimensions(size);
+        if rows == 0 || cols == 0 {
+            return Err(io::Error::other("Terminal is too small to fit any grid"));
+        }
+        (rows, Some(cols))
+    } else {
+        (args.grid_size, None)
+    };
+
+    let builder = SimulationBuilder::new()
+        .grid_size(grid_size)
+        .grid_width(grid_width)
+        .exit_steady(args.exit_steady || args.loop_runs)
+        .quiet_steady(args.quiet_steady)
+        .topology(args.topology)
+        .seed(args.seed.unwrap_or_else(|| rand::thread_rng().gen()))
+        .warmup(args.warmup)
+        .density(args.density)
+        .invert(args.invert)
+        .mirror(args.mirror)
+        .seed_mode(args.seed_mode)
+        .packed(args.packed)
+        .rule(args.rule)
+        .active_cells(args.active_cells)
+        .incremental_counts(args.incremental_counts)
+        .dead_border(args.dead_border)
+        .threads(args.threads)
+        .full_history(args.full_history)
+        .stabilize(args.stabilize)
+        .terminate_on_quiescence(args.terminate_on_quiescence)
+        .max_iterations(args.max_iterations)
+        .max_runtime(args.max_runtime)
+        .checkpoint_interval(args.checkpoint_interval)
+        .checkpoint_every(args.checkpoint_every)
+        .keep_checkpoints(args.keep_checkpoints)
+        .region(args.region)
+        .trace(args.trace);
+    #[cfg(feature = "lua")]
+    let builder = match &args.lua_rules {
+        Some(path) => {
+            let script = lua::LuaRules::load(path).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+            builder.rule_fn(rules::SharedRuleFn::new(script))
+        }
+        None => builder,
+    };
+    let builder = if args.wrap_horizontal || args.wrap_vertical {
+        builder.wrap(args.wrap_horizontal, args.wrap_vertical)
+    } else {
+        builder
+    };
+    builder.config().map_err(io::Error::other)
+}
+
+/// Prints and saves the run statistics as requested on the command line.
+fn report_metrics(args: &Args, metrics: &metrics::SimulationMetrics) -> io::Result<()> {
+    if args.show_metrics {
+        println!("{}", metrics);
+    }
+    if let Some(objects) = metrics.final_objects.filter(|_| !args.show_metrics && !args.headless) {
+        println!("Final objects: {}", objects);
+    }
+    if let Some(trace) = metrics.trace.as_ref().filter(|_| !args.headless) {
+        println!("{}", trace);
+    }
+    if args.headless {
+        metrics.write_json(io::stdout().lock())?;
+    }
+    if let Some(path) = &args.metrics_json {
+        metrics.save_json(path)?;
+    }
+    if args.fail_on_extinction {
+        if let metrics::SimulationOutcome::Extinct { .. } = metrics.outcome() {
+            std::process::exit(EXTINCTION_EXIT_CODE);
+        }
+    }
+    Ok(())
 }
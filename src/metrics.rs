@@ -0,0 +1,638 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+use serde::Serialize;
+
+/// Reason why the simulation loop stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum TerminationReason {
+    /// The iteration limit was reached.
+    MaxIterations,
+    /// The grid repeated an earlier state; holds the period of the cycle.
+    SteadyState(usize),
+    /// No live cells remain on the grid.
+    Extinction,
+    /// The population stayed nearly constant over the stabilization window; holds the population it settled near.
+    Stabilized(usize),
+    /// The user stopped the simulation with `Ctrl+C`.
+    UserInterrupt,
+    /// The wall-clock limit was reached; holds how long the run took.
+    MaxRuntime(Duration),
+}
+
+impl fmt::Display for TerminationReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TerminationReason::MaxIterations => write!(f, "maximum number of iterations reached"),
+            TerminationReason::SteadyState(1) => write!(f, "steady state reached"),
+            TerminationReason::SteadyState(period) => write!(f, "oscillation with period {} detected", period),
+            TerminationReason::Extinction => write!(f, "population extinct"),
+            TerminationReason::Stabilized(population) => write!(f, "population stabilized near {}", population),
+            TerminationReason::UserInterrupt => write!(f, "interrupted by user"),
+            TerminationReason::MaxRuntime(elapsed) => write!(f, "stopped after {:.1} seconds", elapsed.as_secs_f64()),
+        }
+    }
+}
+
+/// How a simulation run ended, with the iteration at which it stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(tag = "outcome", rename_all = "kebab-case")]
+pub enum SimulationOutcome {
+    /// The grid repeated a state seen `period` generations earlier.
+    SteadyState { iteration: u64, period: usize },
+    /// No live cells remain on the grid.
+    Extinct { iteration: u64 },
+    /// The population stayed nearly constant around `population`.
+    Stabilized { iteration: u64, population: usize },
+    /// The iteration limit was reached.
+    MaxIterations { iteration: u64 },
+    /// The user stopped the simulation.
+    Interrupted { iteration: u64 },
+    /// The wall-clock limit was reached.
+    MaxRuntime { iteration: u64 },
+}
+
+impl SimulationOutcome {
+    /// Builds the outcome of a run that stopped for the given reason at the given iteration.
+    pub fn new(reason: TerminationReason, iteration: u64) -> Self {
+        match reason {
+            TerminationReason::SteadyState(period) => SimulationOutcome::SteadyState { iteration, period },
+            TerminationReason::Extinction => SimulationOutcome::Extinct { iteration },
+            TerminationReason::Stabilized(population) => SimulationOutcome::Stabilized { iteration, population },
+            TerminationReason::MaxIterations => SimulationOutcome::MaxIterations { iteration },
+            TerminationReason::UserInterrupt => SimulationOutcome::Interrupted { iteration },
+            TerminationReason::MaxRuntime(_) => SimulationOutcome::MaxRuntime { iteration },
+        }
+    }
+
+    /// Iteration at which the run stopped.
+    pub fn iteration(&self) -> u64 {
+        match *self {
+            SimulationOutcome::SteadyState { iteration, .. }
+            | SimulationOutcome::Extinct { iteration }
+            | SimulationOutcome::Stabilized { iteration, .. }
+            | SimulationOutcome::MaxIterations { iteration }
+            | SimulationOutcome::Interrupted { iteration }
+            | SimulationOutcome::MaxRuntime { iteration } => iteration,
+        }
+    }
+}
+
+impl fmt::Display for SimulationOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SimulationOutcome::SteadyState { period: 1, .. } => write!(f, "steady-state"),
+            SimulationOutcome::SteadyState { period, .. } => write!(f, "oscillation(period={})", period),
+            SimulationOutcome::Extinct { .. } => write!(f, "extinct"),
+            SimulationOutcome::Stabilized { population, .. } => write!(f, "stabilized(population={})", population),
+            SimulationOutcome::MaxIterations { .. } => write!(f, "max-iterations"),
+            SimulationOutcome::Interrupted { .. } => write!(f, "interrupted"),
+            SimulationOutcome::MaxRuntime { .. } => write!(f, "max-runtime"),
+        }
+    }
+}
+
+/// One-line result of a headless run, meant for batch sweeps over many seeds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub struct RunSummary {
+    pub seed: u64,
+    pub final_population: usize,
+    pub outcome: SimulationOutcome,
+}
+
+impl fmt::Display for RunSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "seed={} iteration={} population={} outcome={}",
+            self.seed,
+            self.outcome.iteration(),
+            self.final_population,
+            self.outcome
+        )
+    }
+}
+
+/// Statistics collected over a single simulation run.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct SimulationMetrics {
+    pub total_iterations: u64,
+    pub peak_population: usize,
+    pub peak_iteration: u64,
+    pub final_population: usize,
+    pub mean_entropy: f64,
+    pub elapsed_wall_time: Duration,
+    pub termination_reason: TerminationReason,
+    /// Cells born over the whole run.
+    pub births: u64,
+    /// Cells died over the whole run.
+    pub deaths: u64,
+    /// Population statistics of the tracked region, when there is one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<RegionMetrics>,
+    /// Timeline of the cell traced with `--trace`, when there is one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<CellTrace>,
+    /// Number of objects, groups of connected live cells, on the final grid when they were counted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub final_objects: Option<usize>,
+}
+
+/// Population statistics of the rectangle tracked with `--region`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct RegionMetrics {
+    pub peak_population: usize,
+    pub peak_iteration: u64,
+    pub final_population: usize,
+}
+
+/// State and live neighbor count of a single cell, generation by generation.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct CellTrace {
+    /// Column of the cell.
+    pub x: usize,
+    /// Row of the cell.
+    pub y: usize,
+    /// Iteration of the first recorded generation.
+    pub first_iteration: u64,
+    pub alive: Vec<bool>,
+    pub neighbors: Vec<u8>,
+}
+
+impl CellTrace {
+    pub fn new(x: usize, y: usize) -> Self {
+        CellTrace { x, y, ..Default::default() }
+    }
+
+    /// Records the cell in the given generation; a generation recorded before is ignored.
+    pub fn record(&mut self, iteration: u64, alive: bool, neighbors: u8) {
+        if self.alive.is_empty() {
+            self.first_iteration = iteration;
+        } else if iteration < self.first_iteration + self.alive.len() as u64 {
+            return;
+        }
+        self.alive.push(alive);
+        self.neighbors.push(neighbors);
+    }
+
+    /// States of the cell as `1` for alive and `0` for dead, e.g. `0101`.
+    pub fn states(&self) -> String {
+        self.alive.iter().map(|&alive| if alive { '1' } else { '0' }).collect()
+    }
+
+    /// Live neighbor counts of the cell as one digit per generation, e.g. `3131`.
+    pub fn neighbor_counts(&self) -> String {
+        self.neighbors.iter().map(|&count| char::from(b'0' + count)).collect()
+    }
+}
+
+impl fmt::Display for CellTrace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Trace of cell ({}, {}) from iteration {}:", self.x, self.y, self.first_iteration)?;
+        writeln!(f, "  Alive:     {}", self.states())?;
+        write!(f, "  Neighbors: {}", self.neighbor_counts())
+    }
+}
+
+impl SimulationMetrics {
+    /// Structured description of how the run ended.
+    pub fn outcome(&self) -> SimulationOutcome {
+        SimulationOutcome::new(self.termination_reason, self.total_iterations)
+    }
+
+    /// Saves the metrics as JSON to the given path.
+    pub fn save_json(&self, path: &Path) -> io::Result<()> {
+        self.write_json(BufWriter::new(File::create(path)?))
+    }
+
+    /// Writes the metrics as pretty-printed JSON, followed by a newline.
+    pub fn write_json(&self, mut writer: impl Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writeln!(writer)?;
+        writer.flush()
+    }
+}
+
+impl fmt::Display for SimulationMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Simulation summary:")?;
+        writeln!(f, "  Termination reason: {}", self.termination_reason)?;
+        writeln!(f, "  Total iterations:   {}", self.total_iterations)?;
+        writeln!(f, "  Peak population:    {} (iteration {})", self.peak_population, self.peak_iteration)?;
+        writeln!(f, "  Final population:   {}", self.final_population)?;
+        writeln!(f, "  Births:             {}", self.births)?;
+        writeln!(f, "  Deaths:             {}", self.deaths)?;
+        if let Some(objects) = self.final_objects {
+            writeln!(f, "  Final objects:      {}", objects)?;
+        }
+        if let Some(region) = &self.region {
+            writeln!(f, "  Region peak:        {} (iteration {})", region.peak_population, region.peak_iteration)?;
+            writeln!(f, "  Region final:       {}", region.final_population)?;
+        }
+        writeln!(f, "  Mean entropy:       {:.3}", self.mean_entropy)?;
+        write!(f, "  Elapsed time:       {:.2?}", self.elapsed_wall_time)
+    }
+}
+
+/// Accumulates population statistics generation by generation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PopulationTracker {
+    peak_population: usize,
+    peak_iteration: u64,
+    entropy_sum: f64,
+    samples: u64,
+    region: Option<RegionMetrics>,
+    trace: Option<CellTrace>,
+    /// Populations of the latest recorded generations, in order, once enabled with
+    /// [`with_populations`](Self::with_populations); holds up to twice `population_window`.
+    populations: Vec<usize>,
+    /// Number of the latest populations the series statistics look at, `0` when they aren't kept.
+    population_window: usize,
+    /// Cells born over all recorded generations.
+    births: u64,
+    /// Cells died over all recorded generations.
+    deaths: u64,
+    /// Iteration, births and deaths of the latest [`CHANGE_HISTORY`] generations with recorded changes.
+    changes: VecDeque<(u64, u64, u64)>,
+}
+
+impl PopulationTracker {
+    /// Creates a tracker that also follows the population of a region, see [`record_region`](Self::record_region).
+    pub fn with_region() -> Self {
+        PopulationTracker { region: Some(RegionMetrics::default()), ..Default::default() }
+    }
+
+    /// Records the population of the tracked region in the given generation; ignored without a region.
+    pub fn record_region(&mut self, iteration: u64, population: usize) {
+        if let Some(region) = &mut self.region {
+            if population > region.peak_population {
+                region.peak_population = population;
+                region.peak_iteration = iteration;
+            }
+            region.final_population = population;
+        }
+    }
+
+    /// Also records the state of the cell at column `x` of row `y`, see [`record_cell`](Self::record_cell).
+    pub fn with_trace(self, x: usize, y: usize) -> Self {
+        PopulationTracker { trace: Some(CellTrace::new(x, y)), ..self }
+    }
+
+    /// Records the state of the traced cell in the given generation; ignored without a traced cell.
+    pub fn record_cell(&mut self, iteration: u64, alive: bool, neighbors: u8) {
+        if let Some(trace) = &mut self.trace {
+            trace.record(iteration, alive, neighbors);
+        }
+    }
+
+    /// Also keeps the populations of the latest `window` generations for [`autocorrelation`](Self::autocorrelation),
+    /// [`detect_period`](Self::detect_period) and [`std_dev`](Self::std_dev), which see nothing otherwise.
+    pub fn with_populations(self, window: usize) -> Self {
+        PopulationTracker { population_window: window, ..self }
+    }
+
+    /// Records the population of the given generation out of `total_cells` cells.
+    pub fn record(&mut self, iteration: u64, population: usize, total_cells: usize) {
+        if population > self.peak_population {
+            self.peak_population = population;
+            self.peak_iteration = iteration;
+        }
+        self.entropy_sum += binary_entropy(population, total_cells);
+        self.samples += 1;
+        if self.population_window > 0 {
+            if self.populations.len() == 2 * self.population_window {
+                self.populations.drain(..self.population_window);
+            }
+            self.populations.push(population);
+        }
+    }
+
+    /// Populations of the latest generations, at most `population_window` of them.
+    fn recent_populations(&self) -> &[usize] {
+        &self.populations[self.populations.len().saturating_sub(self.population_window)..]
+    }
+
+    /// Records how many cells were born and died going into the given generation.
+    pub fn record_changes(&mut self, iteration: u64, births: u64, deaths: u64) {
+        self.births += births;
+        self.deaths += deaths;
+        if self.changes.len() == CHANGE_HISTORY {
+            self.changes.pop_front();
+        }
+        self.changes.push_back((iteration, births, deaths));
+    }
+
+    /// Births and deaths going into the given generation, `None` unless it is among the latest
+    /// [`CHANGE_HISTORY`] recorded ones.
+    pub fn changes(&self, iteration: u64) -> Option<(u64, u64)> {
+        self.changes
+            .iter()
+            .rev()
+            .find(|&&(recorded, _, _)| recorded == iteration)
+            .map(|&(_, births, deaths)| (births, deaths))
+    }
+
+    /// Births minus deaths going into generation `iter`, `0` when it isn't among the latest recorded ones.
+    pub fn net_births(&self, iter: usize) -> i64 {
+        self.changes(iter as u64).map_or(0, |(births, deaths)| births as i64 - deaths as i64)
+    }
+
+    /// Standard deviation of the kept populations, `0.0` when none are.
+    pub fn std_dev(&self) -> f64 {
+        std_dev(self.recent_populations())
+    }
+
+    /// Pearson correlation of the kept populations with themselves `lag` generations later.
+    ///
+    /// A series that doesn't vary correlates perfectly, `1.0`, with any lag shorter than itself.
+    /// Returns `0.0` when fewer than two generations overlap.
+    pub fn autocorrelation(&self, lag: usize) -> f64 {
+        let populations = self.recent_populations();
+        let overlap = populations.len().saturating_sub(lag);
+        if overlap < 2 {
+            return 0.0;
+        }
+        let (early, late) = (&populations[..overlap], &populations[lag..]);
+        let (early_mean, late_mean) = (mean(early), mean(late));
+        let covariance: f64 = early.iter().zip(late).map(|(&a, &b)| (a as f64 - early_mean) * (b as f64 - late_mean)).sum();
+        let (early_spread, late_spread) = (std_dev(early), std_dev(late));
+        match (early_spread == 0.0, late_spread == 0.0) {
+            (true, true) => 1.0,
+            (false, false) => covariance / (early_spread * late_spread * overlap as f64),
+            _ => 0.0,
+        }
+    }
+
+    /// Smallest lag, up to half the kept generations, at which the populations correlate above
+    /// [`PERIOD_CORRELATION`] with themselves, hinting at an oscillation even when the grid doesn't repeat.
+    pub fn detect_period(&self) -> Option<usize> {
+        (1..=self.recent_populations().len() / 2).find(|&lag| self.autocorrelation(lag) > PERIOD_CORRELATION)
+    }
+
+    /// Mean entropy over all recorded generations, `0.0` when nothing was recorded.
+    pub fn mean_entropy(&self) -> f64 {
+        if self.samples == 0 {
+            0.0
+        } else {
+            self.entropy_sum / self.samples as f64
+        }
+    }
+
+    /// Builds the final run statistics from the tracked data.
+    pub fn finish(
+        &self,
+        total_iterations: u64,
+        final_population: usize,
+        elapsed_wall_time: Duration,
+        termination_reason: TerminationReason,
+    ) -> SimulationMetrics {
+        SimulationMetrics {
+            total_iterations,
+            peak_population: self.peak_population,
+            peak_iteration: self.peak_iteration,
+            final_population,
+            mean_entropy: self.mean_entropy(),
+            elapsed_wall_time,
+            termination_reason,
+            births: self.births,
+            deaths: self.deaths,
+            region: self.region,
+            trace: self.trace.clone(),
+            final_objects: None,
+        }
+    }
+}
+
+/// Autocorrelation above which [`PopulationTracker::detect_period`] takes a lag for the period.
+pub const PERIOD_CORRELATION: f64 = 0.9;
+/// Number of the latest generations whose births and deaths [`PopulationTracker`] keeps one by one.
+pub const CHANGE_HISTORY: usize = 64;
+/// Multiple of the lag shown with `--show-autocorrelation` giving the number of populations it's computed over.
+pub const POPULATION_WINDOW_LAGS: usize = 16;
+
+fn mean(values: &[usize]) -> f64 {
+    values.iter().sum::<usize>() as f64 / values.len() as f64
+}
+
+/// Population standard deviation of the values, `0.0` when there are none.
+fn std_dev(values: &[usize]) -> f64 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mean = mean(values);
+    (values.iter().map(|&value| (value as f64 - mean).powi(2)).sum::<f64>() / values.len() as f64).sqrt()
+}
+
+/// Shannon entropy, in bits, of a population of `live` cells out of `total`.
+///
+/// Returns `0.0` for an all-dead or all-alive population and `1.0` when exactly half is alive.
+pub fn binary_entropy(live: usize, total: usize) -> f64 {
+    if total == 0 || live == 0 || live >= total {
+        return 0.0;
+    }
+    let p = live as f64 / total as f64;
+    -p * p.log2() - (1.0 - p) * (1.0 - p).log2()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_termination_reason_display() {
+        assert_eq!(TerminationReason::MaxIterations.to_string(), "maximum number of iterations reached");
+        assert_eq!(TerminationReason::SteadyState(1).to_string(), "steady state reached");
+        assert_eq!(TerminationReason::SteadyState(2).to_string(), "oscillation with period 2 detected");
+        assert_eq!(TerminationReason::Extinction.to_string(), "population extinct");
+        assert_eq!(TerminationReason::Stabilized(42).to_string(), "population stabilized near 42");
+        assert_eq!(TerminationReason::UserInterrupt.to_string(), "interrupted by user");
+        assert_eq!(TerminationReason::MaxRuntime(Duration::from_millis(2049)).to_string(), "stopped after 2.0 seconds");
+    }
+
+    #[test]
+    fn test_metrics_summary_includes_reason() {
+        let metrics = SimulationMetrics {
+            total_iterations: 12,
+            peak_population: 40,
+            peak_iteration: 3,
+            final_population: 0,
+            mean_entropy: 0.5,
+            elapsed_wall_time: Duration::from_millis(600),
+            termination_reason: TerminationReason::Extinction,
+            births: 30,
+            deaths: 70,
+            region: None,
+            trace: None,
+            final_objects: Some(2),
+        };
+
+        let summary = metrics.to_string();
+
+        assert!(summary.contains("Termination reason: population extinct"));
+        assert!(summary.contains("Peak population:    40 (iteration 3)"));
+        assert!(summary.contains("Mean entropy:       0.500"));
+        assert!(summary.contains("Final objects:      2"));
+        assert!(summary.contains("Deaths:             70"));
+    }
+
+    #[test]
+    fn test_simulation_outcome() {
+        let outcome = SimulationOutcome::new(TerminationReason::SteadyState(2), 14);
+
+        assert_eq!(outcome, SimulationOutcome::SteadyState { iteration: 14, period: 2 });
+        assert_eq!(outcome.iteration(), 14);
+        assert_eq!(
+            SimulationOutcome::new(TerminationReason::UserInterrupt, 3),
+            SimulationOutcome::Interrupted { iteration: 3 }
+        );
+    }
+
+    #[test]
+    fn test_run_summary_display() {
+        let summary = RunSummary {
+            seed: 42,
+            final_population: 6,
+            outcome: SimulationOutcome::SteadyState { iteration: 31, period: 2 },
+        };
+
+        assert_eq!(summary.to_string(), "seed=42 iteration=31 population=6 outcome=oscillation(period=2)");
+    }
+
+    #[test]
+    fn test_binary_entropy() {
+        assert_eq!(binary_entropy(0, 16), 0.0);
+        assert_eq!(binary_entropy(16, 16), 0.0);
+        assert_eq!(binary_entropy(0, 0), 0.0);
+        assert!((binary_entropy(8, 16) - 1.0).abs() < 1e-12);
+        assert!((binary_entropy(1, 4) - 0.811).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_population_tracker() {
+        let mut tracker = PopulationTracker::default();
+        tracker.record(0, 2, 4);
+        tracker.record(1, 3, 4);
+        tracker.record(2, 0, 4);
+
+        let metrics = tracker.finish(2, 0, Duration::ZERO, TerminationReason::Extinction);
+
+        assert_eq!(metrics.peak_population, 3);
+        assert_eq!(metrics.peak_iteration, 1);
+        assert!((metrics.mean_entropy - (1.0 + binary_entropy(3, 4)) / 3.0).abs() < 1e-12);
+        assert_eq!(metrics.region, None);
+    }
+
+    #[test]
+    fn test_autocorrelation_of_constant_series() {
+        let mut tracker = PopulationTracker::default().with_populations(20);
+        for iteration in 0..12 {
+            tracker.record(iteration, 7, 100);
+        }
+
+        for lag in 0..10 {
+            assert_eq!(tracker.autocorrelation(lag), 1.0, "lag {}", lag);
+        }
+        assert_eq!(tracker.std_dev(), 0.0);
+        assert_eq!(tracker.detect_period(), Some(1));
+    }
+
+    #[test]
+    fn test_detect_period_of_period_4_series() {
+        let mut tracker = PopulationTracker::default().with_populations(40);
+        for (iteration, population) in [10, 14, 22, 14].into_iter().cycle().take(40).enumerate() {
+            tracker.record(iteration as u64, population, 100);
+        }
+
+        assert_eq!(tracker.detect_period(), Some(4));
+        assert!((tracker.autocorrelation(4) - 1.0).abs() < 1e-12);
+        assert!(tracker.autocorrelation(2) < 0.0);
+        assert!((tracker.std_dev() - 19f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_populations_are_kept_only_for_the_window() {
+        let mut untracked = PopulationTracker::default();
+        let mut tracker = PopulationTracker::default().with_populations(8);
+        // A long constant stretch followed by a period 2 oscillation
+        for (iteration, population) in (0..1000).map(|iteration| if iteration < 990 { 5 } else { 4 + iteration % 2 * 2 }).enumerate() {
+            untracked.record(iteration as u64, population, 100);
+            tracker.record(iteration as u64, population, 100);
+        }
+
+        assert!(tracker.populations.len() <= 16);
+        assert!(untracked.populations.is_empty());
+        assert_eq!(untracked.detect_period(), None);
+        // Only the latest generations count, so the oscillation isn't drowned out by the long stretch
+        assert_eq!(tracker.detect_period(), Some(2));
+        assert_eq!(tracker.std_dev(), 1.0);
+    }
+
+    #[test]
+    fn test_net_births() {
+        let mut tracker = PopulationTracker::default();
+        for (iteration, births, deaths) in [(1, 12, 7), (2, 1, 4)] {
+            tracker.record_changes(iteration, births, deaths);
+        }
+
+        assert_eq!(tracker.net_births(1), 5);
+        assert_eq!(tracker.net_births(2), -3);
+        assert_eq!(tracker.net_births(3), 0);
+        let metrics = tracker.finish(2, 0, Duration::ZERO, TerminationReason::MaxIterations);
+        assert_eq!((metrics.births, metrics.deaths), (13, 11));
+    }
+
+    #[test]
+    fn test_changes_are_kept_for_latest_generations_only() {
+        let mut tracker = PopulationTracker::default();
+        for iteration in 1..=1000 {
+            tracker.record_changes(iteration, 2, 1);
+        }
+
+        assert_eq!(tracker.changes.len(), CHANGE_HISTORY);
+        assert_eq!(tracker.changes(1000), Some((2, 1)));
+        assert_eq!(tracker.changes(1000 - CHANGE_HISTORY as u64), None);
+        let metrics = tracker.finish(1000, 0, Duration::ZERO, TerminationReason::MaxIterations);
+        assert_eq!((metrics.births, metrics.deaths), (2000, 1000));
+    }
+
+    #[test]
+    fn test_autocorrelation_needs_overlap() {
+        let mut tracker = PopulationTracker::default().with_populations(10);
+        tracker.record(0, 1, 4);
+        tracker.record(1, 2, 4);
+
+        assert_eq!(tracker.autocorrelation(1), 0.0);
+        assert_eq!(tracker.detect_period(), None);
+        assert_eq!(PopulationTracker::default().std_dev(), 0.0);
+    }
+
+    #[test]
+    fn test_population_tracker_with_region() {
+        let mut tracker = PopulationTracker::with_region();
+        tracker.record_region(0, 1);
+        tracker.record_region(1, 4);
+        tracker.record_region(2, 2);
+
+        let metrics = tracker.finish(2, 5, Duration::ZERO, TerminationReason::MaxIterations);
+
+        assert_eq!(metrics.region, Some(RegionMetrics { peak_population: 4, peak_iteration: 1, final_population: 2 }));
+        assert!(metrics.to_string().contains("Region peak:        4 (iteration 1)"));
+    }
+
+    #[test]
+    fn test_cell_trace_skips_recorded_generations() {
+        let mut trace = CellTrace::new(1, 2);
+        trace.record(5, true, 2);
+        trace.record(6, false, 4);
+        trace.record(6, false, 4);
+        trace.record(7, true, 3);
+
+        assert_eq!(trace.states(), "101");
+        assert_eq!(trace.neighbor_counts(), "243");
+        assert_eq!(trace.to_string(), "Trace of cell (1, 2) from iteration 5:\n  Alive:     101\n  Neighbors: 243");
+    }
+}
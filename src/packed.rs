@@ -0,0 +1,215 @@
+use crate::game::{grid_dimensions, initialize_grid, Grid};
+
+/// Widest grid that fits the packed representation.
+pub const MAX_PACKED_WIDTH: usize = 64;
+
+/// Grid storing each row as a `u64` bitmask, bit `n` being column `n`.
+///
+/// Only usable for grids at most [`MAX_PACKED_WIDTH`] columns wide; cells beyond the edges are dead.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PackedGrid {
+    rows: Vec<u64>,
+    row_count: usize,
+    col_count: usize,
+}
+
+impl PackedGrid {
+    /// Packs the grid, or returns `None` when it is wider than [`MAX_PACKED_WIDTH`].
+    pub fn from_grid(g: &Grid) -> Option<Self> {
+        let (row_count, col_count) = grid_dimensions(g);
+        if col_count > MAX_PACKED_WIDTH {
+            return None;
+        }
+
+        let rows = g
+            .iter()
+            .map(|row| row.iter().enumerate().fold(0, |bits, (col, &cell)| bits | (cell as u64) << col))
+            .collect();
+        Some(PackedGrid { rows, row_count, col_count })
+    }
+
+    /// Unpacks the bitmasks back into the standard grid representation.
+    pub fn to_grid(&self) -> Grid {
+        let mut grid = initialize_grid(self.row_count, self.col_count);
+        for (row, &bits) in grid.iter_mut().zip(&self.rows) {
+            for (col, cell) in row.iter_mut().enumerate() {
+                *cell = bits >> col & 1 == 1;
+            }
+        }
+        grid
+    }
+
+    /// Numbers of cells born and died going from this grid to `next`, as `(births, deaths)`.
+    pub fn births_and_deaths(&self, next: &PackedGrid) -> (u64, u64) {
+        self.rows.iter().zip(&next.rows).fold((0, 0), |(births, deaths), (&before, &after)| {
+            (births + u64::from((after & !before).count_ones()), deaths + u64::from((before & !after).count_ones()))
+        })
+    }
+
+    /// Computes the next generation.
+    pub fn step(&self) -> Self {
+        PackedGrid {
+            rows: packed_compute_next_generation(&self.rows, self.row_count, self.col_count),
+            row_count: self.row_count,
+            col_count: self.col_count,
+        }
+    }
+
+    /// Flips every cell by negating each row, keeping the bits past the last column clear.
+    pub fn inverted(&self) -> Self {
+        let mask = width_mask(self.col_count);
+        PackedGrid {
+            rows: self.rows.iter().map(|&bits| !bits & mask).collect(),
+            row_count: self.row_count,
+            col_count: self.col_count,
+        }
+    }
+}
+
+/// Bitmask with the lowest `width` bits set.
+fn width_mask(width: usize) -> u64 {
+    if width >= MAX_PACKED_WIDTH {
+        u64::MAX
+    } else {
+        (1 << width) - 1
+    }
+}
+
+/// Counts how many of the neighboring cells are alive.
+pub fn packed_count_live_neighbors(grid: &[u64], row: usize, col: usize, width: usize) -> u32 {
+    let window = (0b111_u64 << col >> 1) & width_mask(width);
+    let above = row.checked_sub(1).map_or(0, |above| grid[above]);
+    let below = grid.get(row + 1).copied().unwrap_or(0);
+    let own = grid[row] & !(1 << col);
+
+    (above & window).count_ones() + (own & window).count_ones() + (below & window).count_ones()
+}
+
+/// Computes the next generation of a packed grid, evaluating all cells of a row at once.
+///
+/// The eight neighbor masks are summed with bit-sliced adders, keeping the count modulo 8 in
+/// three bit planes. A count of 8 wraps to 0, which doesn't matter since only 2 and 3 are relevant.
+pub fn packed_compute_next_generation(current: &[u64], row_count: usize, col_count: usize) -> Vec<u64> {
+    let mask = width_mask(col_count);
+
+    (0..row_count)
+        .map(|row| {
+            let above = row.checked_sub(1).map_or(0, |above| current[above]);
+            let own = current[row];
+            let below = current.get(row + 1).copied().unwrap_or(0);
+            let neighbors = [
+                above << 1, above, above >> 1,
+                own << 1, own >> 1,
+                below << 1, below, below >> 1,
+            ];
+
+            let (mut ones, mut twos, mut fours) = (0_u64, 0_u64, 0_u64);
+            for neighbor in neighbors {
+                let carry = ones & neighbor;
+                ones ^= neighbor;
+                fours ^= twos & carry;
+                twos ^= carry;
+            }
+
+            // Survive with 2 or 3 neighbors, born with exactly 3.
+            twos & !fours & (ones | own) & mask
+        })
+        .collect()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{compute_next_generation, create_seeded_grid, SimulationConfig, Topology};
+
+    #[test]
+    fn test_round_trip() {
+        let grid = vec![
+            vec![true, false, true],
+            vec![false, true, false],
+        ];
+
+        let packed = PackedGrid::from_grid(&grid).unwrap();
+
+        assert_eq!(packed.rows, vec![0b101, 0b010]);
+        assert_eq!(packed.to_grid(), grid);
+    }
+
+    #[test]
+    fn test_inverted() {
+        let grid = vec![vec![true, false, true], vec![false, false, false]];
+
+        let inverted = PackedGrid::from_grid(&grid).unwrap().inverted();
+
+        assert_eq!(inverted.rows, vec![0b010, 0b111]);
+        assert_eq!(inverted.to_grid(), crate::game::grid_invert(&grid));
+        assert_eq!(PackedGrid::from_grid(&initialize_grid(1, 64)).unwrap().inverted().rows, vec![u64::MAX]);
+    }
+
+    #[test]
+    fn test_rejects_wide_grids() {
+        assert!(PackedGrid::from_grid(&initialize_grid(2, 64)).is_some());
+        assert!(PackedGrid::from_grid(&initialize_grid(2, 65)).is_none());
+    }
+
+    #[test]
+    fn test_packed_count_live_neighbors() {
+        let grid = [0b101, 0b010, 0b101];
+
+        assert_eq!(packed_count_live_neighbors(&grid, 1, 1, 3), 4);
+        assert_eq!(packed_count_live_neighbors(&grid, 0, 0, 3), 1);
+        assert_eq!(packed_count_live_neighbors(&grid, 0, 2, 3), 1);
+    }
+
+    #[test]
+    fn test_matches_standard_representation() {
+        for (rows, cols) in [(10, 10), (20, 64), (5, 33)] {
+            let config = SimulationConfig { grid_size: rows, grid_width: Some(cols), seed: 9, density: Some(0.4), ..Default::default() };
+            let mut grid = create_seeded_grid(&config);
+            let mut next = grid.clone();
+            let mut packed = PackedGrid::from_grid(&grid).unwrap();
+
+            for _ in 0..30 {
+                compute_next_generation(&grid, &mut next, Topology::Finite);
+                std::mem::swap(&mut grid, &mut next);
+                packed = packed.step();
+                assert_eq!(packed.to_grid(), grid);
+            }
+        }
+    }
+
+    /// Compares both representations; run with `cargo test --release -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_packed_next_generation() {
+        const GENERATIONS: usize = 2000;
+
+        let config = SimulationConfig { grid_size: 64, seed: 4, density: Some(0.35), ..Default::default() };
+        let mut grid = create_seeded_grid(&config);
+        let mut next = grid.clone();
+        let mut packed = PackedGrid::from_grid(&grid).unwrap();
+
+        let started = std::time::Instant::now();
+        for _ in 0..GENERATIONS {
+            compute_next_generation(&grid, &mut next, Topology::Finite);
+            std::mem::swap(&mut grid, &mut next);
+        }
+        let standard = started.elapsed();
+
+        let started = std::time::Instant::now();
+        for _ in 0..GENERATIONS {
+            packed = packed.step();
+        }
+        let bitboard = started.elapsed();
+
+        assert_eq!(packed.to_grid(), grid);
+        println!(
+            "64x64, {} generations: standard {:?}, packed {:?} ({:.1}x)",
+            GENERATIONS,
+            standard,
+            bitboard,
+            standard.as_secs_f64() / bitboard.as_secs_f64()
+        );
+    }
+}
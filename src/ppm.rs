@@ -0,0 +1,170 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::game::{grid_dimensions, Grid};
+
+/// Grayscale image with 8-bit brightness values, `0` black and `255` white.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GrayImage {
+    pub width: usize,
+    pub height: usize,
+    /// Brightness of each pixel, row by row from the top.
+    pub pixels: Vec<u8>,
+}
+
+impl GrayImage {
+    /// Brightness of the pixel at the given position.
+    pub fn pixel(&self, x: usize, y: usize) -> u8 {
+        self.pixels[y * self.width + x]
+    }
+}
+
+/// Reads a grayscale PGM image, either plain (P2) or binary (P5).
+pub fn load_pgm(path: &Path) -> io::Result<GrayImage> {
+    parse_pgm(&fs::read(path)?).map_err(|message| {
+        io::Error::new(io::ErrorKind::InvalidData, format!("Invalid PGM image {}: {}", path.display(), message))
+    })
+}
+
+/// Parses a PGM image, scaling its values to 8 bits whatever the maximum value in the header.
+pub fn parse_pgm(bytes: &[u8]) -> Result<GrayImage, String> {
+    let mut position = 0;
+    let mut header = Vec::new();
+    while header.len() < 4 {
+        header.push(next_token(bytes, &mut position).ok_or("truncated header")?);
+    }
+    let number = |token: &str| token.parse::<usize>().map_err(|_| format!("`{}` isn't a valid number", token));
+    let (width, height, max_value) = (number(&header[1])?, number(&header[2])?, number(&header[3])?);
+    if width == 0 || height == 0 {
+        return Err(format!("image is empty ({}x{})", width, height));
+    }
+    if !(1..=65535).contains(&max_value) {
+        return Err(format!("maximum value {} is out of range", max_value));
+    }
+
+    let count = width.checked_mul(height).ok_or("image is too large")?;
+    let values: Vec<usize> = match header[0].as_str() {
+        "P2" => (0..count)
+            .map(|_| next_token(bytes, &mut position).ok_or("missing pixels".to_string()).and_then(|token| number(&token)))
+            .collect::<Result<_, _>>()?,
+        "P5" => {
+            // A single whitespace byte separates the header from the raster
+            let raster = bytes.get(position + 1..).unwrap_or_default();
+            let bytes_per_value = if max_value > 255 { 2 } else { 1 };
+            if raster.len() < count * bytes_per_value {
+                return Err("missing pixels".to_string());
+            }
+            raster
+                .chunks(bytes_per_value)
+                .take(count)
+                .map(|value| value.iter().fold(0, |acc, &byte| acc << 8 | byte as usize))
+                .collect()
+        }
+        magic => return Err(format!("unsupported format `{}`, expected P2 or P5", magic)),
+    };
+
+    let pixels = values.into_iter().map(|value| (value.min(max_value) * 255 / max_value) as u8).collect();
+    Ok(GrayImage { width, height, pixels })
+}
+
+/// Reads the next whitespace separated header token, skipping `#` comments up to the end of the line.
+fn next_token(bytes: &[u8], position: &mut usize) -> Option<String> {
+    loop {
+        match bytes.get(*position)? {
+            byte if byte.is_ascii_whitespace() => *position += 1,
+            b'#' => {
+                while bytes.get(*position).is_some_and(|&byte| byte != b'\n') {
+                    *position += 1;
+                }
+            }
+            _ => break,
+        }
+    }
+    let start = *position;
+    while bytes.get(*position).is_some_and(|byte| !byte.is_ascii_whitespace()) {
+        *position += 1;
+    }
+    Some(String::from_utf8_lossy(&bytes[start..*position]).into_owned())
+}
+
+/// Writes the grid as a binary (P6) PPM image, drawing each cell as a `cell_size` pixel square.
+pub fn export_ppm(grid: &Grid, path: &Path, cell_size: u32, alive_rgb: [u8; 3], dead_rgb: [u8; 3]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_ppm(grid, &mut writer, cell_size, alive_rgb, dead_rgb)?;
+    writer.flush()
+}
+
+/// Writes the PPM header followed by the pixel rows, top to bottom.
+fn write_ppm(grid: &Grid, writer: &mut impl Write, cell_size: u32, alive_rgb: [u8; 3], dead_rgb: [u8; 3]) -> io::Result<()> {
+    let (rows, cols) = grid_dimensions(grid);
+    let cell_size = cell_size as usize;
+    write!(writer, "P6\n{} {}\n255\n", cols * cell_size, rows * cell_size)?;
+
+    let mut line = Vec::with_capacity(cols * cell_size * 3);
+    for row in grid {
+        line.clear();
+        for &alive in row {
+            let rgb = if alive { alive_rgb } else { dead_rgb };
+            for _ in 0..cell_size {
+                line.extend_from_slice(&rgb);
+            }
+        }
+        for _ in 0..cell_size {
+            writer.write_all(&line)?;
+        }
+    }
+    Ok(())
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: [u8; 3] = [255, 255, 255];
+    const BLACK: [u8; 3] = [0, 0, 0];
+
+    #[test]
+    fn test_export_ppm_file() {
+        let grid = vec![vec![true, false, false], vec![false, true, false]];
+        let path = std::env::temp_dir().join(format!("rusty-game-of-life-{}.ppm", std::process::id()));
+
+        export_ppm(&grid, &path, 4, WHITE, BLACK).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let header = b"P6\n12 8\n255\n";
+        assert_eq!(&bytes[..2], b"P6");
+        assert_eq!(&bytes[..header.len()], header);
+        assert_eq!(bytes.len(), header.len() + 12 * 8 * 3);
+    }
+
+    #[test]
+    fn test_write_ppm_pixels() {
+        let grid = vec![vec![true, false]];
+        let mut bytes = Vec::new();
+
+        write_ppm(&grid, &mut bytes, 1, [1, 2, 3], [4, 5, 6]).unwrap();
+
+        assert_eq!(bytes, b"P6\n2 1\n255\n\x01\x02\x03\x04\x05\x06");
+    }
+
+    #[test]
+    fn test_parse_pgm() {
+        let plain = parse_pgm(b"P2\n# a comment\n2 2\n15\n0 15\n15 0\n").unwrap();
+        let binary = parse_pgm(b"P5 2 2 255\n\x00\xff\xff\x00").unwrap();
+
+        assert_eq!(plain, GrayImage { width: 2, height: 2, pixels: vec![0, 255, 255, 0] });
+        assert_eq!(binary, plain);
+        assert!(parse_pgm(b"P5 2 2 255\n\x00").is_err());
+        assert!(parse_pgm(b"P6 1 1 255\n\x00\x00\x00").is_err());
+    }
+
+    #[test]
+    fn test_parse_pgm_rejects_empty_image() {
+        assert_eq!(parse_pgm(b"P2 0 0 255\n"), Err("image is empty (0x0)".to_string()));
+        assert!(parse_pgm(b"P5 3 0 255\n").is_err());
+        assert!(parse_pgm(b"P2 0 2 255\n").is_err());
+    }
+}
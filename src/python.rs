@@ -0,0 +1,90 @@
+//! Python module of the simulation, built into a wheel with `maturin build`.
+//!
+//! ```python
+//! from rusty_game_of_life import PySimulation
+//!
+//! simulation = PySimulation(30, "B3/S23")
+//! simulation.set_cell(1, 2, True)
+//! simulation.step()
+//! ```
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+use crate::game::{count_population, initialize_grid, Grid, Topology, Universe};
+use crate::rules::Rules;
+
+/// Square grid evolving under a chosen rule, driven from Python.
+#[pyclass]
+pub struct PySimulation {
+    universe: Universe,
+}
+
+#[pymethods]
+impl PySimulation {
+    /// Creates a `grid_size` x `grid_size` finite grid of dead cells under the given rulestring,
+    /// e.g. `B3/S23`, raising `ValueError` when it isn't valid.
+    #[new]
+    pub fn new(grid_size: usize, rules: &str) -> PyResult<Self> {
+        let rules: Rules = rules.parse().map_err(|err| PyValueError::new_err(format!("{}", err)))?;
+        let universe = Universe::new(initialize_grid(grid_size, grid_size), Topology::Finite).with_rules(rules);
+        Ok(PySimulation { universe })
+    }
+
+    /// Advances the grid by one generation.
+    pub fn step(&mut self) {
+        self.universe.step_once();
+    }
+
+    /// Rows of cells, `True` for alive.
+    pub fn get_grid(&self) -> Grid {
+        self.universe.grid().clone()
+    }
+
+    /// Brings the cell at row `r` and column `c` to life or kills it; cells outside of the grid are ignored.
+    pub fn set_cell(&mut self, r: usize, c: usize, val: bool) {
+        self.universe.set_cell(r, c, val);
+    }
+
+    /// Number of live cells.
+    pub fn live_count(&self) -> usize {
+        count_population(self.universe.grid())
+    }
+}
+
+#[pymodule]
+fn rusty_game_of_life(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PySimulation>()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glider_moves_diagonally() {
+        let mut simulation = PySimulation::new(6, "B3/S23").unwrap();
+        for (r, c) in [(0, 1), (1, 2), (2, 0), (2, 1), (2, 2)] {
+            simulation.set_cell(r, c, true);
+        }
+        let start = simulation.get_grid();
+
+        for _ in 0..4 {
+            simulation.step();
+        }
+
+        let grid = simulation.get_grid();
+        assert_eq!(simulation.live_count(), 5);
+        for r in 0..5 {
+            for c in 0..5 {
+                assert_eq!(grid[r + 1][c + 1], start[r][c], "cell ({}, {})", r, c);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rejects_invalid_rules() {
+        assert!(PySimulation::new(5, "nonsense").is_err());
+    }
+}
@@ -0,0 +1,143 @@
+use std::fs::File;
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+use serde::{Deserialize, Serialize};
+
+use crate::game::{grid_dimensions, Grid, SimulationConfig};
+use crate::grid_ops::{from_coords, live_cells};
+
+/// Serializable snapshot of a grid, storing only the coordinates of live cells.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GridState {
+    pub rows: usize,
+    pub cols: usize,
+    pub live_cells: Vec<(usize, usize)>,
+}
+
+impl GridState {
+    /// Captures the live cells of the given grid.
+    pub fn from_grid(grid: &Grid) -> Self {
+        let live_cells = live_cells(grid);
+
+        let (rows, cols) = grid_dimensions(grid);
+        GridState { rows, cols, live_cells }
+    }
+
+    /// Rebuilds the grid described by this snapshot.
+    pub fn to_grid(&self) -> io::Result<Grid> {
+        if let Some(&(row, col)) = self.live_cells.iter().find(|&&(row, col)| row >= self.rows || col >= self.cols) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Live cell ({}, {}) is outside of the {}x{} grid", row, col, self.rows, self.cols),
+            ));
+        }
+        Ok(from_coords(self.cols, self.rows, &self.live_cells))
+    }
+}
+
+/// Writes the starting point of a simulation run so it can be replayed later.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SimulationRecorder {
+    pub initial: GridState,
+    pub config: SimulationConfig,
+}
+
+impl SimulationRecorder {
+    pub fn new(config: &SimulationConfig, initial: &Grid) -> Self {
+        SimulationRecorder {
+            initial: GridState::from_grid(initial),
+            config: config.clone(),
+        }
+    }
+
+    /// Saves the recording as JSON to the given path.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+}
+
+/// Restores a recorded simulation run from a JSON file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationReplayer {
+    pub config: SimulationConfig,
+    pub initial: Grid,
+}
+
+impl SimulationReplayer {
+    /// Loads a recording previously written by [`SimulationRecorder::save`].
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let reader = BufReader::new(File::open(path)?);
+        let recording: SimulationRecorder = serde_json::from_reader(reader)?;
+        let initial = recording.initial.to_grid()?;
+
+        if recording.config.dimensions() != grid_dimensions(&initial) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Recorded grid size doesn't match the recorded configuration",
+            ));
+        }
+
+        Ok(SimulationReplayer { config: recording.config, initial })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{compute_next_generation, create_seeded_grid, initialize_grid, Topology};
+
+    #[test]
+    fn test_grid_state_round_trip() {
+        let grid = vec![
+            vec![true, false, false],
+            vec![false, false, true],
+            vec![false, true, false],
+        ];
+
+        let state = GridState::from_grid(&grid);
+
+        assert_eq!(state.live_cells, vec![(0, 0), (1, 2), (2, 1)]);
+        assert_eq!(state.to_grid().unwrap(), grid);
+    }
+
+    #[test]
+    fn test_grid_state_rejects_out_of_bounds_cells() {
+        let state = GridState { rows: 2, cols: 3, live_cells: vec![(2, 0)] };
+
+        assert!(state.to_grid().is_err());
+    }
+
+    #[test]
+    fn test_record_and_replay_round_trip() {
+        let config = SimulationConfig {
+            grid_size: 20,
+            exit_steady: false,
+            topology: Topology::Toroidal,
+            seed: 7,
+            ..Default::default()
+        };
+        let path = std::env::temp_dir().join(format!("rusty-game-of-life-record-{}.json", std::process::id()));
+
+        let mut recorded = create_seeded_grid(&config);
+        SimulationRecorder::new(&config, &recorded).save(&path).unwrap();
+        let replayer = SimulationReplayer::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(replayer.config, config);
+        let mut replayed = replayer.initial;
+        let mut recorded_next = initialize_grid(config.grid_size, config.grid_size);
+        let mut replayed_next = initialize_grid(config.grid_size, config.grid_size);
+
+        for _ in 0..20 {
+            assert_eq!(recorded, replayed);
+            compute_next_generation(&recorded, &mut recorded_next, config.topology);
+            compute_next_generation(&replayed, &mut replayed_next, replayer.config.topology);
+            std::mem::swap(&mut recorded, &mut recorded_next);
+            std::mem::swap(&mut replayed, &mut replayed_next);
+        }
+        assert_eq!(recorded, replayed);
+    }
+}
@@ -0,0 +1,253 @@
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::game::{grid_dimensions, initialize_grid, Grid};
+use crate::grid_ops::place_pattern_centered;
+
+/// Largest number of cells a pattern file may declare, so a bogus header can't allocate an
+/// enormous grid before a single cell is read.
+pub const MAX_PATTERN_CELLS: usize = 1 << 24;
+
+/// Descriptive `#N`, `#O` and `#C` lines found before the RLE header.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PatternMetadata {
+    /// Pattern name from the `#N` line.
+    pub name: Option<String>,
+    /// Author from the `#O` line.
+    pub author: Option<String>,
+    /// Text of every `#C` (or `#c`) line, in order.
+    pub comments: Vec<String>,
+}
+
+/// Pattern read from an RLE file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    pub cells: Grid,
+    pub metadata: PatternMetadata,
+}
+
+impl Pattern {
+    /// Places the pattern in the middle of an empty grid of the given dimensions.
+    ///
+    /// Returns `None` when the pattern doesn't fit.
+    pub fn centered_in(&self, rows: usize, cols: usize) -> Option<Grid> {
+        let (height, width) = grid_dimensions(&self.cells);
+        if height > rows || width > cols {
+            return None;
+        }
+        let mut grid = initialize_grid(rows, cols);
+        place_pattern_centered(&mut grid, &self.cells);
+        Some(grid)
+    }
+
+    /// Places the pattern with its top left corner at the given cell of an empty grid.
+    ///
+    /// Returns `None` when the pattern would overflow the grid.
+    pub fn placed_at(&self, rows: usize, cols: usize, top: usize, left: usize) -> Option<Grid> {
+        let (height, width) = grid_dimensions(&self.cells);
+        if top.checked_add(height)? > rows || left.checked_add(width)? > cols {
+            return None;
+        }
+
+        let mut grid = initialize_grid(rows, cols);
+        for (row, cells) in self.cells.iter().enumerate() {
+            grid[top + row][left..left + width].copy_from_slice(cells);
+        }
+        Some(grid)
+    }
+}
+
+/// Error returned for malformed RLE input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RleError {
+    /// One-based number of the offending line, `0` when the input ends before the header.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid RLE on line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for RleError {}
+
+/// Reads an RLE pattern file.
+pub fn load_rle(path: &Path) -> io::Result<Pattern> {
+    let input = fs::read_to_string(path)?;
+    parse_rle(&input).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+}
+
+/// Parses a pattern in the run length encoded format, capturing its metadata lines.
+///
+/// `#` lines other than `#N`, `#O` and `#C` are ignored, as is anything after the closing `!`.
+/// Headers declaring more than [`MAX_PATTERN_CELLS`] cells are rejected.
+pub fn parse_rle(input: &str) -> Result<Pattern, RleError> {
+    let mut metadata = PatternMetadata::default();
+    let mut lines = input.lines().enumerate().map(|(index, line)| (index + 1, line.trim()));
+
+    let (header_line, header) = loop {
+        match lines.next() {
+            Some((_, "")) => {}
+            Some((_, line)) if line.starts_with('#') => read_metadata(line, &mut metadata),
+            Some(header) => break header,
+            None => return Err(RleError { line: 0, message: "missing `x = ..., y = ...` header".to_string() }),
+        }
+    };
+    let (width, height) = parse_header(header).ok_or_else(|| RleError {
+        line: header_line,
+        message: format!("malformed header `{}`", header),
+    })?;
+    if width.checked_mul(height).is_none_or(|cells| cells > MAX_PATTERN_CELLS) {
+        return Err(RleError {
+            line: header_line,
+            message: format!("declared size {}x{} exceeds the limit of {} cells", width, height, MAX_PATTERN_CELLS),
+        });
+    }
+
+    let mut cells = initialize_grid(height, width);
+    let (mut row, mut col) = (0usize, 0usize);
+    'lines: for (number, line) in lines {
+        let mut run = String::new();
+        for symbol in line.chars() {
+            if symbol.is_ascii_digit() {
+                run.push(symbol);
+                continue;
+            }
+            let count = if run.is_empty() { 1 } else { run.parse().unwrap_or(usize::MAX) };
+            run.clear();
+
+            let error = |message: &str| RleError { line: number, message: message.to_string() };
+            match symbol {
+                'b' => col = col.saturating_add(count),
+                'o' => {
+                    if row >= height || col.saturating_add(count) > width {
+                        return Err(error("live cells outside of the declared size"));
+                    }
+                    cells[row][col..col + count].fill(true);
+                    col += count;
+                }
+                '$' => {
+                    row = row.saturating_add(count);
+                    col = 0;
+                }
+                '!' => break 'lines,
+                symbol if symbol.is_whitespace() => {}
+                _ => return Err(error(&format!("unexpected symbol `{}`", symbol))),
+            }
+        }
+    }
+
+    Ok(Pattern { cells, metadata })
+}
+
+/// Stores the content of a `#` line in the metadata if it's one of the known kinds.
+fn read_metadata(line: &str, metadata: &mut PatternMetadata) {
+    let text = |prefix: &str| line[prefix.len()..].trim().to_string();
+    if line.starts_with("#N") {
+        metadata.name = Some(text("#N"));
+    } else if line.starts_with("#O") {
+        metadata.author = Some(text("#O"));
+    } else if line.starts_with("#C") || line.starts_with("#c") {
+        metadata.comments.push(text("#C"));
+    }
+}
+
+/// Reads the pattern width and height from an `x = m, y = n, rule = ...` header.
+fn parse_header(header: &str) -> Option<(usize, usize)> {
+    let mut width = None;
+    let mut height = None;
+    for field in header.split(',') {
+        let (key, value) = field.split_once('=')?;
+        match key.trim() {
+            "x" => width = value.trim().parse().ok(),
+            "y" => height = value.trim().parse().ok(),
+            _ => {}
+        }
+    }
+    Some((width?, height?))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GLIDER: &str = "\
+#N Glider
+#O Richard K. Guy
+#C The smallest, most common, and first discovered spaceship.
+#C www.conwaylife.com/wiki/index.php?title=Glider
+x = 3, y = 3, rule = B3/S23
+bob$2bo$3o!
+";
+
+    #[test]
+    fn test_parse_rle_with_metadata() {
+        let pattern = parse_rle(GLIDER).unwrap();
+
+        assert_eq!(
+            pattern.cells,
+            vec![
+                vec![false, true, false],
+                vec![false, false, true],
+                vec![true, true, true],
+            ]
+        );
+        assert_eq!(pattern.metadata.name.as_deref(), Some("Glider"));
+        assert_eq!(pattern.metadata.author.as_deref(), Some("Richard K. Guy"));
+        assert_eq!(pattern.metadata.comments.len(), 2);
+        assert_eq!(pattern.metadata.comments[0], "The smallest, most common, and first discovered spaceship.");
+    }
+
+    #[test]
+    fn test_parse_rle_without_metadata() {
+        let pattern = parse_rle("x = 4, y = 2\n2o$\n2b2o!").unwrap();
+
+        assert_eq!(pattern.cells, vec![vec![true, true, false, false], vec![false, false, true, true]]);
+        assert_eq!(pattern.metadata, PatternMetadata::default());
+    }
+
+    #[test]
+    fn test_parse_rle_rejects_malformed_input() {
+        assert_eq!(parse_rle("#C only a comment\n").unwrap_err().line, 0);
+        assert_eq!(parse_rle("#N Bad\nx = 3\nooo!").unwrap_err().line, 2);
+        assert_eq!(parse_rle("x = 2, y = 1\n3o!").unwrap_err().line, 2);
+        assert!(parse_rle("x = 2, y = 1\noz!").is_err());
+    }
+
+    #[test]
+    fn test_parse_rle_rejects_oversized_headers() {
+        assert_eq!(parse_rle("#N Huge\nx = 100000, y = 100000\no!").unwrap_err().line, 2);
+        assert!(parse_rle("x = 18446744073709551615, y = 2\no!").is_err());
+        assert!(parse_rle("x = 4096, y = 4096\no!").is_ok());
+    }
+
+    #[test]
+    fn test_pattern_centered_in_grid() {
+        let pattern = parse_rle(GLIDER).unwrap();
+
+        let grid = pattern.centered_in(5, 7).unwrap();
+
+        assert!(grid[1][3] && grid[2][4] && grid[3][2] && grid[3][3] && grid[3][4]);
+        assert_eq!(grid.iter().flatten().filter(|&&cell| cell).count(), 5);
+        assert_eq!(pattern.centered_in(2, 10), None);
+    }
+
+    #[test]
+    fn test_pattern_placed_at_offset() {
+        let pattern = parse_rle(GLIDER).unwrap();
+
+        let grid = pattern.placed_at(6, 8, 3, 5).unwrap();
+
+        assert!(grid[3][6] && grid[4][7] && grid[5][5] && grid[5][6] && grid[5][7]);
+        assert_eq!(grid.iter().flatten().filter(|&&cell| cell).count(), 5);
+        assert_eq!(pattern.placed_at(6, 8, 4, 0), None);
+        assert_eq!(pattern.placed_at(6, 8, 0, 6), None);
+        assert_eq!(pattern.placed_at(6, 8, 0, usize::MAX), None);
+    }
+}
@@ -0,0 +1,215 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+
+/// Outer-totalistic rule deciding cell fate from its state and number of live neighbors.
+///
+/// Neighbor counts are stored as bit masks, so up to 31 neighbors are supported. Rules are
+/// written and parsed as rulestrings, e.g. `B3/S23`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Rules {
+    birth: u32,
+    survival: u32,
+}
+
+impl Rules {
+    /// Creates rules from the neighbor counts causing birth and survival.
+    pub fn new(birth: &[u32], survival: &[u32]) -> Self {
+        Rules {
+            birth: to_mask(birth),
+            survival: to_mask(survival),
+        }
+    }
+
+    /// Conway's Game of Life, `B3/S23`.
+    pub fn conway() -> Self {
+        Rules::new(&[3], &[2, 3])
+    }
+
+    /// Bays' 3D Life 5766: a cell survives with 5 to 7 neighbors and is born with 6.
+    pub fn bays_5766() -> Self {
+        Rules::new(&[6], &[5, 6, 7])
+    }
+
+    /// Returns whether the cell is alive in the next generation.
+    pub fn next_state(&self, alive: bool, live_neighbors: u32) -> bool {
+        let mask = if alive { self.survival } else { self.birth };
+        live_neighbors < u32::BITS && mask & (1 << live_neighbors) != 0
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::conway()
+    }
+}
+
+/// Decides the fate of a cell of a 2D grid, for rules that may not fit a rulestring, e.g. scripted ones.
+pub trait RuleFn {
+    /// Returns whether the cell is alive in the next generation, given its live neighbors (0 to 8).
+    fn next_cell(&self, alive: bool, neighbors: u8) -> bool;
+}
+
+impl RuleFn for Rules {
+    fn next_cell(&self, alive: bool, neighbors: u8) -> bool {
+        self.next_state(alive, u32::from(neighbors))
+    }
+}
+
+/// [`RuleFn`] that can be cloned into every universe and thread of a run, e.g. through its
+/// [`SimulationConfig`](crate::game::SimulationConfig).
+///
+/// Clones share the same rule, and only handles to the same rule compare equal.
+#[derive(Clone)]
+pub struct SharedRuleFn(Arc<dyn RuleFn + Send + Sync>);
+
+impl SharedRuleFn {
+    pub fn new(rule: impl RuleFn + Send + Sync + 'static) -> Self {
+        SharedRuleFn(Arc::new(rule))
+    }
+}
+
+impl RuleFn for SharedRuleFn {
+    fn next_cell(&self, alive: bool, neighbors: u8) -> bool {
+        self.0.next_cell(alive, neighbors)
+    }
+}
+
+impl PartialEq for SharedRuleFn {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Eq for SharedRuleFn {}
+
+impl fmt::Debug for SharedRuleFn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SharedRuleFn").finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for Rules {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let counts = |mask: u32| -> String {
+            (0..u32::BITS).filter(|count| mask & (1 << count) != 0).map(|count| count.to_string()).collect()
+        };
+        write!(f, "B{}/S{}", counts(self.birth), counts(self.survival))
+    }
+}
+
+/// Error returned for a malformed or unsupported rulestring.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseRulesError {
+    pub rulestring: String,
+}
+
+impl fmt::Display for ParseRulesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Invalid rulestring `{}`, expected e.g. `B3/S23` or `23/3`", self.rulestring)
+    }
+}
+
+impl Error for ParseRulesError {}
+
+impl FromStr for Rules {
+    type Err = ParseRulesError;
+
+    /// Parses a rulestring for a 2D grid, with neighbor counts from 0 to 8.
+    ///
+    /// Both `B3/S23` (in either order) and the older survival/birth form `23/3` are accepted; the
+    /// letters decide which convention is used.
+    fn from_str(rulestring: &str) -> Result<Self, Self::Err> {
+        let error = || ParseRulesError { rulestring: rulestring.to_string() };
+        let (first, second) = rulestring.trim().split_once('/').ok_or_else(error)?;
+
+        let lettered = |part: &str| part.starts_with(['B', 'b', 'S', 's']);
+        let (birth, survival) = match (lettered(first), lettered(second)) {
+            (true, true) => {
+                let is_birth = |part: &str| part.starts_with(['B', 'b']);
+                match (is_birth(first), is_birth(second)) {
+                    (true, false) => (&first[1..], &second[1..]),
+                    (false, true) => (&second[1..], &first[1..]),
+                    _ => return Err(error()),
+                }
+            }
+            (false, false) => (second, first),
+            _ => return Err(error()),
+        };
+
+        Ok(Rules {
+            birth: parse_counts(birth).ok_or_else(error)?,
+            survival: parse_counts(survival).ok_or_else(error)?,
+        })
+    }
+}
+
+impl TryFrom<String> for Rules {
+    type Error = ParseRulesError;
+
+    fn try_from(rulestring: String) -> Result<Self, Self::Error> {
+        rulestring.parse()
+    }
+}
+
+impl From<Rules> for String {
+    fn from(rules: Rules) -> Self {
+        rules.to_string()
+    }
+}
+
+/// Parses a run of neighbor counts such as `23` into a mask, rejecting anything but digits 0 to 8.
+fn parse_counts(digits: &str) -> Option<u32> {
+    digits.chars().try_fold(0, |mask, digit| match digit.to_digit(10) {
+        Some(count) if count <= 8 => Some(mask | 1 << count),
+        _ => None,
+    })
+}
+
+fn to_mask(counts: &[u32]) -> u32 {
+    counts.iter().filter(|&&count| count < u32::BITS).fold(0, |mask, &count| mask | 1 << count)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_state() {
+        let rules = Rules::new(&[3], &[2, 3]);
+
+        assert!(rules.next_state(false, 3));
+        assert!(!rules.next_state(false, 2));
+        assert!(rules.next_state(true, 2));
+        assert!(!rules.next_state(true, 4));
+        assert!(!rules.next_state(true, 40));
+    }
+
+    #[test]
+    fn test_parse_rulestring_conventions() {
+        for rulestring in ["B3/S23", "23/3", "S23/B3", "b3/s23"] {
+            assert_eq!(rulestring.parse::<Rules>(), Ok(Rules::conway()), "{}", rulestring);
+        }
+        assert_eq!("B36/S23".parse::<Rules>(), Ok(Rules::new(&[3, 6], &[2, 3])));
+        assert_eq!("/3".parse::<Rules>(), Ok(Rules::new(&[3], &[])));
+    }
+
+    #[test]
+    fn test_parse_rulestring_rejects_nonsense() {
+        for rulestring in ["B9", "B9/S23", "B3/23", "B3/B3", "S23", "x/y", ""] {
+            assert!(rulestring.parse::<Rules>().is_err(), "{}", rulestring);
+        }
+    }
+
+    #[test]
+    fn test_rules_display_round_trip() {
+        let rules = Rules::new(&[3, 6], &[2, 3]);
+
+        assert_eq!(rules.to_string(), "B36/S23");
+        assert_eq!(rules.to_string().parse::<Rules>(), Ok(rules));
+    }
+}
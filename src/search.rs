@@ -0,0 +1,187 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::game::{create_seeded_grid, run_headless, Grid, SimulationConfig, Universe};
+use crate::grid_ops::{bounding_box, live_cells, BoundingBox};
+use crate::metrics::SimulationOutcome;
+
+/// Longest spaceship period looked for; covers the glider and the light, middle and heavy weight spaceships.
+pub const MAX_SPACESHIP_PERIOD: usize = 4;
+
+/// Live cells of a pattern relative to its bounding box, together with the box's top left corner.
+type Shape = ((usize, usize), Vec<(usize, usize)>);
+
+/// What a random soup settled into.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SoupClass {
+    /// Every cell died.
+    Extinct,
+    /// The grid stopped changing.
+    StillLife,
+    /// The grid repeats itself every `period` generations.
+    Oscillator(usize),
+    /// The whole pattern reappears shifted every `period` generations.
+    Spaceship(usize),
+    /// The soup was still evolving when the iteration limit was reached.
+    Unsettled,
+}
+
+impl fmt::Display for SoupClass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SoupClass::Extinct => write!(f, "extinct"),
+            SoupClass::StillLife => write!(f, "still-life"),
+            SoupClass::Oscillator(period) => write!(f, "oscillator-p{}", period),
+            SoupClass::Spaceship(period) => write!(f, "spaceship-p{}", period),
+            SoupClass::Unsettled => write!(f, "unsettled"),
+        }
+    }
+}
+
+/// Seeds and classified outcomes of every soup of a search, in the order they ran.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SearchReport {
+    pub master_seed: u64,
+    pub soups: Vec<(u64, SoupClass)>,
+}
+
+impl SearchReport {
+    /// Number of soups that ended in each class.
+    pub fn tally(&self) -> BTreeMap<SoupClass, usize> {
+        let mut tally = BTreeMap::new();
+        for &(_, class) in &self.soups {
+            *tally.entry(class).or_insert(0) += 1;
+        }
+        tally
+    }
+
+    /// Classes reached by the fewest soups, each with the seeds that produced it.
+    pub fn rarest(&self) -> Vec<(SoupClass, Vec<u64>)> {
+        let tally = self.tally();
+        let Some(&fewest) = tally.values().min() else {
+            return Vec::new();
+        };
+        tally
+            .into_iter()
+            .filter(|&(_, count)| count == fewest)
+            .map(|(class, _)| {
+                let seeds = self.soups.iter().filter(|&&(_, soup)| soup == class).map(|&(seed, _)| seed).collect();
+                (class, seeds)
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for SearchReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Searched {} soups from master seed {}:", self.soups.len(), self.master_seed)?;
+        for (class, count) in self.tally() {
+            writeln!(f, "  {:<16}{}", class.to_string(), count)?;
+        }
+        writeln!(f, "Rarest outcomes:")?;
+        for (class, seeds) in self.rarest() {
+            let seeds: Vec<String> = seeds.iter().map(u64::to_string).collect();
+            writeln!(f, "  {:<16}seeds {}", class.to_string(), seeds.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs `count` randomly seeded soups headless and classifies how each one ended.
+///
+/// The soup seeds are drawn from `master_seed`, so the same master seed always gives the same
+/// report; any soup can be rerun on its own with `--seed`. The seed of `config` is ignored.
+pub fn soup_search(config: &SimulationConfig, count: usize, master_seed: u64) -> SearchReport {
+    let mut rng = StdRng::seed_from_u64(master_seed);
+    let soups = (0..count)
+        .map(|_| {
+            let config = SimulationConfig { seed: rng.gen(), ..config.clone() };
+            let (outcome, last) = run_headless(&config, create_seeded_grid(&config), config.iteration_limit());
+            (config.seed, classify_soup(&config, outcome, last))
+        })
+        .collect();
+    SearchReport { master_seed, soups }
+}
+
+/// Classifies the outcome of a headless run, checking whether the final grid is a spaceship.
+pub fn classify_soup(config: &SimulationConfig, outcome: SimulationOutcome, last: Grid) -> SoupClass {
+    if let SimulationOutcome::Extinct { .. } = outcome {
+        return SoupClass::Extinct;
+    }
+    if let Some(period) = spaceship_period(config, last) {
+        return SoupClass::Spaceship(period);
+    }
+    match outcome {
+        SimulationOutcome::SteadyState { period: 1, .. } => SoupClass::StillLife,
+        SimulationOutcome::SteadyState { period, .. } => SoupClass::Oscillator(period),
+        _ => SoupClass::Unsettled,
+    }
+}
+
+/// Returns the period after which the live cells reappear unchanged but shifted, if within [`MAX_SPACESHIP_PERIOD`].
+fn spaceship_period(config: &SimulationConfig, grid: Grid) -> Option<usize> {
+    let (origin, shape) = normalized_shape(&grid)?;
+    let mut universe = Universe::new(grid, config.topology).with_rules(config.rule).with_rule_fn(config.rule_fn.clone());
+    for period in 1..=MAX_SPACESHIP_PERIOD {
+        universe.step_once();
+        if let Some((moved, moved_shape)) = normalized_shape(universe.grid()) {
+            if moved_shape == shape && moved != origin {
+                return Some(period);
+            }
+        }
+    }
+    None
+}
+
+/// Finds the shape of the live cells, `None` when there are none.
+fn normalized_shape(grid: &Grid) -> Option<Shape> {
+    let cells = live_cells(grid);
+    let BoundingBox { top, left, .. } = bounding_box(grid)?;
+    Some(((top, left), cells.into_iter().map(|(row, col)| (row - top, col - left)).collect()))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::parse_grid;
+
+    #[test]
+    fn test_soup_search_is_reproducible() {
+        let config = SimulationConfig { grid_size: 12, max_iterations: Some(200), ..Default::default() };
+
+        let first = soup_search(&config, 8, 42);
+        let second = soup_search(&config, 8, 42);
+
+        assert_eq!(first, second);
+        assert_eq!(first.soups.len(), 8);
+        assert_eq!(first.tally().values().sum::<usize>(), 8);
+        assert_ne!(soup_search(&config, 8, 43).soups, first.soups);
+    }
+
+    #[test]
+    fn test_classify_soup() {
+        let config = SimulationConfig { grid_size: 8, ..Default::default() };
+        let block = parse_grid("....\n.##.\n.##.\n....").unwrap();
+        let glider = parse_grid(".#......\n..#.....\n###.....\n........\n........\n........\n........\n........").unwrap();
+
+        let still = SimulationOutcome::SteadyState { iteration: 3, period: 1 };
+        let unsettled = SimulationOutcome::MaxIterations { iteration: 10 };
+        assert_eq!(classify_soup(&config, still, block.clone()), SoupClass::StillLife);
+        assert_eq!(classify_soup(&config, SimulationOutcome::Extinct { iteration: 4 }, block), SoupClass::Extinct);
+        assert_eq!(classify_soup(&config, unsettled, glider), SoupClass::Spaceship(4));
+    }
+
+    #[test]
+    fn test_rarest_outcomes() {
+        let report = SearchReport {
+            master_seed: 0,
+            soups: vec![(1, SoupClass::StillLife), (2, SoupClass::Oscillator(2)), (3, SoupClass::StillLife)],
+        };
+
+        assert_eq!(report.rarest(), vec![(SoupClass::Oscillator(2), vec![2])]);
+        assert!(report.to_string().contains("oscillator-p2   seeds 2"));
+    }
+}
@@ -0,0 +1,174 @@
+//! HTTP API for running simulations remotely, started with `--server`.
+//!
+//! | Request                              | Effect                                         |
+//! |--------------------------------------|------------------------------------------------|
+//! | `POST /simulations`                  | creates a simulation, returns its id           |
+//! | `GET /simulations/{id}`              | returns the current grid                       |
+//! | `POST /simulations/{id}/step`        | advances one generation, returns the grid      |
+//! | `POST /simulations/{id}/step/{n}`    | advances `n` generations, returns the grid     |
+//! | `DELETE /simulations/{id}`           | removes the simulation                         |
+//!
+//! Requests for grids larger than [`MAX_GRID_SIZE`], steps of more than [`MAX_STEP_GENERATIONS`]
+//! generations or live cells outside of the grid are rejected with `400 Bad Request`.
+
+use std::collections::HashMap;
+use std::io;
+use std::sync::{Arc, Mutex};
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use uuid::Uuid;
+
+use crate::builder::SimulationBuilder;
+use crate::game::{count_population, Grid, Universe};
+use crate::grid_ops::from_coords;
+use crate::rules::Rules;
+
+/// Largest number of rows and columns of a simulation.
+pub const MAX_GRID_SIZE: usize = 1000;
+/// Largest number of generations a single step request advances.
+pub const MAX_STEP_GENERATIONS: u64 = 10_000;
+
+/// Simulations being run by the server, by id, each behind its own lock so that stepping one
+/// doesn't hold up requests for the others.
+pub type Simulations = Arc<Mutex<HashMap<Uuid, Arc<Mutex<Universe>>>>>;
+
+/// Status and message of a rejected request.
+type Rejection = (StatusCode, String);
+
+/// Body of `POST /simulations`; only `grid_size` is required.
+#[derive(Clone, Debug, Deserialize)]
+pub struct CreateSimulation {
+    pub grid_size: usize,
+    /// Rule of the grid, Conway's `B3/S23` when left out.
+    #[serde(default)]
+    pub rules: Rules,
+    /// Seed of the random initial grid.
+    #[serde(default)]
+    pub seed: u64,
+    /// `(row, col)` coordinates of the live cells to start from instead of a seeded grid.
+    #[serde(default)]
+    pub live_cells: Option<Vec<(usize, usize)>>,
+}
+
+/// Response to `POST /simulations`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CreatedSimulation {
+    pub id: Uuid,
+    pub grid_size: usize,
+    pub rules: Rules,
+}
+
+/// Current state of a simulation, returned by the `GET` and `step` requests.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SimulationState {
+    pub id: Uuid,
+    pub generation: u64,
+    pub live_count: usize,
+    pub grid: Grid,
+}
+
+/// Routes of the API, sharing one set of simulations.
+pub fn router() -> Router {
+    Router::new()
+        .route("/simulations", post(create))
+        .route("/simulations/{id}", get(show).delete(remove))
+        .route("/simulations/{id}/step", post(step))
+        .route("/simulations/{id}/step/{n}", post(step_many))
+        .with_state(Simulations::default())
+}
+
+/// Serves the API on the given port of the loopback interface until the process is stopped.
+pub async fn serve(port: u16) -> io::Result<()> {
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await?;
+    info!("Serving simulations on http://{}", listener.local_addr()?);
+    axum::serve(listener, router()).await
+}
+
+async fn create(
+    State(simulations): State<Simulations>,
+    Json(request): Json<CreateSimulation>,
+) -> Result<(StatusCode, Json<CreatedSimulation>), Rejection> {
+    let size = request.grid_size;
+    if size > MAX_GRID_SIZE {
+        return Err(bad_request(format!("Grid size must be at most {}, but got {}", MAX_GRID_SIZE, size)));
+    }
+    let builder = SimulationBuilder::new().grid_size(size).rule(request.rules).seed(request.seed);
+    let builder = match &request.live_cells {
+        Some(cells) => {
+            if let Some(&(row, col)) = cells.iter().find(|&&(row, col)| row >= size || col >= size) {
+                return Err(bad_request(format!("Live cell ({}, {}) is outside of the {}x{} grid", row, col, size, size)));
+            }
+            builder.initial(from_coords(size, size, cells))
+        }
+        None => builder,
+    };
+    let universe = builder.build().map_err(|err| bad_request(err.to_string()))?;
+
+    let id = Uuid::new_v4();
+    simulations.lock().expect("Simulations lock poisoned").insert(id, Arc::new(Mutex::new(universe)));
+    Ok((StatusCode::CREATED, Json(CreatedSimulation { id, grid_size: size, rules: request.rules })))
+}
+
+async fn show(State(simulations): State<Simulations>, Path(id): Path<Uuid>) -> Result<Json<SimulationState>, Rejection> {
+    advance(&simulations, id, 0).await
+}
+
+async fn step(State(simulations): State<Simulations>, Path(id): Path<Uuid>) -> Result<Json<SimulationState>, Rejection> {
+    advance(&simulations, id, 1).await
+}
+
+async fn step_many(
+    State(simulations): State<Simulations>,
+    Path((id, generations)): Path<(Uuid, u64)>,
+) -> Result<Json<SimulationState>, Rejection> {
+    if generations > MAX_STEP_GENERATIONS {
+        return Err(bad_request(format!(
+            "At most {} generations can be stepped at once, but got {}",
+            MAX_STEP_GENERATIONS, generations
+        )));
+    }
+    advance(&simulations, id, generations).await
+}
+
+async fn remove(State(simulations): State<Simulations>, Path(id): Path<Uuid>) -> StatusCode {
+    match simulations.lock().expect("Simulations lock poisoned").remove(&id) {
+        Some(_) => StatusCode::NO_CONTENT,
+        None => StatusCode::NOT_FOUND,
+    }
+}
+
+/// Steps the simulation the given number of generations and returns its new state.
+///
+/// The stepping runs on tokio's blocking pool, holding only the lock of this simulation.
+async fn advance(simulations: &Simulations, id: Uuid, generations: u64) -> Result<Json<SimulationState>, Rejection> {
+    let universe = simulations
+        .lock()
+        .expect("Simulations lock poisoned")
+        .get(&id)
+        .cloned()
+        .ok_or((StatusCode::NOT_FOUND, format!("No simulation with id {}", id)))?;
+    let state = tokio::task::spawn_blocking(move || {
+        let mut universe = universe.lock().expect("Simulation lock poisoned");
+        for _ in 0..generations {
+            universe.step_once();
+        }
+        SimulationState {
+            id,
+            generation: universe.generation(),
+            live_count: count_population(universe.grid()),
+            grid: universe.grid().clone(),
+        }
+    })
+    .await
+    .map_err(|err| (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()))?;
+    Ok(Json(state))
+}
+
+fn bad_request(message: String) -> Rejection {
+    (StatusCode::BAD_REQUEST, message)
+}
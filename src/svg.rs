@@ -0,0 +1,181 @@
+use std::io::{self, Write};
+
+use crate::game::{grid_dimensions, Grid};
+
+/// Fill of live cells.
+const ALIVE_FILL: &str = "#ffffff";
+/// Background fill, showing through where cells are dead.
+const DEAD_FILL: &str = "#000000";
+/// Id of the timing element every frame starts from, restarting itself at the end of each cycle.
+const CYCLE_ID: &str = "cycle";
+
+/// Renders the frames as a self-contained SVG that plays them in a loop using SMIL animations.
+///
+/// See [`SvgAnimationWriter`] for the layout of the document.
+pub fn export_svg_animation(frames: &[Grid], delay_ms: u64, cell_size: u32) -> String {
+    let mut animation = SvgAnimationWriter::new(Vec::new(), delay_ms, cell_size);
+    // Writing to a Vec never fails
+    for frame in frames {
+        let _ = animation.push(frame);
+    }
+    let svg = animation.finish().unwrap_or_default();
+    String::from_utf8(svg).expect("the SVG is built from UTF-8 text")
+}
+
+/// Writes a looping SVG animation frame by frame, so no more than one frame is held at a time.
+///
+/// Every frame is a hidden group of `<rect>`s for its live cells, made visible for `delay_ms` by a
+/// `<set>` that begins that long after the previous frame's within a cycle. The cycle is a `<set>`
+/// restarting itself whenever it ends; its duration is only known, and written, once the last
+/// frame is in. A single frame is drawn without any animation.
+pub struct SvgAnimationWriter<W: Write> {
+    writer: W,
+    delay_ms: u64,
+    cell_size: u32,
+    /// First frame, held back until another one shows whether it needs animating.
+    first: Option<Grid>,
+    frames: u64,
+}
+
+impl<W: Write> SvgAnimationWriter<W> {
+    pub fn new(writer: W, delay_ms: u64, cell_size: u32) -> Self {
+        SvgAnimationWriter { writer, delay_ms, cell_size, first: None, frames: 0 }
+    }
+
+    /// Number of frames pushed so far.
+    pub fn len(&self) -> u64 {
+        self.frames
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames == 0
+    }
+
+    /// Adds the next frame; every frame is expected to have the dimensions of the first one.
+    pub fn push(&mut self, frame: &Grid) -> io::Result<()> {
+        match self.frames {
+            0 => {
+                self.write_header(grid_dimensions(frame))?;
+                self.first = Some(frame.clone());
+            }
+            1 => {
+                if let Some(first) = self.first.take() {
+                    self.write_frame(&first, Some(0))?;
+                }
+                self.write_frame(frame, Some(1))?;
+            }
+            index => self.write_frame(frame, Some(index))?,
+        }
+        self.frames += 1;
+        Ok(())
+    }
+
+    /// Writes the end of the document and returns the underlying writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        match self.frames {
+            0 => self.write_header((0, 0))?,
+            1 => {
+                if let Some(first) = self.first.take() {
+                    self.write_frame(&first, None)?;
+                }
+            }
+            frames => writeln!(
+                self.writer,
+                r#"<g><set id="{}" attributeName="visibility" to="visible" begin="0s;{}.end" dur="{}"/></g>"#,
+                CYCLE_ID,
+                CYCLE_ID,
+                format_seconds(frames * self.delay_ms)
+            )?,
+        }
+        writeln!(self.writer, "</svg>")?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    fn write_header(&mut self, (rows, cols): (usize, usize)) -> io::Result<()> {
+        let (width, height) = (cols as u64 * self.cell_size as u64, rows as u64 * self.cell_size as u64);
+        writeln!(
+            self.writer,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = width,
+            h = height
+        )?;
+        writeln!(self.writer, r#"<rect width="100%" height="100%" fill="{}"/>"#, DEAD_FILL)
+    }
+
+    /// Writes the group of a frame, shown in its slot of the cycle if it has an index, or always otherwise.
+    fn write_frame(&mut self, frame: &Grid, index: Option<u64>) -> io::Result<()> {
+        let visibility = if index.is_some() { "hidden" } else { "visible" };
+        writeln!(self.writer, r#"<g fill="{}" visibility="{}">"#, ALIVE_FILL, visibility)?;
+        if let Some(index) = index {
+            writeln!(
+                self.writer,
+                r#"<set attributeName="visibility" to="visible" begin="{}.begin+{}" dur="{}"/>"#,
+                CYCLE_ID,
+                format_seconds(index * self.delay_ms),
+                format_seconds(self.delay_ms)
+            )?;
+        }
+        for (row, cells) in frame.iter().enumerate() {
+            for (col, _) in cells.iter().enumerate().filter(|(_, &alive)| alive) {
+                writeln!(
+                    self.writer,
+                    r#"<rect x="{}" y="{}" width="{size}" height="{size}"/>"#,
+                    col as u64 * self.cell_size as u64,
+                    row as u64 * self.cell_size as u64,
+                    size = self.cell_size
+                )?;
+            }
+        }
+        writeln!(self.writer, "</g>")
+    }
+}
+
+/// Formats a duration in milliseconds as an SVG clock value in seconds, e.g. `0.4s`.
+fn format_seconds(milliseconds: u64) -> String {
+    format!("{}s", milliseconds as f64 / 1000.0)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blinker_animation() {
+        let horizontal = vec![vec![false; 3], vec![true; 3], vec![false; 3]];
+        let vertical = vec![vec![false, true, false]; 3];
+        let delay_ms = 200;
+
+        let svg = export_svg_animation(&[horizontal, vertical], delay_ms, 10);
+
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="30" height="30""#));
+        assert!(svg.contains(r#"begin="0s;cycle.end""#));
+        assert!(svg.contains(&format!(r#"dur="{}s""#, (2 * delay_ms) as f64 / 1000.0)));
+        assert_eq!(svg.matches("<rect x=").count(), 6);
+        assert!(svg.contains(r#"begin="cycle.begin+0.2s" dur="0.2s""#));
+        assert!(svg.trim_end().ends_with("</svg>"));
+    }
+
+    #[test]
+    fn test_single_frame_is_static() {
+        let svg = export_svg_animation(&[vec![vec![true]]], 100, 4);
+
+        assert!(!svg.contains("<set"));
+        assert!(svg.contains(r#"<rect x="0" y="0" width="4" height="4"/>"#));
+    }
+
+    #[test]
+    fn test_frames_are_written_as_they_are_pushed() {
+        let mut animation = SvgAnimationWriter::new(Vec::new(), 100, 1);
+        animation.push(&vec![vec![true]]).unwrap();
+        animation.push(&vec![vec![false]]).unwrap();
+        let written = animation.writer.len();
+        animation.push(&vec![vec![true]]).unwrap();
+
+        assert!(animation.writer.len() > written);
+        let svg = String::from_utf8(animation.finish().unwrap()).unwrap();
+        assert_eq!(svg.matches(r#"<set attributeName="visibility""#).count(), 3);
+        assert!(svg.contains(r#"dur="0.3s""#));
+    }
+}
@@ -0,0 +1,197 @@
+use std::env;
+use clap::ValueEnum;
+use terminal_size::{terminal_size, Height, Width};
+
+/// Number of terminal columns used to render a single cell.
+const COLUMNS_PER_CELL: usize = 2;
+/// Number of terminal rows reserved below the grid for the status line.
+const STATUS_LINES: usize = 1;
+
+/// Terminal dimensions in character columns and rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TerminalSize {
+    pub columns: usize,
+    pub rows: usize,
+}
+
+/// Color capabilities of the terminal.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColorMode {
+    #[default]
+    NoColor,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+/// When to color the output, as chosen on the command line.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum ColorChoice {
+    /// Use colors when the environment supports them.
+    #[default]
+    Auto,
+    /// Always use colors, at least the basic 16.
+    Always,
+    /// Never use colors.
+    Never,
+}
+
+impl ColorChoice {
+    /// Decides the color mode to use given the detected terminal support.
+    pub fn resolve(self, detected: ColorMode) -> ColorMode {
+        match (self, detected) {
+            (ColorChoice::Never, _) => ColorMode::NoColor,
+            (ColorChoice::Always, ColorMode::NoColor) => ColorMode::Ansi16,
+            (_, detected) => detected,
+        }
+    }
+}
+
+/// Detects the color support of the terminal from the environment.
+///
+/// Honors `FORCE_COLOR` and the `NO_COLOR` convention (<https://no-color.org>), then looks at
+/// `COLORTERM` and `TERM`.
+pub fn detect_color_support() -> ColorMode {
+    color_support_from(|name| env::var(name).ok())
+}
+
+/// Detects the color support using `var` to look up environment variables.
+fn color_support_from(var: impl Fn(&str) -> Option<String>) -> ColorMode {
+    let forced = var("FORCE_COLOR").is_some_and(|value| value != "0" && value != "false");
+    let term = var("TERM").unwrap_or_default();
+    if !forced && (var("NO_COLOR").is_some_and(|value| !value.is_empty()) || term == "dumb") {
+        return ColorMode::NoColor;
+    }
+
+    if var("COLORTERM").is_some_and(|value| value == "truecolor" || value == "24bit") {
+        ColorMode::TrueColor
+    } else if term.contains("256color") {
+        ColorMode::Ansi256
+    } else {
+        ColorMode::Ansi16
+    }
+}
+
+/// Queries the size of the terminal attached to stdout, if any.
+pub fn query_terminal_size() -> Option<TerminalSize> {
+    terminal_size().map(|(Width(columns), Height(rows))| TerminalSize {
+        columns: columns as usize,
+        rows: rows as usize,
+    })
+}
+
+/// Largest grid, as `(rows, cols)`, that can be rendered in a terminal of the given size.
+pub fn fit_grid_dimensions(terminal: TerminalSize) -> (usize, usize) {
+    (terminal.rows.saturating_sub(STATUS_LINES), terminal.columns / COLUMNS_PER_CELL)
+}
+
+/// Largest square grid that can be rendered in a terminal of the given size.
+pub fn max_grid_size(terminal: TerminalSize) -> usize {
+    let (rows, cols) = fit_grid_dimensions(terminal);
+    rows.min(cols)
+}
+
+/// Checks that a grid of the given dimensions fits in the terminal, so the in-place animation doesn't garble.
+pub fn ensure_grid_fits((rows, cols): (usize, usize), terminal: TerminalSize) -> Result<(), String> {
+    let (max_rows, max_cols) = fit_grid_dimensions(terminal);
+    if rows <= max_rows && cols <= max_cols {
+        return Ok(());
+    }
+
+    Err(format!(
+        "Grid of {}x{} cells needs a terminal of at least {}x{}, but the current one is {}x{}. \
+         Try a smaller --grid-size (at most {}), --fit or enlarge the window.",
+        rows,
+        cols,
+        cols * COLUMNS_PER_CELL,
+        rows + STATUS_LINES,
+        terminal.columns,
+        terminal.rows,
+        max_grid_size(terminal),
+    ))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_max_grid_size() {
+        assert_eq!(max_grid_size(TerminalSize { columns: 80, rows: 24 }), 23);
+        assert_eq!(max_grid_size(TerminalSize { columns: 40, rows: 50 }), 20);
+        assert_eq!(max_grid_size(TerminalSize { columns: 0, rows: 0 }), 0);
+    }
+
+    #[test]
+    fn test_fit_grid_dimensions() {
+        assert_eq!(fit_grid_dimensions(TerminalSize { columns: 80, rows: 24 }), (23, 40));
+        assert_eq!(fit_grid_dimensions(TerminalSize { columns: 201, rows: 51 }), (50, 100));
+        assert_eq!(fit_grid_dimensions(TerminalSize { columns: 1, rows: 0 }), (0, 0));
+    }
+
+    #[test]
+    fn test_ensure_grid_fits() {
+        let terminal = TerminalSize { columns: 60, rows: 31 };
+
+        assert!(ensure_grid_fits((30, 30), terminal).is_ok());
+        assert!(ensure_grid_fits((31, 31), terminal).is_err());
+        assert!(ensure_grid_fits((20, 31), terminal).is_err());
+    }
+
+    /// Detects the color support from the given `(name, value)` environment.
+    fn detect(vars: &[(&str, &str)]) -> ColorMode {
+        color_support_from(|name| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string()))
+    }
+
+    #[test]
+    fn test_color_support_detection() {
+        assert_eq!(detect(&[("NO_COLOR", "1"), ("COLORTERM", "truecolor")]), ColorMode::NoColor);
+        assert_eq!(detect(&[("TERM", "dumb")]), ColorMode::NoColor);
+        assert_eq!(detect(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]), ColorMode::TrueColor);
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), ColorMode::Ansi256);
+        assert_eq!(detect(&[("TERM", "xterm")]), ColorMode::Ansi16);
+        assert_eq!(detect(&[]), ColorMode::Ansi16);
+    }
+
+    #[test]
+    fn test_force_color_overrides_no_color() {
+        assert_eq!(detect(&[("NO_COLOR", "1"), ("FORCE_COLOR", "1")]), ColorMode::Ansi16);
+        assert_eq!(detect(&[("TERM", "dumb"), ("FORCE_COLOR", "1"), ("COLORTERM", "truecolor")]), ColorMode::TrueColor);
+        assert_eq!(detect(&[("NO_COLOR", "1"), ("FORCE_COLOR", "0")]), ColorMode::NoColor);
+        // An empty NO_COLOR doesn't disable colors
+        assert_eq!(detect(&[("NO_COLOR", "")]), ColorMode::Ansi16);
+    }
+
+    #[test]
+    fn test_detect_color_support_reads_process_environment() {
+        // The only test touching these variables, so it doesn't race with the others
+        for name in ["FORCE_COLOR", "COLORTERM"] {
+            env::remove_var(name);
+        }
+        env::set_var("TERM", "xterm-256color");
+        env::set_var("NO_COLOR", "1");
+        assert_eq!(detect_color_support(), ColorMode::NoColor);
+
+        env::remove_var("NO_COLOR");
+        assert_eq!(detect_color_support(), ColorMode::Ansi256);
+    }
+
+    #[test]
+    fn test_color_choice_resolve() {
+        assert_eq!(ColorChoice::Auto.resolve(ColorMode::NoColor), ColorMode::NoColor);
+        assert_eq!(ColorChoice::Auto.resolve(ColorMode::Ansi256), ColorMode::Ansi256);
+        assert_eq!(ColorChoice::Always.resolve(ColorMode::NoColor), ColorMode::Ansi16);
+        assert_eq!(ColorChoice::Always.resolve(ColorMode::TrueColor), ColorMode::TrueColor);
+        assert_eq!(ColorChoice::Never.resolve(ColorMode::TrueColor), ColorMode::NoColor);
+    }
+
+    #[test]
+    fn test_ensure_grid_fits_suggests_smaller_size() {
+        let terminal = TerminalSize { columns: 40, rows: 20 };
+
+        let error = ensure_grid_fits((30, 30), terminal).unwrap_err();
+
+        assert!(error.contains("at most 19"));
+    }
+}
@@ -0,0 +1,82 @@
+use std::fmt::Write;
+
+use crate::game::{grid_dimensions, Grid};
+
+/// Renders the grid as a TikZ picture, each live cell a filled black square of `cell_size_cm`.
+///
+/// The cell borders are drawn as a light gray grid. With `standalone` the picture is wrapped in a
+/// complete LaTeX document using the `standalone` class, which compiles on its own; otherwise only
+/// the `tikzpicture` environment is returned, for pasting into a document loading TikZ.
+pub fn export_tikz(grid: &Grid, cell_size_cm: f64, standalone: bool) -> String {
+    let (rows, cols) = grid_dimensions(grid);
+    let mut output = String::new();
+    if standalone {
+        output.push_str("\\documentclass[tikz]{standalone}\n\\begin{document}\n");
+    }
+    output.push_str("\\begin{tikzpicture}\n");
+    // Writing to a String never fails
+    let _ = writeln!(
+        output,
+        "  \\draw[white!90!black, step={size}] (0, 0) grid ({}, {});",
+        centimeters(cols as f64 * cell_size_cm),
+        centimeters(rows as f64 * cell_size_cm),
+        size = centimeters(cell_size_cm)
+    );
+    for (row, cells) in grid.iter().enumerate() {
+        let y = (rows - row - 1) as f64 * cell_size_cm;
+        for (col, _) in cells.iter().enumerate().filter(|(_, &alive)| alive) {
+            let x = col as f64 * cell_size_cm;
+            let _ = writeln!(
+                output,
+                "  \\fill[black] ({}, {}) rectangle ({}, {});",
+                centimeters(x),
+                centimeters(y),
+                centimeters(x + cell_size_cm),
+                centimeters(y + cell_size_cm)
+            );
+        }
+    }
+    output.push_str("\\end{tikzpicture}\n");
+    if standalone {
+        output.push_str("\\end{document}\n");
+    }
+    output
+}
+
+/// Formats a length without floating point noise or trailing zeros, e.g. `0.9` rather than `0.8999999999999999`.
+fn centimeters(value: f64) -> String {
+    let text = format!("{:.4}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" { "0".to_string() } else { text.to_string() }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_tikz_fills_live_cells() {
+        let grid = vec![vec![true, true], vec![true, true]];
+
+        let tikz = export_tikz(&grid, 0.3, false);
+
+        assert_eq!(tikz.matches("\\fill").count(), 4);
+        assert!(tikz.starts_with("\\begin{tikzpicture}"));
+        assert!(tikz.contains("\\fill[black] (0.3, 0) rectangle (0.6, 0.3);"));
+        assert!(!tikz.contains("\\documentclass"));
+    }
+
+    #[test]
+    fn test_export_tikz_standalone_document() {
+        let grid = vec![vec![false, true, false], vec![false; 3], vec![false; 3], vec![false; 3]];
+
+        let tikz = export_tikz(&grid, 0.3, true);
+
+        assert!(tikz.starts_with("\\documentclass[tikz]{standalone}"));
+        assert!(tikz.trim_end().ends_with("\\end{document}"));
+        // The top row sits highest, three cells above the origin
+        assert!(tikz.contains("\\fill[black] (0.3, 0.9) rectangle (0.6, 1.2);"));
+        assert!(tikz.contains("grid (0.9, 1.2);"));
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::VecDeque;
+
+use crate::game::{Grid, Universe};
+
+/// Number of past grids kept for stepping backward unless set otherwise.
+pub const DEFAULT_HISTORY_SIZE: usize = 100;
+
+/// Past and undone grid states of a universe, for stepping backward and forward through a run.
+///
+/// Stepping backward restores a saved grid instead of recomputing anything, and stepping forward
+/// replays undone states before computing new generations, so the run itself never changes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimeTravelState {
+    /// Grids before the current one, oldest first, at most `history_size` of them.
+    pub past: VecDeque<Grid>,
+    /// Grids undone by stepping backward, the next one last.
+    pub future: Vec<Grid>,
+    history_size: usize,
+}
+
+impl TimeTravelState {
+    pub fn new(history_size: usize) -> Self {
+        TimeTravelState { past: VecDeque::with_capacity(history_size), future: Vec::new(), history_size }
+    }
+
+    /// Advances the universe by one generation, replaying an undone state when there is one.
+    pub fn step_forward(&mut self, universe: &mut Universe) {
+        self.remember(universe.grid().clone());
+        match self.future.pop() {
+            Some(grid) => universe.restore(grid, universe.generation() + 1),
+            None => universe.step_once(),
+        }
+    }
+
+    /// Moves the universe back to the previous generation, returning `false` when none is saved.
+    pub fn step_back(&mut self, universe: &mut Universe) -> bool {
+        let Some(grid) = self.past.pop_back() else {
+            return false;
+        };
+        self.future.push(universe.grid().clone());
+        universe.restore(grid, universe.generation() - 1);
+        true
+    }
+
+    /// Replaces the current grid with an edited one, dropping the undone states that no longer follow from it.
+    pub fn edit(&mut self, universe: &mut Universe, grid: Grid) {
+        self.future.clear();
+        universe.restore(grid, universe.generation());
+    }
+
+    fn remember(&mut self, grid: Grid) {
+        if self.history_size == 0 {
+            return;
+        }
+        if self.past.len() == self.history_size {
+            self.past.pop_front();
+        }
+        self.past.push_back(grid);
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{SimulationConfig, Topology};
+
+    fn universe() -> Universe {
+        Universe::from_config(&SimulationConfig { grid_size: 16, topology: Topology::Toroidal, seed: 3, ..Default::default() })
+    }
+
+    #[test]
+    fn test_step_back_restores_earlier_states() {
+        let mut reference = universe();
+        let mut states = vec![reference.grid().clone()];
+        for _ in 0..5 {
+            reference.step_once();
+            states.push(reference.grid().clone());
+        }
+
+        let mut universe = universe();
+        let mut time_travel = TimeTravelState::new(DEFAULT_HISTORY_SIZE);
+        for _ in 0..5 {
+            time_travel.step_forward(&mut universe);
+        }
+        for _ in 0..3 {
+            assert!(time_travel.step_back(&mut universe));
+        }
+
+        assert_eq!(universe.generation(), 2);
+        assert_eq!(universe.grid(), &states[2]);
+
+        for _ in 0..3 {
+            time_travel.step_forward(&mut universe);
+        }
+        assert_eq!(universe.generation(), 5);
+        assert_eq!(universe.grid(), &states[5]);
+        assert!(time_travel.future.is_empty());
+    }
+
+    #[test]
+    fn test_history_size_limits_backward_steps() {
+        let mut universe = universe();
+        let mut time_travel = TimeTravelState::new(2);
+        for _ in 0..4 {
+            time_travel.step_forward(&mut universe);
+        }
+
+        assert!(time_travel.step_back(&mut universe));
+        assert!(time_travel.step_back(&mut universe));
+        assert!(!time_travel.step_back(&mut universe));
+        assert_eq!(universe.generation(), 2);
+    }
+
+    #[test]
+    fn test_edit_drops_undone_states() {
+        let mut universe = universe();
+        let mut time_travel = TimeTravelState::new(DEFAULT_HISTORY_SIZE);
+        time_travel.step_forward(&mut universe);
+        time_travel.step_forward(&mut universe);
+        time_travel.step_back(&mut universe);
+
+        let cleared = vec![vec![false; 16]; 16];
+        time_travel.edit(&mut universe, cleared.clone());
+
+        assert!(time_travel.future.is_empty());
+        assert_eq!(universe.generation(), 1);
+        time_travel.step_forward(&mut universe);
+        assert_eq!(universe.grid(), &cleared);
+    }
+}
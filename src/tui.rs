@@ -0,0 +1,404 @@
+//! Full-screen interface drawn with ratatui, opted into with `--tui`.
+//!
+//! The grid fills a bordered block on the left 70% of the screen and the statistics of the
+//! generation a panel on the right. Unlike the escape sequences of [`crate::display`], the
+//! alternate screen leaves the scroll buffer alone and every frame is redrawn to the current size.
+//!
+//! Space pauses and resumes the run and `q`, Esc or Ctrl+C end it. While paused, a left click
+//! toggles the clicked cell and a right click kills it. The arrow keys scroll grids larger than
+//! the screen, Home goes back to the top left corner and End to the bottom right live cells.
+
+use std::io::{self, Stdout};
+use std::time::Duration;
+
+use ratatui::buffer::Buffer;
+use ratatui::crossterm::cursor::{Hide, Show};
+use ratatui::crossterm::event::{
+    self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind,
+};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::prelude::CrosstermBackend;
+use ratatui::style::{Color, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Widget};
+use ratatui::{Frame, Terminal};
+
+use crate::color::{age_color, CellAges};
+use crate::display::DisplayOptions;
+use crate::game::{count_population, grid_dimensions, grid_entropy, Grid, Universe};
+use crate::grid_ops::{bounding_box, count_live_in_region};
+use crate::terminal::ColorMode;
+
+/// Terminal columns taken by each cell: the glyph and the gap after it.
+const CELL_WIDTH: u16 = 2;
+/// How long a paused interface waits for input before checking again.
+const PAUSED_POLL: Duration = Duration::from_millis(50);
+
+/// Grid of one generation in a bordered block, drawn with the configured glyphs and colors.
+pub struct GameWidget<'a> {
+    pub grid: &'a Grid,
+    pub iteration: u64,
+    pub options: &'a DisplayOptions,
+    pub ages: Option<&'a CellAges>,
+    pub paused: bool,
+    /// Column and row of the grid drawn in the top left corner, clamped with [`clamp_viewport`].
+    pub offset: (usize, usize),
+}
+
+impl Widget for GameWidget<'_> {
+    /// Draws the cells two columns apart like the plain display; cells beyond the area are cut off.
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let title = if self.paused {
+            format!(" Generation {} (paused, click to edit) ", self.iteration)
+        } else {
+            format!(" Generation {} ", self.iteration)
+        };
+        let block = Block::bordered().title(title);
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let glyphs = if self.options.invert_display { self.options.glyphs.swapped() } else { self.options.glyphs };
+        let (x, y) = clamp_viewport(self.offset, self.grid, viewport_cells(inner));
+        for (row, cells) in self.grid.iter().enumerate().skip(y).take(inner.height as usize) {
+            for (col, &alive) in cells.iter().enumerate().skip(x).take(inner.width.div_ceil(CELL_WIDTH) as usize) {
+                let position = (inner.x + CELL_WIDTH * (col - x) as u16, inner.y + (row - y) as u16);
+                if let Some(cell) = buf.cell_mut(position) {
+                    cell.set_char(if alive { glyphs.alive } else { glyphs.dead });
+                    if alive {
+                        cell.set_style(self.live_cell_style(row, col));
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl GameWidget<'_> {
+    /// Style of a live cell: colored by age when ages are tracked, otherwise in the live cell color.
+    fn live_cell_style(&self, row: usize, col: usize) -> Style {
+        let color = match (self.ages, self.options.color) {
+            (Some(ages), _) => {
+                let (age, max_age) = (ages.age(row, col), ages.max_age());
+                match self.options.palette {
+                    Some(palette) => Some(Color::Indexed(palette.color(age, max_age))),
+                    None => {
+                        let (r, g, b) = age_color(age, max_age);
+                        Some(Color::Rgb(r, g, b))
+                    }
+                }
+            }
+            (None, ColorMode::NoColor) => None,
+            (None, ColorMode::Ansi16) => Some(Color::LightGreen),
+            (None, ColorMode::Ansi256) => Some(Color::Indexed(83)),
+            (None, ColorMode::TrueColor) => Some(Color::Rgb(80, 250, 123)),
+        };
+        color.map_or_else(Style::default, |color| Style::default().fg(color))
+    }
+}
+
+/// Statistics of the generation, one per line, with the optional ones the options ask for.
+fn stats_lines(grid: &Grid, iteration: u64, options: &DisplayOptions) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(format!("Iteration: {}", iteration)),
+        Line::from(format!("Population: {}", count_population(grid))),
+    ];
+    if options.show_entropy {
+        lines.push(Line::from(format!("H: {:.3}", grid_entropy(grid))));
+    }
+    if options.show_bbox {
+        lines.push(Line::from(match bounding_box(grid) {
+            Some(bbox) => format!("BBox: {}x{} at ({},{})", bbox.width, bbox.height, bbox.left, bbox.top),
+            None => "BBox: empty".to_string(),
+        }));
+    }
+    if let Some((r1, c1, r2, c2)) = options.region {
+        lines.push(Line::from(format!("Region: {}", count_live_in_region(grid, r1, c1, r2, c2))));
+    }
+    lines
+}
+
+/// Lays out one frame: the grid on the left 70% of the screen, scrolled to `offset`, the statistics on the right.
+///
+/// Returns the area the cells are drawn in, inside the grid's border.
+pub fn draw(
+    frame: &mut Frame,
+    grid: &Grid,
+    iteration: u64,
+    options: &DisplayOptions,
+    ages: Option<&CellAges>,
+    paused: bool,
+    offset: (usize, usize),
+) -> Rect {
+    let [grid_area, stats_area] = Layout::horizontal([Constraint::Percentage(70), Constraint::Percentage(30)]).areas(frame.area());
+    frame.render_widget(GameWidget { grid, iteration, options, ages, paused, offset }, grid_area);
+    let stats = Paragraph::new(stats_lines(grid, iteration, options)).block(Block::bordered().title(" Stats "));
+    frame.render_widget(stats, stats_area);
+    Block::bordered().inner(grid_area)
+}
+
+/// Maps a terminal position to the row and column of the cell drawn there, for a grid drawn from
+/// `(viewport_x, viewport_y)` with each cell `cell_width` columns wide.
+///
+/// Returns `None` left of or above the grid; the caller checks the far edges, which depend on the
+/// grid's size.
+pub fn mouse_to_grid_coords(mouse_col: u16, mouse_row: u16, viewport_x: u16, viewport_y: u16, cell_width: u16) -> Option<(usize, usize)> {
+    let col = mouse_col.checked_sub(viewport_x)?.checked_div(cell_width)?;
+    let row = mouse_row.checked_sub(viewport_y)?;
+    Some((row as usize, col as usize))
+}
+
+/// Number of columns and rows of cells shown in the area, counting a cell cut off at the right edge.
+fn viewport_cells(area: Rect) -> (usize, usize) {
+    (area.width.div_ceil(CELL_WIDTH) as usize, area.height as usize)
+}
+
+/// Moves the `(x, y)` offset back just enough for a viewport of `(width, height)` cells to stay
+/// within the grid; grids smaller than the viewport are always shown from their top left corner.
+pub fn clamp_viewport(offset: (usize, usize), grid: &Grid, viewport_size: (usize, usize)) -> (usize, usize) {
+    let (rows, cols) = grid_dimensions(grid);
+    (offset.0.min(cols.saturating_sub(viewport_size.0)), offset.1.min(rows.saturating_sub(viewport_size.1)))
+}
+
+/// Offset after pressing `key` in a viewport of `(width, height)` cells: the arrow keys move it by
+/// `step` cells, Home to the top left corner and End so the bottom right live cells are in view.
+/// Other keys leave it alone.
+pub fn scroll_viewport(offset: (usize, usize), key: KeyCode, step: usize, grid: &Grid, viewport_size: (usize, usize)) -> (usize, usize) {
+    let (x, y) = offset;
+    let scrolled = match key {
+        KeyCode::Left => (x.saturating_sub(step), y),
+        KeyCode::Right => (x.saturating_add(step), y),
+        KeyCode::Up => (x, y.saturating_sub(step)),
+        KeyCode::Down => (x, y.saturating_add(step)),
+        KeyCode::Home => (0, 0),
+        KeyCode::End => match bounding_box(grid) {
+            Some(bbox) => (
+                (bbox.left + bbox.width).saturating_sub(viewport_size.0),
+                (bbox.top + bbox.height).saturating_sub(viewport_size.1),
+            ),
+            None => (usize::MAX, usize::MAX),
+        },
+        _ => offset,
+    };
+    clamp_viewport(scrolled, grid, viewport_size)
+}
+
+/// What the user asked for while a frame was shown.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TuiAction {
+    /// Go on to the next generation.
+    Continue,
+    /// End the run.
+    Quit,
+}
+
+/// Terminal switched to the alternate screen for the interface, switched back when dropped.
+pub struct Tui {
+    terminal: Terminal<CrosstermBackend<Stdout>>,
+    paused: bool,
+    /// Where the cells were last drawn, to map clicks onto them.
+    viewport: Rect,
+    /// Column and row of the grid shown in the viewport's top left corner.
+    viewport_offset: (usize, usize),
+    /// Number of cells an arrow key scrolls by.
+    scroll_step: usize,
+}
+
+impl Tui {
+    /// Switches to the alternate screen, hides the cursor and starts reading keys and mouse clicks.
+    ///
+    /// The arrow keys scroll by `scroll_step` cells, at least one.
+    pub fn enter(scroll_step: usize) -> io::Result<Self> {
+        enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, EnableMouseCapture, Hide)?;
+        Ok(Tui {
+            terminal: Terminal::new(CrosstermBackend::new(io::stdout()))?,
+            paused: false,
+            viewport: Rect::default(),
+            viewport_offset: (0, 0),
+            scroll_step: scroll_step.max(1),
+        })
+    }
+
+    /// Draws one generation, see [`draw`].
+    pub fn draw(&mut self, grid: &Grid, iteration: u64, options: &DisplayOptions, ages: Option<&CellAges>) -> io::Result<()> {
+        let (paused, offset) = (self.paused, self.viewport_offset);
+        let mut viewport = self.viewport;
+        self.terminal.draw(|frame| viewport = draw(frame, grid, iteration, options, ages, paused, offset))?;
+        self.viewport = viewport;
+        // Keep the offset the widget drew with, which may have moved back after a resize
+        self.viewport_offset = clamp_viewport(offset, grid, viewport_cells(viewport));
+        Ok(())
+    }
+
+    /// Draws the universe's generation and handles the input waiting since the last frame. While
+    /// paused, keeps applying clicks to the universe and redrawing it until resumed or ended.
+    pub fn show(&mut self, universe: &mut Universe, options: &DisplayOptions, ages: Option<&CellAges>) -> io::Result<TuiAction> {
+        self.draw(universe.grid(), universe.generation(), options, ages)?;
+        let mut timeout = Duration::ZERO;
+        loop {
+            while event::poll(timeout)? {
+                if self.handle_event(event::read()?, universe) == TuiAction::Quit {
+                    return Ok(TuiAction::Quit);
+                }
+                timeout = Duration::ZERO;
+            }
+            if !self.paused {
+                return Ok(TuiAction::Continue);
+            }
+            self.draw(universe.grid(), universe.generation(), options, ages)?;
+            timeout = PAUSED_POLL;
+        }
+    }
+
+    fn handle_event(&mut self, event: Event, universe: &mut Universe) -> TuiAction {
+        match event {
+            Event::Key(key) if key.kind == KeyEventKind::Press => match key.code {
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return TuiAction::Quit,
+                KeyCode::Char('q') | KeyCode::Esc => return TuiAction::Quit,
+                KeyCode::Char(' ') => self.paused = !self.paused,
+                key => {
+                    let size = viewport_cells(self.viewport);
+                    self.viewport_offset = scroll_viewport(self.viewport_offset, key, self.scroll_step, universe.grid(), size);
+                }
+            },
+            Event::Mouse(mouse) if self.paused => {
+                let kill = match mouse.kind {
+                    MouseEventKind::Down(MouseButton::Left) => false,
+                    MouseEventKind::Down(MouseButton::Right) => true,
+                    _ => return TuiAction::Continue,
+                };
+                let inside = self.viewport.contains((mouse.column, mouse.row).into());
+                let cell = mouse_to_grid_coords(mouse.column, mouse.row, self.viewport.x, self.viewport.y, CELL_WIDTH);
+                let (x, y) = self.viewport_offset;
+                if let Some((row, col)) = cell.filter(|_| inside).map(|(row, col)| (row + y, col + x)) {
+                    let alive = universe.grid().get(row).and_then(|cells| cells.get(col)).copied();
+                    if let Some(alive) = alive {
+                        universe.set_cell(row, col, !kill && !alive);
+                    }
+                }
+            }
+            _ => {}
+        }
+        TuiAction::Continue
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        // Nothing more can be done if the terminal can't be restored
+        let _ = execute!(io::stdout(), Show, DisableMouseCapture, LeaveAlternateScreen);
+        let _ = disable_raw_mode();
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::initialize_grid;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn test_renders_frame_on_test_backend() {
+        let grid = vec![vec![false, true, false], vec![false, true, false], vec![false, true, false]];
+        let options = DisplayOptions { show_entropy: true, ..DisplayOptions::default() };
+        let mut terminal = Terminal::new(TestBackend::new(80, 8)).unwrap();
+
+        terminal.draw(|frame| {
+            draw(frame, &grid, 7, &options, None, false, (0, 0));
+        }).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let text: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(text.contains("Generation 7"));
+        assert!(text.contains("Population: 3"));
+        assert!(text.contains("H: "));
+        assert_eq!(buffer[(3, 1)].symbol(), "#");
+        assert_eq!(buffer[(1, 1)].symbol(), ".");
+    }
+
+    #[test]
+    fn test_tiny_area_does_not_panic() {
+        let grid = vec![vec![true; 50]; 50];
+        let mut terminal = Terminal::new(TestBackend::new(3, 2)).unwrap();
+
+        terminal.draw(|frame| {
+            draw(frame, &grid, 0, &DisplayOptions::default(), None, true, (0, 0));
+        }).unwrap();
+    }
+
+    #[test]
+    fn test_mouse_to_grid_coords() {
+        assert_eq!(mouse_to_grid_coords(1, 1, 1, 1, 2), Some((0, 0)));
+        assert_eq!(mouse_to_grid_coords(2, 1, 1, 1, 2), Some((0, 0)));
+        assert_eq!(mouse_to_grid_coords(3, 1, 1, 1, 2), Some((0, 1)));
+        assert_eq!(mouse_to_grid_coords(10, 6, 4, 2, 3), Some((4, 2)));
+        assert_eq!(mouse_to_grid_coords(7, 4, 3, 1, 1), Some((3, 4)));
+        assert_eq!(mouse_to_grid_coords(0, 1, 1, 1, 2), None);
+        assert_eq!(mouse_to_grid_coords(1, 0, 1, 1, 2), None);
+        assert_eq!(mouse_to_grid_coords(5, 5, 1, 1, 0), None);
+    }
+
+    #[test]
+    fn test_draw_returns_cell_viewport() {
+        let mut terminal = Terminal::new(TestBackend::new(80, 8)).unwrap();
+        let mut viewport = Rect::default();
+
+        terminal.draw(|frame| viewport = draw(frame, &vec![vec![true]], 0, &DisplayOptions::default(), None, false, (0, 0))).unwrap();
+
+        assert_eq!(viewport, Rect::new(1, 1, 54, 6));
+    }
+
+    #[test]
+    fn test_scrolling_right_stops_at_grid_edge() {
+        let grid = initialize_grid(30, 100);
+        let mut offset = (0, 0);
+
+        for _ in 0..10 {
+            offset = scroll_viewport(offset, KeyCode::Right, 1, &grid, (40, 20));
+        }
+        assert_eq!(offset, (10, 0));
+
+        for _ in 0..100 {
+            offset = scroll_viewport(offset, KeyCode::Right, 1, &grid, (40, 20));
+        }
+        assert_eq!(offset, (60, 0));
+        assert_eq!(scroll_viewport(offset, KeyCode::Down, 25, &grid, (40, 20)), (60, 10));
+        assert_eq!(scroll_viewport(offset, KeyCode::Home, 1, &grid, (40, 20)), (0, 0));
+    }
+
+    #[test]
+    fn test_clamp_viewport() {
+        let grid = initialize_grid(50, 100);
+
+        assert_eq!(clamp_viewport((30, 20), &grid, (40, 20)), (30, 20));
+        assert_eq!(clamp_viewport((90, 45), &grid, (40, 20)), (60, 30));
+        // A viewport larger than the grid always starts at the corner
+        assert_eq!(clamp_viewport((5, 5), &grid, (120, 80)), (0, 0));
+    }
+
+    #[test]
+    fn test_end_scrolls_to_bottom_right_live_cells() {
+        let mut grid = initialize_grid(200, 200);
+        grid[10][20] = true;
+        grid[120][150] = true;
+
+        assert_eq!(scroll_viewport((0, 0), KeyCode::End, 1, &grid, (40, 40)), (111, 81));
+        assert_eq!(scroll_viewport((0, 0), KeyCode::End, 1, &initialize_grid(200, 200), (40, 40)), (160, 160));
+    }
+
+    #[test]
+    fn test_draws_from_offset() {
+        let mut grid = initialize_grid(20, 60);
+        grid[5][30] = true;
+        let mut terminal = Terminal::new(TestBackend::new(80, 8)).unwrap();
+
+        terminal.draw(|frame| {
+            draw(frame, &grid, 0, &DisplayOptions::default(), None, false, (28, 4));
+        }).unwrap();
+
+        assert_eq!(terminal.backend().buffer()[(5, 2)].symbol(), "#");
+    }
+}
@@ -0,0 +1,90 @@
+//! JavaScript bindings of the simulation, built with `make build-wasm`.
+//!
+//! Off the `wasm32` target the bindings are plain Rust, with [`WasmSimulation::get_cells`]
+//! returning a `Vec<u8>` instead of a `Uint8Array`, so they can be tested natively.
+
+use wasm_bindgen::prelude::*;
+
+use crate::game::{count_population, initialize_grid, Topology, Universe};
+use crate::rules::Rules;
+
+/// Square grid evolving under a chosen rule, driven from JavaScript.
+#[wasm_bindgen]
+pub struct WasmSimulation {
+    universe: Universe,
+}
+
+#[wasm_bindgen]
+impl WasmSimulation {
+    /// Creates a `grid_size` x `grid_size` finite grid of dead cells under the given rulestring,
+    /// e.g. `B3/S23`, failing with the parse error when it isn't valid.
+    #[wasm_bindgen(constructor)]
+    pub fn new(grid_size: u32, rules_str: &str) -> Result<WasmSimulation, String> {
+        let rules: Rules = rules_str.parse().map_err(|err| format!("{}", err))?;
+        let size = grid_size as usize;
+        let universe = Universe::new(initialize_grid(size, size), Topology::Finite).with_rules(rules);
+        Ok(WasmSimulation { universe })
+    }
+
+    /// Advances the grid by one generation.
+    pub fn step(&mut self) {
+        self.universe.step_once();
+    }
+
+    /// Cells row by row, `1` for alive and `0` for dead.
+    #[cfg(target_arch = "wasm32")]
+    pub fn get_cells(&self) -> js_sys::Uint8Array {
+        js_sys::Uint8Array::from(&self.cells()[..])
+    }
+
+    /// Cells row by row, `1` for alive and `0` for dead.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn get_cells(&self) -> Vec<u8> {
+        self.cells()
+    }
+
+    /// Brings the cell at the given row and column to life or kills it; cells outside of the grid are ignored.
+    pub fn set_cell(&mut self, row: u32, col: u32, val: bool) {
+        self.universe.set_cell(row as usize, col as usize, val);
+    }
+
+    /// Number of live cells.
+    pub fn live_count(&self) -> u32 {
+        count_population(self.universe.grid()) as u32
+    }
+}
+
+impl WasmSimulation {
+    fn cells(&self) -> Vec<u8> {
+        self.universe.grid().iter().flatten().map(|&cell| u8::from(cell)).collect()
+    }
+}
+
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blinker_oscillates() {
+        let mut simulation = WasmSimulation::new(5, "B3/S23").unwrap();
+        for row in 1..=3 {
+            simulation.set_cell(row, 2, true);
+        }
+
+        simulation.step();
+
+        let cells = simulation.get_cells();
+        assert_eq!(cells.len(), 25);
+        assert_eq!(&cells[10..15], &[0, 1, 1, 1, 0]);
+        assert_eq!(simulation.live_count(), 3);
+        simulation.step();
+        assert_eq!(&simulation.get_cells()[10..15], &[0, 0, 1, 0, 0]);
+    }
+
+    #[test]
+    fn test_rejects_invalid_rules() {
+        assert!(WasmSimulation::new(5, "B9/S").is_err());
+        assert!(WasmSimulation::new(5, "B36/S23").is_ok());
+    }
+}
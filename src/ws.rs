@@ -0,0 +1,110 @@
+//! WebSocket feed of the simulation for web frontends, started with `--ws-port`.
+//!
+//! Every generation is broadcast as a JSON [`Frame`] to all connected clients. Clients only
+//! receive the generations computed after they connect, and a client too slow to keep up skips
+//! the generations it missed.
+
+use std::io;
+use std::net::SocketAddr;
+use std::thread;
+
+use futures_util::SinkExt;
+use serde::{Deserialize, Serialize};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio_tungstenite::tungstenite::Message;
+use tracing::{debug, warn};
+
+use crate::game::{count_population, Grid};
+
+/// Number of frames kept for each client before the oldest ones are skipped.
+const FRAME_BUFFER: usize = 64;
+
+/// Message sent to the clients for every generation.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Frame {
+    pub iter: u64,
+    pub cells: Grid,
+    pub live: usize,
+}
+
+/// Broadcasts the generations of a simulation to every connected WebSocket client.
+#[derive(Clone, Debug)]
+pub struct WsServer {
+    broadcaster: broadcast::Sender<String>,
+}
+
+impl WsServer {
+    /// Creates a server without any clients; see [`WsServer::serve`] and [`WsServer::start`].
+    pub fn new() -> Self {
+        WsServer { broadcaster: broadcast::channel(FRAME_BUFFER).0 }
+    }
+
+    /// Serves the loopback interface on the given port from a background thread, returning once
+    /// the port is bound.
+    pub fn start(port: u16) -> io::Result<(WsServer, SocketAddr)> {
+        let listener = std::net::TcpListener::bind(("127.0.0.1", port))?;
+        listener.set_nonblocking(true)?;
+        let address = listener.local_addr()?;
+        let runtime = tokio::runtime::Runtime::new()?;
+        let server = WsServer::new();
+        let accepting = server.clone();
+        thread::spawn(move || {
+            let result = runtime.block_on(async { accepting.serve(TcpListener::from_std(listener)?).await });
+            if let Err(err) = result {
+                warn!(%err, "WebSocket server stopped");
+            }
+        });
+        Ok((server, address))
+    }
+
+    /// Accepts clients from the listener, forwarding them every frame broadcast after they connect.
+    pub async fn serve(self, listener: TcpListener) -> io::Result<()> {
+        loop {
+            let (stream, peer) = listener.accept().await?;
+            debug!(%peer, "WebSocket client connected");
+            tokio::spawn(forward_frames(stream, self.broadcaster.subscribe()));
+        }
+    }
+
+    /// Sends the grid of the given generation to every connected client, returning how many there are.
+    pub fn broadcast(&self, iteration: u64, grid: &Grid) -> usize {
+        let frame = Frame { iter: iteration, cells: grid.clone(), live: count_population(grid) };
+        let message = serde_json::to_string(&frame).expect("Frames always serialize");
+        self.broadcaster.send(message).unwrap_or(0)
+    }
+
+    /// Number of connected clients.
+    pub fn client_count(&self) -> usize {
+        self.broadcaster.receiver_count()
+    }
+}
+
+impl Default for WsServer {
+    fn default() -> Self {
+        WsServer::new()
+    }
+}
+
+/// Completes the WebSocket handshake and sends the client each frame until it disconnects.
+async fn forward_frames(stream: TcpStream, mut frames: broadcast::Receiver<String>) {
+    let mut socket = match tokio_tungstenite::accept_async(stream).await {
+        Ok(socket) => socket,
+        Err(err) => {
+            debug!(%err, "WebSocket handshake failed");
+            return;
+        }
+    };
+    loop {
+        match frames.recv().await {
+            Ok(frame) => {
+                if socket.send(Message::text(frame)).await.is_err() {
+                    break;
+                }
+            }
+            Err(RecvError::Lagged(skipped)) => debug!(skipped, "WebSocket client fell behind"),
+            Err(RecvError::Closed) => break,
+        }
+    }
+    debug!("WebSocket client disconnected");
+}
@@ -0,0 +1,24 @@
+use std::fs;
+use std::process::Command;
+
+#[test]
+fn numbered_checkpoints_are_written_every_n_generations() {
+    let dir = std::env::temp_dir().join(format!("rusty-game-of-life-checkpoints-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-game-of-life"))
+        .args(["--headless", "--seed", "42", "--grid-size", "20", "--max-iterations", "20"])
+        .args(["--checkpoint-every", "5", "--keep-checkpoints", "2"])
+        .current_dir(&dir)
+        .output()
+        .expect("Failed to run the binary");
+
+    let mut names: Vec<String> =
+        fs::read_dir(&dir).unwrap().map(|entry| entry.unwrap().file_name().into_string().unwrap()).collect();
+    names.sort();
+    fs::remove_dir_all(&dir).unwrap();
+
+    assert!(output.status.success());
+    // Generation 5 was pruned; generation 20 ends the run before it is saved
+    assert_eq!(names, vec!["checkpoint_10.bin", "checkpoint_15.bin"]);
+}
@@ -0,0 +1,83 @@
+use std::process::Command;
+
+fn run(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_rusty-game-of-life"))
+        .args(args)
+        .output()
+        .expect("Failed to run the binary")
+}
+
+#[test]
+fn dry_run_fails_on_missing_pattern() {
+    let output = run(&["--dry-run", "--pattern", "does/not/exist.rle"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Error"));
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Dry run"));
+}
+
+#[test]
+fn dry_run_checks_pattern_offset() {
+    let path = std::env::temp_dir().join(format!("rusty-game-of-life-offset-{}.rle", std::process::id()));
+    std::fs::write(&path, "x = 3, y = 3\nbo$2bo$3o!\n").unwrap();
+    let path = path.to_str().unwrap();
+
+    let inside = run(&["--dry-run", "-g", "10", "--pattern", path, "--pattern-offset", "7,0"]);
+    let overflow = run(&["--dry-run", "-g", "10", "--pattern", path, "--pattern-offset", "8,0"]);
+    let negative = run(&["--dry-run", "-g", "10", "--pattern", path, "--pattern-offset=-1,2"]);
+    std::fs::remove_file(path).unwrap();
+
+    assert!(inside.status.success());
+    assert!(String::from_utf8_lossy(&overflow.stderr).contains("doesn't fit"));
+    assert!(!negative.status.success());
+    assert!(String::from_utf8_lossy(&negative.stderr).contains("isn't a valid offset"));
+}
+
+#[test]
+fn dry_run_prints_plan() {
+    let output = run(&["--dry-run", "--seed", "7", "--grid-size", "12", "--count-only"]);
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("Grid:               12x12"));
+    assert!(stdout.contains("Seed:               7"));
+}
+
+#[test]
+fn dry_run_grows_grid_for_pattern_border() {
+    let grown = run(&["--dry-run", "-g", "5", "--pattern", "glider", "--border-cells", "5"]);
+    let refused = run(&["--dry-run", "-g", "5", "--pattern", "glider", "--border-cells", "5", "--border-policy", "error"]);
+    let roomy = run(&["--dry-run", "-g", "20", "--pattern", "glider", "--border-cells", "5"]);
+
+    assert!(grown.status.success());
+    assert!(String::from_utf8_lossy(&grown.stdout).contains("Grid:               13x13"));
+    assert!(String::from_utf8_lossy(&grown.stderr).contains("growing it"));
+    assert!(!refused.status.success());
+    assert!(String::from_utf8_lossy(&refused.stderr).contains("needs a 13x13 grid"));
+    assert!(String::from_utf8_lossy(&roomy.stdout).contains("Grid:               20x20"));
+}
+
+#[test]
+fn dry_run_reads_cells_patterns() {
+    let path = std::env::temp_dir().join(format!("rusty-game-of-life-glider-{}.cells", std::process::id()));
+    std::fs::write(&path, "!Name: Glider\n.O\n..O\nOOO\n").unwrap();
+
+    let output = run(&["--dry-run", "-g", "10", "--pattern", path.to_str().unwrap()]);
+    std::fs::remove_file(&path).unwrap();
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.contains("Pattern: Glider (3x3)"));
+    assert!(stdout.contains("Initial population: 5"));
+}
+
+#[test]
+fn dry_run_rejects_grid_too_small_to_seed() {
+    let output = run(&["--dry-run", "--grid-size", "3"]);
+    let dense = run(&["--dry-run", "--grid-size", "3", "--density", "0.5"]);
+
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("too small to seed at random"));
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("Dry run"));
+    assert!(dense.status.success());
+}
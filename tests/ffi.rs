@@ -0,0 +1,26 @@
+use rusty_game_of_life::ffi::{grid_free, grid_get, grid_live_count, grid_new, grid_set, grid_step};
+
+#[test]
+fn blinker_returns_after_two_steps() {
+    unsafe {
+        let grid = grid_new(5, 5);
+        for row in 1..=3 {
+            grid_set(grid, row, 2, true);
+        }
+
+        grid_step(grid);
+        assert!(grid_get(grid, 2, 1) && grid_get(grid, 2, 2) && grid_get(grid, 2, 3));
+        assert!(!grid_get(grid, 1, 2));
+
+        grid_step(grid);
+        for row in 0..5 {
+            for col in 0..5 {
+                assert_eq!(grid_get(grid, row, col), col == 2 && (1..=3).contains(&row), "cell ({}, {})", row, col);
+            }
+        }
+        assert_eq!(grid_live_count(grid), 3);
+        assert!(!grid_get(grid, 9, 9));
+
+        grid_free(grid);
+    }
+}
@@ -0,0 +1,43 @@
+use std::process::Command;
+
+#[test]
+fn headless_run_prints_only_json_metrics() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-game-of-life"))
+        .args(["--headless", "--seed", "42", "--max-iterations", "100", "--grid-size", "20"])
+        .output()
+        .expect("Failed to run the binary");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(!stdout.contains('\x1B'), "ANSI escape codes in headless output: {:?}", stdout);
+
+    let metrics: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(metrics["total_iterations"].as_u64().unwrap() <= 100);
+}
+
+#[test]
+fn headless_run_stops_at_max_runtime() {
+    let output = Command::new(env!("CARGO_BIN_EXE_rusty-game-of-life"))
+        .args(["--headless", "--seed", "42", "--grid-size", "20", "--max-runtime", "0"])
+        .output()
+        .expect("Failed to run the binary");
+
+    assert!(output.status.success());
+    let metrics: serde_json::Value = serde_json::from_str(&String::from_utf8(output.stdout).unwrap()).unwrap();
+    assert_eq!(metrics["total_iterations"], 0);
+    assert!(metrics["termination_reason"]["MaxRuntime"].is_object(), "{}", metrics);
+}
+
+#[test]
+fn thread_count_does_not_change_seeded_run() {
+    let summary = |threads: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_rusty-game-of-life"))
+            .args(["--count-only", "--seed", "42", "--max-iterations", "50", "--grid-size", "30", "--threads", threads])
+            .output()
+            .expect("Failed to run the binary");
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert_eq!(summary("1"), summary("4"));
+}
@@ -0,0 +1,47 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Runs the binary in `--pipe` mode with the given input and returns its stdout.
+fn pipe(input: &str) -> String {
+    pipe_with(&[], input)
+}
+
+/// Like [`pipe`], passing extra arguments.
+fn pipe_with(args: &[&str], input: &str) -> String {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_rusty-game-of-life"))
+        .arg("--pipe")
+        .args(args)
+        .env_remove("RGOL_GRID_SIZE")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("Failed to run the binary");
+
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn blinker_returns_after_two_pipes() {
+    let blinker = ".....\n..O..\n..O..\n..O..\n.....\n";
+
+    let once = pipe(blinker);
+    let twice = pipe(&once);
+
+    assert_eq!(once, ".....\n.....\n.OOO.\n.....\n.....\n");
+    assert_eq!(twice, blinker);
+}
+
+#[test]
+fn grid_size_resizes_the_piped_grid() {
+    let blinker = ".....\n..O..\n..O..\n..O..\n.....\n";
+
+    let padded = pipe_with(&["--grid-size", "6"], blinker);
+    let cropped = pipe_with(&["--grid-size", "3"], blinker);
+
+    assert_eq!(padded, "......\n......\n.OOO..\n......\n......\n......\n");
+    // The top left 3x3 keeps only two cells of the blinker, too few to survive
+    assert_eq!(cropped, "...\n...\n...\n");
+}
@@ -0,0 +1,92 @@
+use reqwest::{Client, StatusCode};
+use rusty_game_of_life::server::{router, CreatedSimulation, SimulationState};
+use serde_json::json;
+
+/// Serves the API on a free port in the background, returning its base URL.
+async fn spawn_server() -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router()).await.unwrap() });
+    format!("http://{}", address)
+}
+
+async fn create(client: &Client, base: &str, live_cells: &[(usize, usize)]) -> CreatedSimulation {
+    let response = client
+        .post(format!("{}/simulations", base))
+        .json(&json!({ "grid_size": 5, "live_cells": live_cells }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn stepping_a_blinker_flips_it() {
+    let base = spawn_server().await;
+    let client = Client::new();
+    let created = create(&client, &base, &[(1, 2), (2, 2), (3, 2)]).await;
+    assert_eq!(created.grid_size, 5);
+    assert_eq!(created.rules.to_string(), "B3/S23");
+
+    let url = format!("{}/simulations/{}", base, created.id);
+    let initial: SimulationState = client.get(&url).send().await.unwrap().json().await.unwrap();
+    let stepped: SimulationState = client.post(format!("{}/step", url)).send().await.unwrap().json().await.unwrap();
+
+    assert_eq!(initial.generation, 0);
+    assert_eq!(stepped.generation, 1);
+    assert!(stepped.grid[2][1] && stepped.grid[2][3] && !stepped.grid[1][2]);
+    assert_eq!(stepped.live_count, 3);
+
+    let skipped: SimulationState = client.post(format!("{}/step/3", url)).send().await.unwrap().json().await.unwrap();
+    assert_eq!(skipped.generation, 4);
+    assert_eq!(skipped.grid, initial.grid);
+}
+
+#[tokio::test]
+async fn stepping_a_lone_cell_changes_the_live_count() {
+    let base = spawn_server().await;
+    let client = Client::new();
+    let created = create(&client, &base, &[(2, 2)]).await;
+
+    let url = format!("{}/simulations/{}", base, created.id);
+    let initial: SimulationState = client.get(&url).send().await.unwrap().json().await.unwrap();
+    let stepped: SimulationState = client.post(format!("{}/step", url)).send().await.unwrap().json().await.unwrap();
+
+    assert_eq!(initial.live_count, 1);
+    assert_eq!(stepped.live_count, 0);
+}
+
+#[tokio::test]
+async fn deleted_simulations_are_gone() {
+    let base = spawn_server().await;
+    let client = Client::new();
+    let created = create(&client, &base, &[]).await;
+    let url = format!("{}/simulations/{}", base, created.id);
+
+    assert_eq!(client.delete(&url).send().await.unwrap().status(), StatusCode::NO_CONTENT);
+    assert_eq!(client.get(&url).send().await.unwrap().status(), StatusCode::NOT_FOUND);
+    assert_eq!(client.delete(&url).send().await.unwrap().status(), StatusCode::NOT_FOUND);
+
+    let invalid = client.post(format!("{}/simulations", base)).json(&json!({ "grid_size": 0 })).send().await.unwrap();
+    assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn oversized_requests_are_rejected() {
+    let base = spawn_server().await;
+    let client = Client::new();
+    let simulations = format!("{}/simulations", base);
+
+    let huge = client.post(&simulations).json(&json!({ "grid_size": 1_000_000_000u64 })).send().await.unwrap();
+    assert_eq!(huge.status(), StatusCode::BAD_REQUEST);
+    let outside = client.post(&simulations).json(&json!({ "grid_size": 5, "live_cells": [[2, 5]] })).send().await.unwrap();
+    assert_eq!(outside.status(), StatusCode::BAD_REQUEST);
+
+    let created = create(&client, &base, &[(2, 2)]).await;
+    let url = format!("{}/simulations/{}", base, created.id);
+    let endless = client.post(format!("{}/step/{}", url, u64::MAX)).send().await.unwrap();
+    assert_eq!(endless.status(), StatusCode::BAD_REQUEST);
+    let state: SimulationState = client.get(&url).send().await.unwrap().json().await.unwrap();
+    assert_eq!(state.generation, 0);
+}
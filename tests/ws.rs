@@ -0,0 +1,42 @@
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use rusty_game_of_life::game::{parse_grid, Topology, Universe};
+use rusty_game_of_life::ws::{Frame, WsServer};
+use tokio_tungstenite::tungstenite::Message;
+
+#[tokio::test]
+async fn clients_receive_sequential_generations() {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = WsServer::new();
+    tokio::spawn(server.clone().serve(listener));
+
+    // Broadcast a blinker until the test ends; the client sees whichever generations follow its connection
+    let broadcaster = server.clone();
+    tokio::spawn(async move {
+        let mut universe = Universe::new(parse_grid(".....\n..#..\n..#..\n..#..\n.....").unwrap(), Topology::Finite);
+        loop {
+            broadcaster.broadcast(universe.generation(), universe.grid());
+            universe.step_once();
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    });
+
+    let (mut client, _) = tokio_tungstenite::connect_async(format!("ws://{}", address)).await.unwrap();
+    let mut frames = Vec::new();
+    while frames.len() < 5 {
+        if let Message::Text(text) = client.next().await.unwrap().unwrap() {
+            frames.push(serde_json::from_str::<Frame>(&text).unwrap());
+        }
+    }
+
+    for pair in frames.windows(2) {
+        assert_eq!(pair[1].iter, pair[0].iter + 1);
+    }
+    for frame in &frames {
+        assert_eq!(frame.live, 3);
+        assert!(frame.cells[2][2]);
+        assert_eq!(frame.cells[1][2], frame.iter % 2 == 0);
+    }
+}